@@ -4,6 +4,7 @@
 //! permitindo a orquestração de layouts paralelos ou gravação sequencial direta no workbook.
 
 use polars::prelude::DataFrame;
+use rayon::prelude::*;
 use rust_xlsxwriter::{Workbook, Worksheet};
 
 use crate::{
@@ -12,49 +13,30 @@ use crate::{
 };
 
 /// Estrutura de contexto unificado contendo referências aos DataFrames processados.
+///
+/// Cada DataFrame é associado ao seu `SheetContext`, preservando a ordem das abas.
 pub struct AllData<'a> {
-    pub itens: &'a DataFrame,
-    pub efd_original: &'a DataFrame,
-    pub efd_auditoria: &'a DataFrame,
+    pub sheets: Vec<(&'a DataFrame, SheetContext)>,
 }
 
 impl<'a> AllData<'a> {
     /// Instancia o contêiner a partir de referências aos DataFrames de origem.
-    pub fn new(
-        itens: &'a DataFrame,
-        efd_original: &'a DataFrame,
-        efd_auditoria: &'a DataFrame,
-    ) -> Self {
+    pub fn new(sheets: &'a [(DataFrame, SheetContext)]) -> Self {
         Self {
-            itens,
-            efd_original,
-            efd_auditoria,
+            sheets: sheets.iter().map(|(df, context)| (df, *context)).collect(),
         }
     }
 
     /// Constrói e retorna as planilhas geradas simultaneamente via Rayon (em memória).
     pub fn generate_worksheets_in_parallel(&self) -> JoinResult<Vec<Worksheet>> {
-        let mut res_itens: JoinResult<Vec<Worksheet>> = Ok(Vec::new());
-        let mut res_orig: JoinResult<Vec<Worksheet>> = Ok(Vec::new());
-        let mut res_aud: JoinResult<Vec<Worksheet>> = Ok(Vec::new());
-
-        rayon::scope(|s| {
-            s.spawn(|_| {
-                res_itens = process_sheet_type(self.itens, SheetContext::Itens);
-            });
-            s.spawn(|_| {
-                res_orig = process_sheet_type(self.efd_original, SheetContext::EfdOriginal);
-            });
-            s.spawn(|_| {
-                res_aud = process_sheet_type(self.efd_auditoria, SheetContext::EfdAuditoria);
-            });
-        });
-
-        let mut worksheets = res_itens?;
-        worksheets.extend(res_orig?);
-        worksheets.extend(res_aud?);
-
-        Ok(worksheets)
+        // O `collect` de um iterador paralelo indexado preserva a ordem original das abas.
+        let worksheets: Vec<Vec<Worksheet>> = self
+            .sheets
+            .par_iter()
+            .map(|&(df, context)| process_sheet_type(df, context))
+            .collect::<JoinResult<Vec<_>>>()?;
+
+        Ok(worksheets.into_iter().flatten().collect())
     }
 
     /// Grava as planilhas sequencialmente no Workbook (otimizado para baixo consumo de memória).
@@ -63,19 +45,9 @@ impl<'a> AllData<'a> {
         workbook: &mut Workbook,
         memory_mode: ExcelMemoryMode,
     ) -> JoinResult<()> {
-        process_sheet_type_sequential(workbook, self.itens, SheetContext::Itens, memory_mode)?;
-        process_sheet_type_sequential(
-            workbook,
-            self.efd_original,
-            SheetContext::EfdOriginal,
-            memory_mode,
-        )?;
-        process_sheet_type_sequential(
-            workbook,
-            self.efd_auditoria,
-            SheetContext::EfdAuditoria,
-            memory_mode,
-        )?;
+        for &(df, context) in &self.sheets {
+            process_sheet_type_sequential(workbook, df, context, memory_mode)?;
+        }
         Ok(())
     }
 }
@@ -7,12 +7,35 @@ use chrono::{
 };
 
 use crate::{
-    Arguments, JoinResult, Side::Left, coluna, get_output_as_date, operacoes_de_entrada_ou_saida,
+    Arguments, JoinResult,
+    Side::{Left, Right},
+    coluna, get_output_as_date, operacoes_de_entrada_ou_saida,
 };
 
-/// Colunas temporárias: `Período de Apuração Inicial` e `Período de Apuração Final`.
+/// Janela padrão do crédito extemporâneo (Situação 02): meses antes do Período de
+/// Apuração inicial e meses após o Período de Apuração final.
+pub const MESES_ANTES_DO_PERIODO: u32 = 2;
+pub const MESES_APOS_O_PERIODO: u32 = 1;
+
+/// Prazo de decadência do direito ao crédito (Situação 20): 5 anos.
+pub const PRAZO_DE_DECADENCIA_EM_MESES: u32 = 60;
+
+/// Coluna temporária: data do documento comparada com a janela do Período de Apuração.
+pub const COLUNA_DATA_DE_REFERENCIA: &str = "Data de Referência";
+
+/// Coluna temporária: datas anteriores a este limite estão alcançadas pela decadência.
+pub const COLUNA_LIMITE_DE_DECADENCIA: &str = "Limite de Decadência";
+
+/// Colunas temporárias: `Período de Apuração Inicial`, `Período de Apuração Final`
+/// e `Data de Referência`.
+///
+/// Adicionar estas 3 colunas temporárias para a Situação 02 (regras de glosa).
 ///
-/// Adicionar estas 2 colunas temporárias para analisar_situacao02.
+/// A janela é configurável (`--extemporaneo-meses-antes` e `--extemporaneo-meses-apos`).
+/// Por padrão, a data de emissão da NF-e é comparada com os Períodos de Apuração
+/// mínimo e máximo de todas as linhas (ou `--date-start` e `--date-final`).
+/// Com `--extemporaneo-por-linha`, a data de entrada ou de emissão de cada linha
+/// é comparada com o seu próprio Período de Apuração.
 pub fn adicionar_coluna_periodo_de_apuracao_inicial_e_final(
     lazyframe: LazyFrame,
     args: &Arguments,
@@ -21,8 +44,36 @@ pub fn adicionar_coluna_periodo_de_apuracao_inicial_e_final(
     let pa_ini: &str = "Período de Apuração Inicial";
     let pa_fim: &str = "Período de Apuração Final";
 
-    let dt_start: Option<u32> = args.date_start;
-    let dt_final: Option<u32> = args.date_final;
+    let meses_antes: u32 = args
+        .extemporaneo_meses_antes
+        .unwrap_or(MESES_ANTES_DO_PERIODO);
+    let meses_apos: u32 = args.extemporaneo_meses_apos.unwrap_or(MESES_APOS_O_PERIODO);
+
+    let por_linha: bool = args.extemporaneo_por_linha == Some(true);
+
+    // No modo por linha, o Período de Apuração de cada linha delimita a janela.
+    let (dt_start, dt_final): (Option<u32>, Option<u32>) = if por_linha {
+        (None, None)
+    } else {
+        (args.date_start, args.date_final)
+    };
+
+    let (periodo_inicial, periodo_final): (Expr, Expr) = if por_linha {
+        (col(periodo_de_apuracao), col(periodo_de_apuracao))
+    } else {
+        // O .min() ignorará os nulos que criamos para as somas,
+        // pegando apenas os meses reais (1 a 12).
+        (
+            col(periodo_de_apuracao).min(),
+            col(periodo_de_apuracao).max(),
+        )
+    };
+
+    let data_de_referencia: Expr = if por_linha {
+        data_do_documento()
+    } else {
+        col(coluna(Right, "dia_emissao"))
+    };
 
     // O Polars ignora valores nulos em funções de agregação como .min() e .max().
     // Portanto, ao adicionar as colunas de período inicial e final:
@@ -30,42 +81,76 @@ pub fn adicionar_coluna_periodo_de_apuracao_inicial_e_final(
     let lf_result: LazyFrame = lazyframe
         .with_column(
             when(operacoes_de_entrada_ou_saida()?)
-                // O .min() ignorará os nulos que criamos para as somas,
-                // pegando apenas os meses reais (1 a 12).
-                .then(col(periodo_de_apuracao).min())
+                .then(periodo_inicial)
                 .otherwise(lit(NULL)) // replace by null
                 .alias(pa_ini),
         )
         .with_column(
             when(operacoes_de_entrada_ou_saida()?)
-                .then(col(periodo_de_apuracao).max())
+                .then(periodo_final)
                 .otherwise(lit(NULL)) // replace by null
                 .alias(pa_fim),
         )
         .with_column(
-            // Subtrair 62 dias, aproximadamente dois meses
+            // Subtrair `meses_antes` meses (padrão: 2)
             // col(pa_ini) - chrono::Duration::days(62).lit()
             col(pa_ini).apply(
-                move |col: Column| subtrair_meses(col, 2, dt_start),
+                move |col: Column| subtrair_meses(col, meses_antes, dt_start),
                 // GetOutput::from_type(DataType::Date),
                 get_output_as_date,
             ),
         )
         .with_column(
-            // Adicionar 31 dias, aproximadamente um mês
+            // Adicionar `meses_apos` meses (padrão: 1)
             // col(pa_fim) + chrono::Duration::days(31).lit()
             col(pa_fim).apply(
-                move |col: Column| adicionar_meses(col, 1, dt_final),
+                move |col: Column| adicionar_meses(col, meses_apos, dt_final),
                 // GetOutput::from_type(DataType::Date),
                 get_output_as_date,
             ),
-        );
+        )
+        .with_column(data_de_referencia.alias(COLUNA_DATA_DE_REFERENCIA));
 
     //println!("lazyframe: {:?}", lz.clone().collect()?);
 
     Ok(lf_result)
 }
 
+/// Colunas temporárias: `Data de Referência` e `Limite de Decadência`.
+///
+/// Adicionar estas 2 colunas temporárias para a Situação 20 (regras de glosa):
+/// o direito ao crédito decai em 5 anos, contados da data do documento até
+/// o Período de Apuração em que o crédito é utilizado.
+pub fn adicionar_coluna_limite_de_decadencia(
+    lazyframe: LazyFrame,
+    _args: &Arguments,
+) -> JoinResult<LazyFrame> {
+    let periodo_de_apuracao: &str = coluna(Left, "pa"); // "Período de Apuração",
+
+    let lf_result: LazyFrame = lazyframe
+        .with_column(
+            col(periodo_de_apuracao)
+                .apply(
+                    |col: Column| subtrair_meses(col, PRAZO_DE_DECADENCIA_EM_MESES, None),
+                    get_output_as_date,
+                )
+                .alias(COLUNA_LIMITE_DE_DECADENCIA),
+        )
+        .with_column(data_do_documento().alias(COLUNA_DATA_DE_REFERENCIA));
+
+    Ok(lf_result)
+}
+
+/// Data do documento de cada linha: data de entrada (EFD), data de emissão (EFD)
+/// ou, na ausência de ambas, data de emissão da NF-e.
+fn data_do_documento() -> Expr {
+    coalesce(&[
+        col(coluna(Left, "data_entrada")),
+        col(coluna(Left, "data_emissao")),
+        col(coluna(Right, "dia_emissao")),
+    ])
+}
+
 /// Subtrair numero_de_mes de Series compostas de datas
 pub fn subtrair_meses(
     col: Column,
@@ -147,7 +147,138 @@ pub struct Arguments {
 
     /// Print additional information in the terminal
     #[arg(short('v'), long, required = false)]
-    verbose: Option<bool>,
+    pub verbose: Option<bool>,
+
+    /// Abort when the number of values rejected while reading the CSV files exceeds this limit.
+    ///
+    /// Interromper se o número de valores rejeitados na leitura dos arquivos CSV exceder este limite.
+    ///
+    /// Os valores rejeitados são gravados em "df_linhas_rejeitadas".
+    #[arg(long, required = false)]
+    pub max_rejeitados: Option<usize>,
+
+    /// Run the whole pipeline with the Polars streaming engine.
+    ///
+    /// Executar todo o processamento em lotes (streaming). Os resultados
+    /// reutilizados em mais de uma etapa são executados uma única vez (cache).
+    #[arg(long, required = false)]
+    pub streaming: Option<bool>,
+
+    /// Memory budget in megabytes.
+    ///
+    /// Orçamento de memória (em MB), repassado ao Polars (POLARS_OOC_MEMORY_BUDGET_MB).
+    /// Ativa o modo streaming, que grava dados temporários em disco (`--spill-dir`)
+    /// ao exceder este valor.
+    #[arg(long, required = false)]
+    pub memory_budget: Option<u64>,
+
+    /// Directory used to spill temporary data to disk in streaming mode.
+    ///
+    /// Diretório utilizado pelo Polars para gravar dados temporários
+    /// (POLARS_TEMP_DIR e POLARS_OOC_SPILL_DIR).
+    #[arg(long, required = false)]
+    pub spill_dir: Option<PathBuf>,
+
+    /// Glosa rules file in TOML format.
+    ///
+    /// Arquivo de regras de glosa (TOML). Se não informado, são aplicadas
+    /// as regras padrão (Situações 01 a 20, inclusive 06a, 06b e 06c).
+    #[arg(long, required = false)]
+    pub regras: Option<PathBuf>,
+
+    /// Apply only these glosa situations (comma separated).
+    ///
+    /// Aplicar apenas as situações de glosa informadas.
+    ///
+    /// Exemple: --situacoes 1,2,3,6a,6b
+    #[arg(long, value_delimiter = ',', required = false)]
+    pub situacoes: Option<Vec<String>>,
+
+    /// Never apply these glosa situations (comma separated).
+    ///
+    /// Não aplicar as situações de glosa informadas.
+    ///
+    /// Exemple: --excluir-situacoes 5,13,14
+    #[arg(long, value_delimiter = ',', required = false)]
+    pub excluir_situacoes: Option<Vec<String>>,
+
+    /// File (CSV or TOML) listing the credits reversed by the taxpayer.
+    ///
+    /// Arquivo (CSV ou TOML) com os números de linha (coluna "Linhas") ou os
+    /// pares chave + item dos créditos estornados pelo Contribuinte em resposta
+    /// às Intimações Fiscais.
+    ///
+    /// Se informado, a Situação 13 é aplicada automaticamente.
+    ///
+    /// Específico de cada auditoria: não é gravado no arquivo de configuração.
+    #[arg(long, required = false)]
+    #[serde(skip)]
+    pub creditos_estornados: Option<PathBuf>,
+
+    /// Add one boolean column per glosa situation.
+    ///
+    /// Adicionar uma coluna booleana por situação de glosa
+    /// ("Linha Glosada: Situação 07"), facilitando a filtragem no Excel.
+    #[arg(long, required = false)]
+    pub colunas_por_situacao: Option<bool>,
+
+    /// File (CSV or TOML) with the operations exempted from specific glosa situations.
+    ///
+    /// Arquivo (CSV ou TOML) com as operações (número da linha, Chave + Nº do Item
+    /// ou Chave) retiradas de situações de glosa específicas, com a justificativa.
+    ///
+    /// Específico de cada auditoria: não é gravado no arquivo de configuração.
+    #[arg(long, required = false)]
+    #[serde(skip)]
+    pub excecoes: Option<PathBuf>,
+
+    /// Compute the marginal effect of dropping each glosa rule.
+    ///
+    /// Calcular, no resumo de impacto, o efeito marginal da retirada de cada
+    /// regra: `glosar_bc` é executado novamente sem a regra e o crédito
+    /// resultante é comparado com o da EFD após a auditoria.
+    #[arg(long, required = false)]
+    pub efeito_marginal: Option<bool>,
+
+    /// The taxpayer is dedicated to the resale of goods (commercial activity).
+    ///
+    /// Pessoa jurídica dedicada à atividade de revenda de bens: habilita a
+    /// glosa de combustíveis da frota própria e de transferências entre
+    /// estabelecimentos próprios (Parecer Normativo Cosit nº 5/2018, item 42).
+    #[arg(long, required = false)]
+    pub atividade_comercial: Option<bool>,
+
+    /// Dictionary (TOML) with the terms of the text-based glosa situations.
+    ///
+    /// Dicionário de termos das situações 09, 10, 11 e 14: termos a incluir e
+    /// a excluir por vocabulário. Substitui apenas os vocabulários nele definidos.
+    #[arg(long, required = false)]
+    pub dicionario: Option<PathBuf>,
+
+    /// Extemporaneous credit window: months allowed before the calculation period.
+    ///
+    /// Situação 02: meses admitidos antes do Período de Apuração inicial
+    /// (ou do Período de Apuração da linha, com `--extemporaneo-por-linha`).
+    ///
+    /// The default is 2
+    #[arg(long, required = false)]
+    pub extemporaneo_meses_antes: Option<u32>,
+
+    /// Extemporaneous credit window: months allowed after the calculation period.
+    ///
+    /// Situação 02: meses admitidos após o Período de Apuração final
+    /// (ou do Período de Apuração da linha, com `--extemporaneo-por-linha`).
+    ///
+    /// The default is 1
+    #[arg(long, required = false)]
+    pub extemporaneo_meses_apos: Option<u32>,
+
+    /// Check the extemporaneous credit window line by line.
+    ///
+    /// Situação 02: comparar a data de entrada ou de emissão de cada linha com
+    /// o seu próprio Período de Apuração, em vez dos Períodos mínimo e máximo.
+    #[arg(long, required = false)]
+    pub extemporaneo_por_linha: Option<bool>,
 }
 
 /// confy needs to implement the default Arguments.
@@ -169,6 +300,22 @@ impl default::Default for Arguments {
             print_parquet: Some(false),
             remove_null_columns: Some(true),
             verbose: Some(true),
+            max_rejeitados: None,
+            streaming: Some(false),
+            memory_budget: None,
+            spill_dir: None,
+            regras: None,
+            situacoes: None,
+            excluir_situacoes: None,
+            creditos_estornados: None,
+            colunas_por_situacao: Some(false),
+            excecoes: None,
+            efeito_marginal: Some(false),
+            atividade_comercial: Some(false),
+            dicionario: None,
+            extemporaneo_meses_antes: Some(2),
+            extemporaneo_meses_apos: Some(1),
+            extemporaneo_por_linha: Some(false),
         }
     }
 }
@@ -190,6 +337,9 @@ impl Arguments {
     /// Get configuration file.
     ///
     /// A new configuration file is created with default values if none exists.
+    ///
+    /// Os arquivos específicos de cada auditoria (`creditos_estornados` e `excecoes`)
+    /// não são lidos nem gravados.
     fn get_config_file(mut self, app_name: &str) -> JoinResult<Self> {
         let config_file: Arguments = confy::load(app_name, None)?;
 
@@ -203,6 +353,28 @@ impl Arguments {
         self.remove_null_columns = self.remove_null_columns.or(config_file.remove_null_columns);
         self.verbose = self.verbose.or(config_file.verbose);
         self.memory_mode = self.memory_mode.or(config_file.memory_mode);
+        self.max_rejeitados = self.max_rejeitados.or(config_file.max_rejeitados);
+        self.streaming = self.streaming.or(config_file.streaming);
+        self.memory_budget = self.memory_budget.or(config_file.memory_budget);
+        self.spill_dir = self.spill_dir.or(config_file.spill_dir);
+        self.regras = self.regras.or(config_file.regras);
+        self.situacoes = self.situacoes.or(config_file.situacoes);
+        self.excluir_situacoes = self.excluir_situacoes.or(config_file.excluir_situacoes);
+        self.colunas_por_situacao = self
+            .colunas_por_situacao
+            .or(config_file.colunas_por_situacao);
+        self.efeito_marginal = self.efeito_marginal.or(config_file.efeito_marginal);
+        self.atividade_comercial = self.atividade_comercial.or(config_file.atividade_comercial);
+        self.dicionario = self.dicionario.or(config_file.dicionario);
+        self.extemporaneo_meses_antes = self
+            .extemporaneo_meses_antes
+            .or(config_file.extemporaneo_meses_antes);
+        self.extemporaneo_meses_apos = self
+            .extemporaneo_meses_apos
+            .or(config_file.extemporaneo_meses_apos);
+        self.extemporaneo_por_linha = self
+            .extemporaneo_por_linha
+            .or(config_file.extemporaneo_por_linha);
 
         Ok(self)
     }
@@ -257,6 +429,22 @@ mod tests {
             print_parquet: Some(false),
             remove_null_columns: Some(true),
             verbose: Some(true),
+            max_rejeitados: None,
+            streaming: Some(false),
+            memory_budget: None,
+            spill_dir: None,
+            regras: None,
+            situacoes: None,
+            excluir_situacoes: None,
+            creditos_estornados: None,
+            colunas_por_situacao: Some(false),
+            excecoes: None,
+            efeito_marginal: Some(false),
+            atividade_comercial: Some(false),
+            dicionario: None,
+            extemporaneo_meses_antes: Some(2),
+            extemporaneo_meses_apos: Some(1),
+            extemporaneo_por_linha: Some(false),
         };
 
         let config_file: Arguments = Arguments {
@@ -276,6 +464,22 @@ mod tests {
             print_parquet: Some(false),
             remove_null_columns: Some(true),
             verbose: Some(true),
+            max_rejeitados: None,
+            streaming: Some(false),
+            memory_budget: None,
+            spill_dir: None,
+            regras: None,
+            situacoes: None,
+            excluir_situacoes: None,
+            creditos_estornados: None,
+            colunas_por_situacao: Some(false),
+            excecoes: None,
+            efeito_marginal: Some(false),
+            atividade_comercial: Some(false),
+            dicionario: None,
+            extemporaneo_meses_antes: Some(2),
+            extemporaneo_meses_apos: Some(1),
+            extemporaneo_por_linha: Some(false),
         };
 
         let mut args1 = Arguments::default();
@@ -296,4 +500,33 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    /// `cargo test -- --show-output test_situacoes_no_arquivo_de_configuracao`
+    fn test_situacoes_no_arquivo_de_configuracao() -> JoinResult<()> {
+        let args = Arguments {
+            situacoes: Some(vec!["13".to_string(), "6a".to_string()]),
+            excluir_situacoes: Some(vec!["05".to_string()]),
+            creditos_estornados: Some("estornos.csv".into()),
+            excecoes: Some("excecoes.toml".into()),
+            ..Arguments::default()
+        };
+
+        let toml: String = toml::to_string_pretty(&args)?;
+        println!("{toml}");
+
+        // Ida e volta: as situações selecionadas são gravadas e lidas novamente.
+        let config_file: Arguments = toml::from_str(&toml)?;
+        assert_eq!(config_file.situacoes, args.situacoes);
+        assert_eq!(config_file.excluir_situacoes, args.excluir_situacoes);
+
+        // Os arquivos específicos de cada auditoria não são gravados.
+        for chave in ["creditos_estornados", "excecoes"] {
+            assert!(!toml.contains(chave), "'{chave}' gravado na configuração");
+        }
+        assert_eq!(config_file.creditos_estornados, None);
+        assert_eq!(config_file.excecoes, None);
+
+        Ok(())
+    }
 }
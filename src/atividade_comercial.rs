@@ -0,0 +1,146 @@
+//! # Despesas de Frota e Fretes nas Transferências na Atividade Comercial (Situação 19)
+//!
+//! Parecer Normativo Cosit nº 5, de 17 de dezembro de 2018:
+//!
+//! > 42. Em razão disso, exemplificativamente, não constituem insumos geradores de
+//! > créditos para pessoas jurídicas dedicadas à atividade de revenda de bens:
+//! > a) combustíveis e lubrificantes utilizados em veículos próprios de entrega de
+//! > mercadorias; b) transporte de mercadorias entre centros de distribuição próprios;
+//! > c) embalagens para transporte das mercadorias; etc.
+//!
+//! A Situação 19 trata das alíneas "a" e "b" (a alínea "c" corresponde à
+//! Situação 14) e é habilitada quando o Contribuinte é dedicado à atividade de
+//! revenda de bens (`--atividade-comercial`):
+//!
+//! - combustíveis e lubrificantes: NCM das posições 27.10 (óleos de petróleo,
+//!   inclusive lubrificantes) e 27.11 (gás de petróleo), as mesmas da Incidência
+//!   Monofásica (EFD ou NF-e). Energia elétrica (27.16), carvão e asfalto não
+//!   são combustíveis de veículos;
+//! - transporte entre estabelecimentos próprios: serviço de transporte (CFOP da
+//!   EFD ou do CT-e de final 351 a 360 ou 932) cujo remetente e destinatário das
+//!   mercadorias transportadas possuem o CNPJ Base do Contribuinte.
+//!
+//! A alínea "b" alcança o frete, e não as mercadorias transferidas: os CFOPs de
+//! transferência de mercadorias (ex.: 1152, 2152, 1409) não são considerados.
+
+use polars::prelude::*;
+
+use crate::{
+    Arguments, JoinError, JoinResult,
+    Side::{Left, Right},
+    ToLiteralListExpr, coluna,
+};
+
+/// Coluna temporária: combustíveis e lubrificantes (NCM das posições 27.10 e 27.11).
+pub const COLUNA_COMBUSTIVEL: &str = "Combustível ou Lubrificante Temp";
+
+/// Coluna temporária: transporte de mercadorias entre estabelecimentos próprios.
+pub const COLUNA_FRETE_NA_TRANSFERENCIA: &str =
+    "Frete na Transferência entre Estabelecimentos Temp";
+
+/// Três últimos dígitos dos CFOPs de serviço de transporte:
+/// aquisição (1351 a 1360, 2351 a 2360) e prestação (5351 a 5360, 6351 a 6360, 5932, 6932).
+const SUFIXOS_DE_TRANSPORTE: [i64; 11] = [351, 352, 353, 354, 355, 356, 357, 358, 359, 360, 932];
+
+/// Adiciona as colunas temporárias da Situação 19.
+pub fn marcar_despesas_de_revenda(lazyframe: LazyFrame, args: &Arguments) -> JoinResult<LazyFrame> {
+    if args.atividade_comercial != Some(true) {
+        return Err(JoinError::InvalidGlosaRule {
+            id: "19".to_string(),
+            motivo: "informe a atividade de revenda de bens (--atividade-comercial)".to_string(),
+        });
+    }
+
+    // NCM das posições 27.10 e 27.11: óleos de petróleo (combustíveis e lubrificantes)
+    // e gás de petróleo. Exemplo: "2710.19.21" ou "27101921".
+    let combustivel = |nome: &str| -> Expr {
+        col(nome)
+            .str()
+            .contains(lit(r"^\D*271[01]"), false)
+            .fill_null(lit(false))
+    };
+
+    let sufixos: Series = Series::new("transporte".into(), SUFIXOS_DE_TRANSPORTE);
+    let transporte = |nome: &str| -> JoinResult<Expr> {
+        Ok((col(nome).cast(DataType::Int64) % lit(1000))
+            .is_in(sufixos.to_list_expr()?, true)
+            .fill_null(lit(false)))
+    };
+
+    // Remetente e destinatário das mercadorias transportadas (CT-e): o próprio Contribuinte.
+    let contribuinte: Expr = col("CNPJ Base do Contribuinte");
+    let entre_estabelecimentos_proprios: Expr = col("CNPJ Base do Remetente")
+        .eq(contribuinte.clone())
+        .and(col("CNPJ Base do Destinatário").eq(contribuinte))
+        .fill_null(lit(false));
+
+    let lazyframe: LazyFrame = lazyframe.with_columns([
+        combustivel(coluna(Left, "ncm"))
+            .or(combustivel(coluna(Right, "ncm")))
+            .alias(COLUNA_COMBUSTIVEL),
+        transporte(coluna(Left, "cfop"))?
+            .or(transporte(coluna(Right, "cfop"))?)
+            .and(entre_estabelecimentos_proprios)
+            .alias(COLUNA_FRETE_NA_TRANSFERENCIA),
+    ]);
+
+    Ok(lazyframe)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_atividade_comercial
+#[cfg(test)]
+mod tests_atividade_comercial {
+    use super::*;
+
+    #[test]
+    /// `cargo test -- --show-output test_despesas_de_revenda`
+    fn test_despesas_de_revenda() -> JoinResult<()> {
+        // Linha 1: óleo diesel. Linha 2: frete entre estabelecimentos próprios (EFD 1353).
+        // Linha 3: frete na compra (remetente terceiro). Linha 4: lubrificante informado
+        // apenas na NF-e. Linha 5: transferência da própria mercadoria (NF-e 6152), e não
+        // frete. Linha 6: GLP. Linha 7: frete entre estabelecimentos próprios (CT-e 6352).
+        let lazyframe = df! {
+            coluna(Left, "ncm") => [Some("2710.19.21"), None, None, None, Some("8708.99.90"), None, None],
+            coluna(Right, "ncm") => [Some("27101921"), None, None, Some("27101932"), None, Some("27111910"), None],
+            coluna(Left, "cfop") => [Some(1653i64), Some(1353), Some(2353), Some(1102), Some(2152), Some(1653), None],
+            coluna(Right, "cfop") => [Some(5656i64), Some(5353), Some(6353), None, Some(6152), Some(5656), Some(6352)],
+            "CNPJ Base do Contribuinte" => ["12345678"; 7],
+            "CNPJ Base do Remetente" => [None, Some("12345678"), Some("87654321"), None, Some("12345678"), None, Some("12345678")],
+            "CNPJ Base do Destinatário" => [None, Some("12345678"), Some("12345678"), None, Some("12345678"), None, Some("12345678")],
+        }?
+        .lazy();
+
+        // Sem o indicador de atividade comercial, a situação não é aplicável.
+        assert!(marcar_despesas_de_revenda(lazyframe.clone(), &Arguments::default()).is_err());
+
+        let args = Arguments {
+            atividade_comercial: Some(true),
+            ..Arguments::default()
+        };
+
+        let df = marcar_despesas_de_revenda(lazyframe, &args)?.collect()?;
+        println!("df: {df}");
+
+        let combustivel: Vec<Option<bool>> =
+            df.column(COLUNA_COMBUSTIVEL)?.bool()?.iter().collect();
+        let frete: Vec<Option<bool>> = df
+            .column(COLUNA_FRETE_NA_TRANSFERENCIA)?
+            .bool()?
+            .iter()
+            .collect();
+
+        let sim = |linhas: &[usize]| -> Vec<Option<bool>> {
+            (1..=7).map(|linha| Some(linhas.contains(&linha))).collect()
+        };
+
+        assert_eq!(combustivel, sim(&[1, 4, 6]));
+        assert_eq!(frete, sim(&[2, 7]));
+
+        Ok(())
+    }
+}
@@ -311,7 +311,7 @@ impl MyColumn {
 
     // Middle
 
-    pub fn set_columns_middle() -> [MyColumn; 2] {
+    pub fn set_columns_middle() -> [MyColumn; 4] {
         let side = Side::Middle;
         [
             MyColumn {
@@ -326,12 +326,24 @@ impl MyColumn {
                 name: "Glosar Base de Cálculo de PIS/PASEP e COFINS",
                 dtype: DataType::String,
             },
+            MyColumn {
+                side,
+                nick: "situacoes",
+                name: "Situações de Glosa",
+                dtype: DataType::List(Box::new(DataType::String)),
+            },
+            MyColumn {
+                side,
+                nick: "valor_glosado",
+                name: "Valor Glosado da Base de Cálculo",
+                dtype: DataType::Float64,
+            },
         ]
     }
 
     // Right
 
-    pub fn set_columns_right() -> [MyColumn; 64] {
+    pub fn set_columns_right() -> [MyColumn; 69] {
         let side = Side::Right;
         [
             MyColumn {
@@ -701,6 +713,36 @@ impl MyColumn {
                 name: "Incidência Monofásica",
                 dtype: DataType::String,
             },
+            MyColumn {
+                side,
+                nick: "suspensao",
+                name: "Suspensão",
+                dtype: DataType::String,
+            },
+            MyColumn {
+                side,
+                nick: "zona_franca",
+                name: "Zona Franca",
+                dtype: DataType::String,
+            },
+            MyColumn {
+                side,
+                nick: "cbs_ibs_aliquota_zero",
+                name: "CBS/IBS Alíquota Zero",
+                dtype: DataType::String,
+            },
+            MyColumn {
+                side,
+                nick: "cbs_ibs_aliquota_reduzida",
+                name: "CBS/IBS Alíquota Reduzida",
+                dtype: DataType::String,
+            },
+            MyColumn {
+                side,
+                nick: "cbs_ibs_credito_presumido",
+                name: "CBS/IBS Crédito Presumido",
+                dtype: DataType::String,
+            },
             MyColumn {
                 side,
                 nick: "cnpj_base_contribuinte",
@@ -841,6 +883,32 @@ pub fn coluna(side: Side, nick: &str) -> &str {
     }
 }
 
+/// Get MyColumn name from NAMES.get(&key) without panicking.
+///
+/// Returns `None` if the key (side, nick) does not exist.
+pub fn obter_coluna(side: Side, nick: &str) -> Option<&'static str> {
+    KEY_NAME.get(&(side, nick)).copied()
+}
+
+/// Prefixo das colunas com o valor glosado por regra (ex.: "Valor Glosado: Situação 04").
+pub const PREFIXO_VALOR_GLOSADO: &str = "Valor Glosado: Situação ";
+
+/// Prefixo das colunas booleanas opcionais (`--colunas-por-situacao`) que indicam
+/// as linhas glosadas por regra (ex.: "Linha Glosada: Situação 07").
+pub const PREFIXO_LINHA_GLOSADA: &str = "Linha Glosada: Situação ";
+
+/// Prefixo das colunas com a justificativa das exceções aplicadas (`--excecoes`)
+/// por regra (ex.: "Exceção Aplicada: Situação 07").
+pub const PREFIXO_EXCECAO_APLICADA: &str = "Exceção Aplicada: Situação ";
+
+/// Colunas de glosa criadas dinamicamente (uma por regra), posicionadas
+/// após as colunas do lado `Middle`.
+pub fn is_coluna_de_glosa(name: &str) -> bool {
+    name.starts_with(PREFIXO_VALOR_GLOSADO)
+        || name.starts_with(PREFIXO_LINHA_GLOSADA)
+        || name.starts_with(PREFIXO_EXCECAO_APLICADA)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -915,7 +983,7 @@ mod tests {
         assert_eq!(KEY_NAME[&(Left, "count_lines")], "Linhas EFD");
         assert_eq!(KEY_NAME[&(Right, "count_lines")], "Linhas NFE");
         assert_eq!(KEY_NAME.get(&(Right, "count_lines")), Some(&"Linhas NFE"));
-        assert_eq!(columns_len, 44 + 2 + 64);
+        assert_eq!(columns_len, 44 + 4 + 69);
     }
 
     #[test]
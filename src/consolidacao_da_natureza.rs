@@ -2,11 +2,15 @@ use polars::{datatypes::DataType, prelude::*};
 use std::ops::Neg;
 
 use crate::{
-    ExprExtension, JoinResult, LazyFrameExtension, RateioDosCreditos, Side::Left,
+    ExprExtension, JoinResult, LazyFrameExtension, RateioDosCreditos,
+    Side::Left,
     ToLiteralListExpr, cfop_de_exportacao_expansivo, cfop_de_exportacao_restritivo, coluna,
     cst_50_a_66, cst_de_receita_bruta, csts, csts_nao_tributados, entrada_de_credito,
     get_cnpj_base_expr, operacoes_de_ajustes_ou_descontos, operacoes_de_saida,
     receita_bruta_cumulativa, receita_bruta_nao_cumulativa, receita_nao_nula,
+    regime_fiscal::{
+        adicionar_coluna_de_zona_franca, legislacao_suspensao::NCM_COM_SUSPENSAO_VEDADA_NA_REVENDA,
+    },
     saida_de_receita_bruta,
 };
 
@@ -25,6 +29,8 @@ pub fn obter_consolidacao_nat(dataframe: &DataFrame, auditar: bool) -> JoinResul
 
     let lazyframe: LazyFrame = dataframe.clone().lazy();
 
+    let lazyframe: LazyFrame = obter_coluna_de_zona_franca(lazyframe)?;
+
     let lazyframe: LazyFrame = selecionar_colunas_apos_filtros(lazyframe, auditar)?;
 
     let lazyframe: LazyFrame = groupby_and_agg_values(lazyframe)?;
@@ -57,7 +63,19 @@ pub fn obter_consolidacao_nat(dataframe: &DataFrame, auditar: bool) -> JoinResul
 
     let lazyframe: LazyFrame = rename_columns(lazyframe)?;
 
-    Ok(lazyframe.collect()?)
+    Ok(lazyframe.coletar()?)
+}
+
+/// Coluna "Zona Franca", adicionada por `glosar_bc`.
+///
+/// Na consolidação anterior à auditoria, a coluna ainda não existe e é obtida
+/// com a mesma função (`adicionar_coluna_de_zona_franca`).
+fn obter_coluna_de_zona_franca(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
+    if lazyframe.clone().collect_schema()?.contains("Zona Franca") {
+        Ok(lazyframe)
+    } else {
+        adicionar_coluna_de_zona_franca(lazyframe)
+    }
 }
 
 /// Reter apenas as colunas de interesse.
@@ -96,7 +114,7 @@ fn selecionar_colunas_apos_filtros(lazyframe: LazyFrame, auditar: bool) -> JoinR
     //let registros_selecionados = col(reg).str().contains(pattern, false);
 
     // Selecionar colunas nesta ordem
-    let selected: [Expr; 19] = [
+    let selected: [Expr; 20] = [
         col("CNPJ Base"),
         col("Ano do Período de Apuração"),
         col("Trimestre do Período de Apuração"),
@@ -116,6 +134,10 @@ fn selecionar_colunas_apos_filtros(lazyframe: LazyFrame, auditar: bool) -> JoinR
         col("RecBrutaNCumulativa"),
         col("RecBrutaCumulativa"),
         col("RecBrutaTotal"),
+        // Operação destinada à Zona Franca de Manaus ou a Área de Livre Comércio
+        col("Zona Franca")
+            .is_not_null()
+            .alias("Venda para Zona Franca"),
     ];
 
     let lazy_filtered: LazyFrame = lazyframe
@@ -226,8 +248,7 @@ fn selecionar_colunas_apos_filtros(lazyframe: LazyFrame, auditar: bool) -> JoinR
         */
         .with_column(get_cnpj_base_expr(contribuinte_cnpj).alias("CNPJ Base"))
         .select(&selected)
-        .collect()? // Executar procedimento para reduzir tamanho do dataframe
-        .lazy(); // Lazy operations don’t execute until we call collect.
+        .cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(lazy_filtered)
 }
@@ -277,19 +298,29 @@ fn groupby_and_agg_values(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
     // 1. RbnTrmi: Apenas CSTs 1, 2, 3, 5
     let filter_a = csts(range_a)?;
 
+    // Vendas destinadas à Zona Franca de Manaus ou a Área de Livre Comércio (coluna
+    // "Zona Franca"): receitas com alíquota zero no mercado interno, e não de exportação.
+    let zona_franca = csts(range_b)?
+        .or(csts(range_c)?)
+        .and(col("Venda para Zona Franca"));
+
     // 2. RbnNtmi (Não Tributada no Mercado Interno):
     // - CSTs 4, 6, 7, 9, 49 quando NÃO atenderem ao critério de exportação restritivo
     // - OU CST 8 quando NÃO atender ao critério de exportação expansivo
+    // - OU vendas destinadas à ZFM ou ALC
     let filter_b = csts(range_b)?
         .and(cfop_de_exportacao_restritivo()?.not())
-        .or(csts(range_c)?.and(cfop_de_exportacao_expansivo()?.not()));
+        .or(csts(range_c)?.and(cfop_de_exportacao_expansivo()?.not()))
+        .or(zona_franca.clone());
 
     // 3. RbnExpo (Receita de Exportação):
     // - CSTs 4, 6, 7, 9, 49 quando atenderem ao critério de exportação restritivo
     // - OU CST 8 quando atender ao critério de exportação expansivo
+    // - exceto vendas destinadas à ZFM ou ALC
     let filter_c = csts(range_b)?
         .and(cfop_de_exportacao_restritivo()?)
-        .or(csts(range_c)?.and(cfop_de_exportacao_expansivo()?));
+        .or(csts(range_c)?.and(cfop_de_exportacao_expansivo()?))
+        .and(zona_franca.not());
 
     let condition_a = filter_a.or(col("RBNC_Tributada").is_not_null());
     let condition_b = filter_b.or(col("RBNC_NTributada").is_not_null());
@@ -374,8 +405,7 @@ fn groupby_and_agg_values(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
         .with_columns([when(operacoes_de_ajustes_ou_descontos()?)
             .then(col(valor_item).alias(valor_bc))
             .otherwise(col(valor_bc))])
-        .collect()? // Executar procedimento para reduzir tamanho do dataframe
-        .lazy(); // Lazy operations don’t execute until we call collect.
+        .cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(lazy_groupby)
 }
@@ -414,8 +444,7 @@ fn replicar_linha_de_soma_da_receita(lazyframe: LazyFrame) -> JoinResult<LazyFra
                     .as_expr()
                     .over(discrimination_window)?,
             )])
-        .collect()? // Executar procedimento para reduzir tamanho do dataframe
-        .lazy(); // Lazy operations don’t execute until we call collect.
+        .cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(lazyframe)
 }
@@ -429,8 +458,7 @@ fn ratear_bc_dos_creditos_conforme_receita_segregada(
     // Aplica o lote unificado de expressões gerado pela struct
     let lazyframe: LazyFrame = lazyframe
         .with_columns(rateador.gerar_colunas_rateio()?)
-        .collect()? // Executar procedimento para reduzir tamanho do dataframe
-        .lazy();
+        .cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(lazyframe)
 }
@@ -534,9 +562,7 @@ fn analisar_operacoes_de_saida(
     }
 
     // https://docs.rs/polars/latest/polars/prelude/fn.concat.html
-    let lazy_total: LazyFrame = concat(partes, union_args)?
-        .collect()? // Executar procedimento para reduzir tamanho do dataframe
-        .lazy(); // Lazy operations don’t execute until we call collect.
+    let lazy_total: LazyFrame = concat(partes, union_args)?.cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(lazy_total)
 }
@@ -546,13 +572,17 @@ fn analisar_debitos_omitidos(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
     let ncm: &str = coluna(Left, "ncm");
 
     // NCM 2309.90.xx ou 230990xx
-    let pattern: Expr = lit(r"^\D*2309\.?90"); // regex
+    let pattern: Expr = lit(NCM_COM_SUSPENSAO_VEDADA_NA_REVENDA); // regex
     let ncm_2309: Expr = col(ncm).str().contains(pattern, false);
 
     // Instrução Normativa RFB nº 2121/2022 (atualmente em vigor), em seu artigo 569:
     // II - preparações classificadas no código 2309.90 da Tipi
     // É vedada a suspensão quando a aquisição for destinada à revenda.
     // Estas operações devem ser tributadas: revenda de mercadorias de NCM 2309.90
+    //
+    // Os débitos permanecem agrupados por CST: as vendas com suspensão (CST 09), cuja
+    // desoneração depende da destinação do bem (ver regime_fiscal::legislacao_suspensao),
+    // são distinguidas das vendas com alíquota zero (CST 06), não prevista para o NCM 2309.90.
 
     let debitos_omitidos_ncm_2309: LazyFrame = lazyframe
         .filter(operacoes_de_saida()?)
@@ -607,8 +637,7 @@ fn analisar_debitos_omitidos(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
             // We do the exact same thing for the "RBNC_Exportação" column.
             lit(NULL).cast(DataType::Float64).alias("RBNC_Exportação"),
         ])
-        .collect()? // Executar procedimento para reduzir tamanho do dataframe
-        .lazy(); // Lazy operations don’t execute until we call collect.
+        .cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(debitos_omitidos_ncm_2309)
 }
@@ -654,8 +683,7 @@ fn remover_colunas_temporarias(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
             col("ReceitaBrutaTotal").sum(),
         ])
         .drop_columns(&colunas_temporarias)?
-        .collect()? // Executar procedimento para reduzir tamanho do dataframe
-        .lazy(); // Lazy operations don’t execute until we call collect.
+        .cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(lazy)
 }
@@ -694,9 +722,7 @@ fn adicionar_valores_trimestrais(
         ]);
 
     // https://docs.rs/polars/latest/polars/prelude/fn.concat.html
-    let lazy_total: LazyFrame = concat(&[lazyframe, lazyframe_trimestral], union_args)?
-        .collect()? // Executar procedimento para reduzir tamanho do dataframe
-        .lazy(); // Lazy operations don’t execute until we call collect.
+    let lazy_total: LazyFrame = concat(&[lazyframe, lazyframe_trimestral], union_args)?.cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(lazy_total)
 }
@@ -736,9 +762,8 @@ fn adicionar_linhas_de_soma_da_bc_dos_creditos(
         ]);
 
     // https://docs.rs/polars/latest/polars/prelude/fn.concat.html
-    let lazy_total: LazyFrame = concat(&[lazyframe, linha_de_soma_da_bc_dos_creditos], union_args)?
-        .collect()? // Executar procedimento para reduzir tamanho do dataframe
-        .lazy(); // Lazy operations don’t execute until we call collect.
+    let lazy_total: LazyFrame =
+        concat(&[lazyframe, linha_de_soma_da_bc_dos_creditos], union_args)?.cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(lazy_total)
 }
@@ -807,9 +832,7 @@ fn adicionar_linhas_de_apuracao_de_pis(
 
     // https://docs.rs/polars/latest/polars/prelude/fn.concat.html
     let lazy_total: LazyFrame =
-        concat(&[lazyframe, lazy_credito_pis, lazy_debitos_pis], union_args)?
-            .collect()? // Executar procedimento para reduzir tamanho do dataframe
-            .lazy(); // Lazy operations don’t execute until we call collect.
+        concat(&[lazyframe, lazy_credito_pis, lazy_debitos_pis], union_args)?.cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(lazy_total)
 }
@@ -872,8 +895,7 @@ fn adicionar_linhas_de_apuracao_de_cofins(
         &[lazyframe, lazy_credito_cofins, lazy_debitos_cofins],
         union_args,
     )?
-    .collect()? // Executar procedimento para reduzir tamanho do dataframe
-    .lazy(); // Lazy operations don’t execute until we call collect.
+    .cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(lazy_total)
 }
@@ -921,9 +943,8 @@ fn adicionar_bc_dos_creditos_valor_total(
         ]);
 
     // https://docs.rs/polars/latest/polars/prelude/fn.concat.html
-    let lazy_total: LazyFrame = concat(&[lazyframe, bc_dos_creditos_valor_total], union_args)?
-        .collect()? // Executar procedimento para reduzir tamanho do dataframe
-        .lazy(); // Lazy operations don’t execute until we call collect.
+    let lazy_total: LazyFrame =
+        concat(&[lazyframe, bc_dos_creditos_valor_total], union_args)?.cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(lazy_total)
 }
@@ -979,9 +1000,7 @@ fn adicionar_linhas_credito_apos_descontos(
     let disponivel_pis = criar_linha_disponivel(filter_pis, 221); // 221: Disponível PIS
     let disponivel_cofins = criar_linha_disponivel(filter_cofins, 225); // 225: Disponível COFINS
 
-    let total = concat(&[lazyframe, disponivel_pis, disponivel_cofins], union_args)?
-        .collect()? // Executar procedimento para reduzir tamanho do dataframe
-        .lazy(); // Lazy operations don’t execute until we call collect.
+    let total = concat(&[lazyframe, disponivel_pis, disponivel_cofins], union_args)?.cache(); // Reutilizado na etapa seguinte: executado uma única vez
 
     Ok(total)
 }
@@ -1043,7 +1062,7 @@ fn dicionar_saldo_passivel_de_ressarcimento(
         ]);
 
     // Concatena o dataframe original com as novas linhas de saldo calculadas
-    Ok(concat(&[lazyframe, saldos], union_args)?.collect()?.lazy())
+    Ok(concat(&[lazyframe, saldos], union_args)?)
 }
 
 /// Formata os valores das colunas aplicando arredondamento e filtrando dados insignificantes.
@@ -1129,7 +1148,7 @@ fn ordenar_linhas(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
                 .alias(cst),
         );
 
-    Ok(lazy_sorted.collect()?.lazy())
+    Ok(lazy_sorted)
 }
 
 fn rename_columns(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
@@ -1151,7 +1170,7 @@ fn rename_columns(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
         "Crédito vinculado à Receita Bruta Total",
     ];
 
-    Ok(lazyframe.rename(de, para, true).collect()?.lazy())
+    Ok(lazyframe.rename(de, para, true))
 }
 
 //----------------------------------------------------------------------------//
@@ -1167,12 +1186,71 @@ fn rename_columns(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
 #[cfg(test)]
 mod tests_consolidacao {
     use super::*;
-    use crate::{EXPLODE_OPTIONS, configure_the_environment, get_output_as_boolean};
+    use crate::{EXPLODE_OPTIONS, Side::Right, configure_the_environment, get_output_as_boolean};
 
     // cargo test -- --help
     // cargo test -- --nocapture
     // cargo test -- --show-output
 
+    #[test]
+    /// Vendas destinadas à Zona Franca de Manaus (CFOP 6109 da NF-e) com CST 08 e sem
+    /// CFOP na EFD são receitas não tributadas no mercado interno, e não receitas de
+    /// exportação (critério expansivo).
+    ///
+    /// `cargo test -- --show-output test_segregacao_da_receita_de_zona_franca`
+    fn test_segregacao_da_receita_de_zona_franca() -> JoinResult<()> {
+        let df_itens: DataFrame = df! {
+            coluna(Left, "contribuinte_cnpj") => ["12.345.678/0001-90"; 3],
+            coluna(Left, "pa") => ["2024-01-01"; 3],
+            "Ano do Período de Apuração" => [2024i64; 3],
+            "Trimestre do Período de Apuração" => [1i64; 3],
+            "Mês do Período de Apuração" => [1i64; 3],
+            coluna(Left, "tipo_operacao") => [1i64, 2, 2],
+            coluna(Left, "cod_cred") => [Some(101i64), None, None],
+            coluna(Left, "tipo_cred") => [Some(1i64), None, None],
+            coluna(Left, "cst") => [56i64, 8, 6],
+            coluna(Left, "registro") => ["C170"; 3],
+            coluna(Left, "cfop") => [Some(1102i64), None, Some(7101)],
+            coluna(Right, "cfop") => [Some(1102i64), Some(6109), Some(7101)],
+            "Código NCM" => ["22021000"; 3],
+            coluna(Left, "aliq_pis") => [Some(1.65), Some(0.0), Some(0.0)],
+            coluna(Left, "aliq_cof") => [Some(7.6), Some(0.0), Some(0.0)],
+            coluna(Left, "natureza") => [Some(1i64), None, None],
+            coluna(Left, "valor_bc") => [1_000.0, 0.0, 0.0],
+            coluna(Left, "valor_item") => [1_000.0, 600.0, 400.0],
+            coluna(Left, "item_desc") => ["Mercadoria"; 3],
+            coluna(Left, "contabil") => ["Vendas"; 3],
+            coluna(Left, "informacao") => [""; 3],
+        }?;
+
+        let df_nat: DataFrame = obter_consolidacao_nat(&df_itens, true)?;
+        println!("df_nat: {df_nat}");
+
+        // Receita e crédito de PIS/PASEP do mês, rateados conforme a receita segregada
+        let nao_tributada = "Crédito vinculado à Receita Bruta Não Cumulativa: Não Tributada";
+        let exportacao = "Crédito vinculado à Receita Bruta Não Cumulativa: de Exportação";
+
+        let segregacao = |natureza: i64| -> JoinResult<Vec<Option<f64>>> {
+            let df: DataFrame = df_nat
+                .clone()
+                .lazy()
+                .filter(col("Mês do Período de Apuração").is_not_null())
+                .filter(col("Natureza da Base de Cálculo dos Créditos").eq(lit(natureza)))
+                .collect()?;
+
+            Ok(vec![
+                df.column(nao_tributada)?.f64()?.get(0),
+                df.column(exportacao)?.f64()?.get(0),
+            ])
+        };
+
+        // Sem a coluna "Zona Franca": [None, Some(1000.0)] e [None, Some(16.5)]
+        assert_eq!(segregacao(101)?, [Some(600.0), Some(400.0)]);
+        assert_eq!(segregacao(201)?, [Some(9.9), Some(6.6)]);
+
+        Ok(())
+    }
+
     #[test]
     /// See polars-0.33.2/tests/it/lazy/explodes.rs
     ///
@@ -0,0 +1,289 @@
+//! # Correspondência de CFOP (Situação 16)
+//!
+//! Confronto entre o CFOP informado na EFD Contribuições (entrada) e o CFOP da
+//! NF-e (emitente).
+//!
+//! O CFOP do emitente (5xxx, 6xxx ou 7xxx) corresponde ao CFOP de entrada do
+//! destinatário (1xxx, 2xxx ou 3xxx) com os mesmos três últimos dígitos, que
+//! identificam a natureza da operação: aquisição, transferência, devolução ou
+//! remessa/retorno. Exemplos: 5102 -> 1102 (compra), 6152 -> 2152
+//! (transferência), 5915 -> 1915 (remessa para conserto).
+//!
+//! Remessas e retornos (demonstração, conserto, comodato, consignação,
+//! vasilhames, amostras e brindes, etc.) nunca geram crédito. Naturezas
+//! distintas na EFD e na NF-e (ex.: EFD 1102 e NF-e 5915) indicam que o crédito
+//! não decorre de uma aquisição.
+
+use polars::prelude::*;
+
+use crate::{
+    Arguments, JoinResult,
+    Side::{Left, Right},
+    coluna,
+};
+
+/// Coluna temporária: par de CFOPs (EFD x NF-e) incompatível com o crédito.
+pub const COLUNA_CFOP_INCOMPATIVEL: &str = "CFOP Incompatível Temp";
+
+/// Coluna temporária: natureza da operação segundo o CFOP da EFD.
+pub const COLUNA_NATUREZA_CFOP_EFD: &str = "Natureza do CFOP da EFD Temp";
+
+/// Coluna temporária: natureza da operação segundo o CFOP da NF-e.
+pub const COLUNA_NATUREZA_CFOP_NFE: &str = "Natureza do CFOP da NF-e Temp";
+
+/// Coluna temporária: CFOP de entrada correspondente ao CFOP da NF-e.
+pub const COLUNA_CFOP_CORRESPONDENTE: &str = "CFOP de Entrada Correspondente Temp";
+
+/// Natureza da operação identificada pelos três últimos dígitos do CFOP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NaturezaDoCfop {
+    Aquisicao,
+    Transferencia,
+    Devolucao,
+    /// Remessas e retornos: operações que nunca geram crédito.
+    RemessaOuRetorno,
+}
+
+impl NaturezaDoCfop {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            NaturezaDoCfop::Aquisicao => "Aquisição",
+            NaturezaDoCfop::Transferencia => "Transferência",
+            NaturezaDoCfop::Devolucao => "Devolução",
+            NaturezaDoCfop::RemessaOuRetorno => "Remessa ou Retorno",
+        }
+    }
+}
+
+/// Tabela de correspondência: intervalos dos três últimos dígitos do CFOP
+/// (comuns a entradas e saídas) e a natureza da operação.
+///
+/// CFOPs ausentes da tabela (ex.: x.949 Outras) não são confrontados.
+pub const CORRESPONDENCIA_DE_CFOP: [(u16, u16, NaturezaDoCfop); 27] = [
+    // Compras e vendas de produção ou de mercadorias
+    (101, 129, NaturezaDoCfop::Aquisicao),
+    (151, 159, NaturezaDoCfop::Transferencia),
+    (201, 213, NaturezaDoCfop::Devolucao),
+    // Energia elétrica, comunicação e transporte
+    (251, 258, NaturezaDoCfop::Aquisicao),
+    (301, 307, NaturezaDoCfop::Aquisicao),
+    (351, 360, NaturezaDoCfop::Aquisicao),
+    // Substituição tributária
+    (401, 407, NaturezaDoCfop::Aquisicao),
+    (408, 409, NaturezaDoCfop::Transferencia),
+    (410, 413, NaturezaDoCfop::Devolucao),
+    (414, 415, NaturezaDoCfop::RemessaOuRetorno),
+    // Remessas com fim específico de exportação e formação de lote
+    (501, 505, NaturezaDoCfop::RemessaOuRetorno),
+    // Ativo imobilizado e material de uso ou consumo
+    (551, 551, NaturezaDoCfop::Aquisicao),
+    (552, 552, NaturezaDoCfop::Transferencia),
+    (553, 553, NaturezaDoCfop::Devolucao),
+    (554, 555, NaturezaDoCfop::RemessaOuRetorno),
+    (556, 556, NaturezaDoCfop::Aquisicao),
+    (557, 557, NaturezaDoCfop::Transferencia),
+    // Combustíveis e lubrificantes
+    (651, 656, NaturezaDoCfop::Aquisicao),
+    (657, 657, NaturezaDoCfop::RemessaOuRetorno),
+    (658, 659, NaturezaDoCfop::Transferencia),
+    (660, 662, NaturezaDoCfop::Devolucao),
+    (663, 666, NaturezaDoCfop::RemessaOuRetorno),
+    (667, 667, NaturezaDoCfop::Aquisicao),
+    // Remessas e retornos: industrialização, armazenagem, demonstração, conserto,
+    // comodato, consignação, vasilhames, amostras, bonificações e brindes, etc.
+    (901, 925, NaturezaDoCfop::RemessaOuRetorno),
+    // Serviços sujeitos ao ISS
+    (933, 933, NaturezaDoCfop::Aquisicao),
+    // Remessa simbólica para depósito em armazém geral
+    (934, 934, NaturezaDoCfop::RemessaOuRetorno),
+    // Mercadorias em consignação industrial (entradas e retornos)
+    (935, 935, NaturezaDoCfop::RemessaOuRetorno),
+];
+
+/// Natureza da operação do CFOP informado, segundo [`CORRESPONDENCIA_DE_CFOP`].
+pub fn natureza_do_cfop(cfop: i64) -> Option<NaturezaDoCfop> {
+    let sufixo: i64 = cfop.rem_euclid(1000);
+
+    CORRESPONDENCIA_DE_CFOP
+        .iter()
+        .find(|(inicio, fim, _)| (i64::from(*inicio)..=i64::from(*fim)).contains(&sufixo))
+        .map(|(_, _, natureza)| *natureza)
+}
+
+/// Três últimos dígitos dos CFOPs da natureza informada.
+pub fn sufixos_do_cfop(natureza: NaturezaDoCfop) -> Vec<i64> {
+    CORRESPONDENCIA_DE_CFOP
+        .iter()
+        .filter(|(_, _, n)| *n == natureza)
+        .flat_map(|(inicio, fim, _)| (*inicio..=*fim).map(i64::from))
+        .collect()
+}
+
+/// Tabela (sufixo do CFOP, natureza) utilizada nos joins.
+fn df_correspondencia(sufixo: &str, natureza: &str) -> PolarsResult<DataFrame> {
+    let (sufixos, naturezas): (Vec<i64>, Vec<&str>) = CORRESPONDENCIA_DE_CFOP
+        .iter()
+        .flat_map(|(inicio, fim, natureza)| {
+            (*inicio..=*fim).map(|valor| (i64::from(valor), natureza.as_str()))
+        })
+        .unzip();
+
+    df! {
+        sufixo => sufixos,
+        natureza => naturezas,
+    }
+}
+
+/**
+Adiciona as colunas temporárias da Situação 16.
+
+O par de CFOPs é incompatível quando a natureza de um deles é
+"Remessa ou Retorno" ou quando as naturezas da EFD e da NF-e são distintas.
+*/
+pub fn confrontar_cfop(lazyframe: LazyFrame, _args: &Arguments) -> JoinResult<LazyFrame> {
+    let cfop_efd: &str = coluna(Left, "cfop");
+    let cfop_nfe: &str = coluna(Right, "cfop");
+
+    let sufixo_efd: &str = "Sufixo do CFOP da EFD";
+    let sufixo_nfe: &str = "Sufixo do CFOP da NF-e";
+    let sufixo: &str = "Sufixo do CFOP";
+
+    let sufixo_do_cfop =
+        |nome: &str| -> Expr { (col(nome).cast(DataType::Int64) % lit(1000)).abs() };
+
+    let natureza_efd: Expr = col(COLUNA_NATUREZA_CFOP_EFD);
+    let natureza_nfe: Expr = col(COLUNA_NATUREZA_CFOP_NFE);
+    let remessa: Expr = lit(NaturezaDoCfop::RemessaOuRetorno.as_str());
+
+    let incompativel: Expr = natureza_efd
+        .clone()
+        .eq(remessa.clone())
+        .fill_null(lit(false))
+        .or(natureza_nfe.clone().eq(remessa).fill_null(lit(false)))
+        .or(natureza_efd.neq(natureza_nfe).fill_null(lit(false)));
+
+    // CFOP do emitente (5xxx, 6xxx, 7xxx) -> CFOP de entrada (1xxx, 2xxx, 3xxx).
+    let cfop: Expr = col(cfop_nfe).cast(DataType::Int64);
+    let correspondente: Expr = when(
+        cfop.clone()
+            .gt_eq(lit(5000))
+            .and(cfop.clone().lt(lit(8000))),
+    )
+    .then(cfop.clone() - lit(4000))
+    .otherwise(cfop);
+
+    let lazyframe: LazyFrame = lazyframe
+        .with_columns([
+            sufixo_do_cfop(cfop_efd).alias(sufixo_efd),
+            sufixo_do_cfop(cfop_nfe).alias(sufixo_nfe),
+        ])
+        .join(
+            df_correspondencia(sufixo, COLUNA_NATUREZA_CFOP_EFD)?.lazy(),
+            vec![col(sufixo_efd)],
+            vec![col(sufixo)],
+            JoinType::Left.into(),
+        )
+        .join(
+            df_correspondencia(sufixo, COLUNA_NATUREZA_CFOP_NFE)?.lazy(),
+            vec![col(sufixo_nfe)],
+            vec![col(sufixo)],
+            JoinType::Left.into(),
+        )
+        .with_columns([
+            incompativel.alias(COLUNA_CFOP_INCOMPATIVEL),
+            correspondente.alias(COLUNA_CFOP_CORRESPONDENTE),
+        ])
+        .drop(by_name([sufixo_efd, sufixo_nfe], true, true));
+
+    Ok(lazyframe)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_correspondencia_de_cfop
+#[cfg(test)]
+mod tests_correspondencia_de_cfop {
+    use super::*;
+
+    #[test]
+    /// `cargo test -- --show-output test_natureza_do_cfop`
+    fn test_natureza_do_cfop() {
+        assert_eq!(natureza_do_cfop(5102), Some(NaturezaDoCfop::Aquisicao));
+        assert_eq!(natureza_do_cfop(1102), Some(NaturezaDoCfop::Aquisicao));
+        assert_eq!(natureza_do_cfop(6152), Some(NaturezaDoCfop::Transferencia));
+        assert_eq!(natureza_do_cfop(5202), Some(NaturezaDoCfop::Devolucao));
+        // Demonstração, conserto e comodato.
+        for cfop in [5912, 6915, 1908, 2909] {
+            assert_eq!(
+                natureza_do_cfop(cfop),
+                Some(NaturezaDoCfop::RemessaOuRetorno)
+            );
+        }
+        assert_eq!(natureza_do_cfop(5949), None);
+
+        // Os intervalos da tabela não se sobrepõem.
+        for par in CORRESPONDENCIA_DE_CFOP.windows(2) {
+            let (inicio, fim, _) = par[0];
+            let (proximo, _, _) = par[1];
+            assert!(inicio <= fim && fim < proximo);
+        }
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_confrontar_cfop`
+    fn test_confrontar_cfop() -> JoinResult<()> {
+        let cfop_efd: &str = coluna(Left, "cfop");
+        let cfop_nfe: &str = coluna(Right, "cfop");
+
+        let lazyframe = df! {
+            cfop_efd => [Some(1102i64), Some(1102), Some(1556), Some(2152), Some(1915), Some(1102)],
+            cfop_nfe => [Some(5102i64), Some(5915), Some(5102), Some(6102), None, Some(5949)],
+        }?
+        .lazy();
+
+        let df = confrontar_cfop(lazyframe, &Arguments::default())?.collect()?;
+        println!("df: {df}");
+
+        let incompativel: Vec<Option<bool>> = df
+            .column(COLUNA_CFOP_INCOMPATIVEL)?
+            .bool()?
+            .iter()
+            .collect();
+        let correspondente: Vec<Option<i64>> = df
+            .column(COLUNA_CFOP_CORRESPONDENTE)?
+            .i64()?
+            .iter()
+            .collect();
+
+        // 1102 x 5102: compra. 1102 x 5915: remessa para conserto.
+        // 1556 x 5102: compra de material de uso ou consumo. 2152 x 6102: transferência x venda.
+        // 1915 sem NF-e: remessa. 1102 x 5949: outras saídas (não confrontado).
+        assert_eq!(
+            incompativel,
+            [
+                Some(false),
+                Some(true),
+                Some(false),
+                Some(true),
+                Some(true),
+                Some(false)
+            ]
+        );
+        assert_eq!(
+            correspondente,
+            [
+                Some(1102),
+                Some(1915),
+                Some(1102),
+                Some(2102),
+                None,
+                Some(1949)
+            ]
+        );
+
+        Ok(())
+    }
+}
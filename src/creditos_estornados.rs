@@ -0,0 +1,369 @@
+//! # Créditos Estornados (Situação 13)
+//!
+//! Relação das operações cujos créditos foram reconhecidos como indevidos pelo
+//! Contribuinte em resposta às Intimações Fiscais (`--creditos-estornados`).
+//!
+//! Cada operação é identificada pelo número da linha do arquivo de entrada
+//! (coluna "Linhas") ou pelo par Chave do Documento + Nº do Item, com referência opcional ao
+//! documento de intimação.
+//!
+//! Formato TOML:
+//!
+//! ```toml
+//! intimacao = "Termo de Intimação Fiscal nº 1/2024"  # referência padrão (opcional)
+//! linhas = [95217, 95222, 95223]
+//!
+//! [[estorno]]
+//! chave = "35230112345678000190550010000012341000012345"
+//! item = 2
+//! intimacao = "Termo de Intimação Fiscal nº 2/2024"
+//! ```
+//!
+//! Formato CSV (delimitador `;`, `,`, `|` ou tabulação; campos entre aspas
+//! podem conter o delimitador), com as colunas
+//! `linha` ou `chave` e `item`, e a coluna opcional `intimacao`:
+//!
+//! ```text
+//! linha;chave;item;intimacao
+//! 95217;;;Termo de Intimação Fiscal nº 1/2024
+//! ;35230112345678000190550010000012341000012345;2;
+//! ```
+//!
+//! A Situação 13 é habilitada automaticamente sempre que o arquivo é informado.
+
+use polars::prelude::*;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{Arguments, JoinError, JoinResult, Side::Left, coluna, tabela_csv::TabelaCsv};
+
+/// Coluna temporária: operação com crédito estornado pelo Contribuinte.
+pub const COLUNA_CREDITO_ESTORNADO: &str = "Crédito Estornado Temp";
+
+/// Coluna temporária: referência ao documento de intimação.
+pub const COLUNA_INTIMACAO: &str = "Intimação Temp";
+
+/// Relação de créditos estornados lida do arquivo `--creditos-estornados`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct CreditosEstornados {
+    /// Referência ao documento de intimação utilizada quando a operação não informa outra.
+    pub intimacao: Option<String>,
+
+    /// Números das linhas do arquivo de entrada (coluna "Linhas").
+    #[serde(default)]
+    pub linhas: Vec<u64>,
+
+    #[serde(default, rename = "estorno")]
+    pub estornos: Vec<Estorno>,
+}
+
+/// Operação com crédito estornado: linha do arquivo de entrada ou Chave + Nº do Item.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Estorno {
+    pub linha: Option<u64>,
+    pub chave: Option<String>,
+    pub item: Option<i64>,
+    pub intimacao: Option<String>,
+}
+
+impl CreditosEstornados {
+    /// Lê o arquivo informado em `--creditos-estornados`.
+    pub fn carregar(args: &Arguments) -> JoinResult<Self> {
+        let path: &PathBuf =
+            args.creditos_estornados
+                .as_ref()
+                .ok_or_else(|| JoinError::InvalidGlosaRule {
+                    id: "13".to_string(),
+                    motivo: "informe o arquivo de créditos estornados (--creditos-estornados)"
+                        .to_string(),
+                })?;
+
+        Self::from_path(path)
+    }
+
+    /// Lê o arquivo no formato TOML (extensão `.toml`) ou CSV.
+    pub fn from_path(path: &Path) -> JoinResult<Self> {
+        let texto: String = fs::read_to_string(path)?;
+
+        let is_toml: bool = path
+            .extension()
+            .is_some_and(|extensao| extensao.eq_ignore_ascii_case("toml"));
+
+        let creditos: CreditosEstornados = if is_toml {
+            toml::from_str(&texto)?
+        } else {
+            Self::from_csv(&texto).map_err(|motivo| JoinError::InvalidInputFile {
+                path: path.to_path_buf(),
+                motivo,
+            })?
+        };
+
+        creditos
+            .validar()
+            .map_err(|motivo| JoinError::InvalidInputFile {
+                path: path.to_path_buf(),
+                motivo,
+            })?;
+
+        println!(
+            "Créditos estornados: '{}' ({} operações)\n",
+            path.display(),
+            creditos.linhas.len() + creditos.estornos.len()
+        );
+
+        Ok(creditos)
+    }
+
+    /// Interpreta o texto CSV. O delimitador é identificado pelo cabeçalho.
+    pub fn from_csv(texto: &str) -> Result<Self, String> {
+        let tabela = TabelaCsv::from_texto(texto)?;
+
+        if !(tabela.contem("linha") || tabela.contem("chave") && tabela.contem("item")) {
+            return Err(
+                "o cabeçalho deve conter a coluna 'linha' ou as colunas 'chave' e 'item'"
+                    .to_string(),
+            );
+        }
+
+        let mut estornos: Vec<Estorno> = Vec::new();
+
+        for indice in 0..tabela.height() {
+            let campo = |nome: &str| tabela.campo(nome, indice);
+            let numero = |valor: &str| format!("valor inválido '{valor}' na linha {}", indice + 2);
+
+            estornos.push(Estorno {
+                linha: campo("linha")
+                    .map(|v| v.parse::<u64>().map_err(|_| numero(v)))
+                    .transpose()?,
+                chave: campo("chave").map(str::to_string),
+                item: campo("item")
+                    .map(|v| v.parse::<i64>().map_err(|_| numero(v)))
+                    .transpose()?,
+                intimacao: campo("intimacao").map(str::to_string),
+            });
+        }
+
+        Ok(CreditosEstornados {
+            intimacao: None,
+            linhas: Vec::new(),
+            estornos,
+        })
+    }
+
+    /// Cada operação deve informar a linha ou o par chave + item.
+    fn validar(&self) -> Result<(), String> {
+        for (indice, estorno) in self.estornos.iter().enumerate() {
+            let por_item: bool = estorno.chave.is_some() && estorno.item.is_some();
+            if estorno.linha.is_none() && !por_item {
+                return Err(format!(
+                    "operação nº {} sem 'linha' e sem o par 'chave' e 'item'",
+                    indice + 1
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Referência à intimação de uma operação (ou a referência padrão).
+    fn referencia(&self, estorno: &Estorno) -> Option<String> {
+        estorno.intimacao.clone().or_else(|| self.intimacao.clone())
+    }
+
+    /// Operações identificadas pelo número da linha (coluna "Linhas"), sem repetições.
+    pub fn df_por_linha(&self) -> JoinResult<DataFrame> {
+        let mut mapa: BTreeMap<u64, Option<String>> = BTreeMap::new();
+
+        for &linha in &self.linhas {
+            mapa.entry(linha).or_insert_with(|| self.intimacao.clone());
+        }
+
+        for estorno in &self.estornos {
+            if let Some(linha) = estorno.linha {
+                mapa.entry(linha)
+                    .or_insert_with(|| self.referencia(estorno));
+            }
+        }
+
+        let (linhas, intimacoes): (Vec<u64>, Vec<Option<String>>) = mapa.into_iter().unzip();
+
+        Ok(df! {
+            coluna(Left, "num_linha") => linhas,
+            "Estorno por Linha" => vec![true; intimacoes.len()],
+            "Intimação por Linha" => intimacoes,
+        }?)
+    }
+
+    /// Operações identificadas por Chave + Nº do Item (sem linha), sem repetições.
+    pub fn df_por_item(&self) -> JoinResult<DataFrame> {
+        let mut mapa: BTreeMap<(String, i64), Option<String>> = BTreeMap::new();
+
+        for estorno in &self.estornos {
+            if let (None, Some(chave), Some(item)) = (estorno.linha, &estorno.chave, estorno.item) {
+                mapa.entry((chave.clone(), item))
+                    .or_insert_with(|| self.referencia(estorno));
+            }
+        }
+
+        let mut chaves: Vec<String> = Vec::new();
+        let mut itens: Vec<i64> = Vec::new();
+        let mut intimacoes: Vec<Option<String>> = Vec::new();
+
+        for ((chave, item), intimacao) in mapa {
+            chaves.push(chave);
+            itens.push(item);
+            intimacoes.push(intimacao);
+        }
+
+        Ok(df! {
+            coluna(Left, "chave") => chaves,
+            coluna(Left, "item_num") => itens,
+            "Estorno por Item" => vec![true; intimacoes.len()],
+            "Intimação por Item" => intimacoes,
+        }?)
+    }
+}
+
+/// Preparo da Situação 13: identifica as operações do arquivo `--creditos-estornados`.
+///
+/// Adiciona as colunas temporárias `COLUNA_CREDITO_ESTORNADO` (booleana) e
+/// `COLUNA_INTIMACAO` (referência ao documento de intimação).
+pub fn marcar_creditos_estornados(lazyframe: LazyFrame, args: &Arguments) -> JoinResult<LazyFrame> {
+    let creditos: CreditosEstornados = CreditosEstornados::carregar(args)?;
+
+    let num_linha: &str = coluna(Left, "num_linha");
+    let chave: &str = coluna(Left, "chave");
+    let item_num: &str = coluna(Left, "item_num");
+
+    let lazyframe: LazyFrame = lazyframe
+        .join(
+            creditos.df_por_linha()?.lazy(),
+            vec![col(num_linha)],
+            vec![col(num_linha)],
+            JoinType::Left.into(),
+        )
+        .join(
+            creditos.df_por_item()?.lazy(),
+            vec![col(chave), col(item_num)],
+            vec![col(chave), col(item_num)],
+            JoinType::Left.into(),
+        )
+        .with_columns([
+            col("Estorno por Linha")
+                .fill_null(lit(false))
+                .or(col("Estorno por Item").fill_null(lit(false)))
+                .alias(COLUNA_CREDITO_ESTORNADO),
+            coalesce(&[col("Intimação por Linha"), col("Intimação por Item")])
+                .alias(COLUNA_INTIMACAO),
+        ])
+        .drop(by_name(
+            [
+                "Estorno por Linha",
+                "Intimação por Linha",
+                "Estorno por Item",
+                "Intimação por Item",
+            ],
+            true,
+            true,
+        ));
+
+    Ok(lazyframe)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_creditos_estornados
+#[cfg(test)]
+mod tests_creditos_estornados {
+    use super::*;
+
+    #[test]
+    /// `cargo test -- --show-output test_creditos_estornados_csv`
+    fn test_creditos_estornados_csv() -> JoinResult<()> {
+        let texto = "linha;chave;item;intimacao\n\
+                     95217;;;TIF 1/2024\n\
+                     95217;;;TIF 2/2024\n\
+                     ;35230112345678000190550010000012341000012345;2;\n";
+
+        let creditos = CreditosEstornados::from_csv(texto).map_err(JoinError::Other)?;
+        creditos.validar().map_err(JoinError::Other)?;
+
+        let df_linhas = creditos.df_por_linha()?;
+        let df_itens = creditos.df_por_item()?;
+        println!("df_linhas: {df_linhas}");
+        println!("df_itens: {df_itens}");
+
+        // Linhas repetidas são consideradas uma única vez.
+        assert_eq!(df_linhas.height(), 1);
+        assert_eq!(df_itens.height(), 1);
+
+        let sem_identificacao =
+            "linha;chave;item\n;35230112345678000190550010000012341000012345;\n";
+        let creditos = CreditosEstornados::from_csv(sem_identificacao).map_err(JoinError::Other)?;
+        assert!(creditos.validar().is_err());
+
+        assert!(CreditosEstornados::from_csv("intimacao\nTIF 1/2024\n").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_creditos_estornados_toml`
+    fn test_creditos_estornados_toml() -> JoinResult<()> {
+        let texto = r#"
+            intimacao = "TIF 1/2024"
+            linhas = [95217, 95222]
+
+            [[estorno]]
+            chave = "35230112345678000190550010000012341000012345"
+            item = 2
+            intimacao = "TIF 2/2024"
+        "#;
+
+        let creditos: CreditosEstornados = toml::from_str(texto)?;
+
+        let num_linha: &str = coluna(Left, "num_linha");
+        let chave: &str = coluna(Left, "chave");
+        let item_num: &str = coluna(Left, "item_num");
+
+        let lazyframe = df! {
+            num_linha => [95217u64, 100, 200],
+            chave => [None, Some("35230112345678000190550010000012341000012345"), Some("35230112345678000190550010000012341000012345")],
+            item_num => [1i64, 2, 3],
+        }?
+        .lazy();
+
+        let mut args = Arguments::default();
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("estornos.toml");
+        fs::write(&path, texto)?;
+        args.creditos_estornados = Some(path);
+
+        assert_eq!(CreditosEstornados::carregar(&args)?, creditos);
+
+        let df = marcar_creditos_estornados(lazyframe, &args)?
+            .sort([num_linha], SortMultipleOptions::default())
+            .collect()?;
+        println!("df: {df}");
+
+        let estornados: Vec<Option<bool>> = df
+            .column(COLUNA_CREDITO_ESTORNADO)?
+            .bool()?
+            .iter()
+            .collect();
+        let intimacoes: Vec<Option<&str>> = df.column(COLUNA_INTIMACAO)?.str()?.iter().collect();
+
+        // Ordenado por linha: 100 (chave + item), 200 (não estornada) e 95217 (linha).
+        assert_eq!(estornados, [Some(true), Some(false), Some(true)]);
+        assert_eq!(intimacoes, [Some("TIF 2/2024"), None, Some("TIF 1/2024")]);
+
+        Ok(())
+    }
+}
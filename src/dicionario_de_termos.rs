@@ -0,0 +1,301 @@
+//! # Dicionário de Termos
+//!
+//! Vocabulários das situações de glosa baseadas em texto (09, 10, 11 e 14):
+//! termos procurados nas descrições dos itens, nas contas contábeis ou nas
+//! descrições de CFOP.
+//!
+//! O dicionário padrão ([`DICIONARIO_PADRAO`]) é incorporado ao executável.
+//! Um dicionário próprio (`--dicionario`) substitui apenas os vocabulários nele
+//! definidos, pois cada Contribuinte nomeia suas contas de forma distinta.
+//!
+//! Formato TOML:
+//!
+//! ```toml
+//! [vocabulario.propaganda]
+//! colunas = ["left.item_desc", "left.contabil"]
+//! incluir = ["marketing", "propaganda", "patrocínio"]
+//! excluir = ["propaganda eleitoral"]
+//! ```
+//!
+//! A comparação ignora maiúsculas, minúsculas e acentos. A regra que utiliza o
+//! vocabulário recebe a coluna temporária [`COLUNA_TERMO_ENCONTRADO`] com o
+//! primeiro termo encontrado e a coluna correspondente.
+
+use polars::prelude::*;
+use regex::escape;
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::{Arguments, JoinError, JoinResult, resolver_coluna};
+
+/// Dicionário padrão de termos.
+pub const DICIONARIO_PADRAO: &str = include_str!("dicionario_de_termos.toml");
+
+/// Coluna temporária: termo que caracterizou a situação, no formato "<termo> (<coluna>)".
+pub const COLUNA_TERMO_ENCONTRADO: &str = "Termo Encontrado Temp";
+
+/// Letras acentuadas (minúsculas) e a letra correspondente sem acento.
+const ACENTOS: [(&str, char); 6] = [
+    ("áàâãä", 'a'),
+    ("éèêë", 'e'),
+    ("íìîï", 'i'),
+    ("óòôõö", 'o'),
+    ("úùûü", 'u'),
+    ("ç", 'c'),
+];
+
+/// Vocabulários por nome, lidos do dicionário padrão ou de `--dicionario`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct DicionarioDeTermos {
+    #[serde(default, rename = "vocabulario")]
+    pub vocabularios: BTreeMap<String, Vocabulario>,
+}
+
+/// Termos que caracterizam (ou afastam) uma situação de glosa.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Vocabulario {
+    /// Colunas pesquisadas (nomes literais ou apelidos `left.` e `right.`).
+    pub colunas: Vec<String>,
+
+    #[serde(default)]
+    pub incluir: Vec<String>,
+
+    /// Termos que afastam a situação, em qualquer das colunas.
+    #[serde(default)]
+    pub excluir: Vec<String>,
+
+    /// Exigir algum termo em todas as colunas (padrão: em ao menos uma).
+    #[serde(default)]
+    pub todas_as_colunas: bool,
+}
+
+impl DicionarioDeTermos {
+    /// Dicionário padrão incorporado ao executável.
+    pub fn padrao() -> JoinResult<Self> {
+        Self::from_toml(DICIONARIO_PADRAO)
+    }
+
+    /// Lê e valida o dicionário a partir de um texto no formato TOML.
+    pub fn from_toml(texto: &str) -> JoinResult<Self> {
+        let dicionario: DicionarioDeTermos = toml::from_str(texto)?;
+
+        for (nome, vocabulario) in &dicionario.vocabularios {
+            let _ = vocabulario
+                .termo_encontrado()
+                .map_err(|motivo| JoinError::Other(format!("vocabulário '{nome}': {motivo}")))?;
+        }
+
+        Ok(dicionario)
+    }
+
+    /// Lê o dicionário do arquivo informado.
+    pub fn from_path(path: &Path) -> JoinResult<Self> {
+        let texto: String = fs::read_to_string(path)?;
+
+        Self::from_toml(&texto).map_err(|erro| JoinError::InvalidInputFile {
+            path: path.to_path_buf(),
+            motivo: erro.to_string(),
+        })
+    }
+
+    /// Dicionário padrão, com os vocabulários de `--dicionario` (se informado).
+    pub fn carregar(args: &Arguments) -> JoinResult<Self> {
+        let mut dicionario: DicionarioDeTermos = Self::padrao()?;
+
+        if let Some(path) = &args.dicionario {
+            let vocabularios = Self::from_path(path)?.vocabularios;
+
+            println!(
+                "Dicionário de termos: '{}' ({} vocabulários)\n",
+                path.display(),
+                vocabularios.len()
+            );
+
+            dicionario.vocabularios.extend(vocabularios);
+        }
+
+        Ok(dicionario)
+    }
+
+    pub fn vocabulario(&self, nome: &str) -> Option<&Vocabulario> {
+        self.vocabularios.get(nome)
+    }
+}
+
+impl Vocabulario {
+    /// Primeiro termo de `incluir` encontrado, no formato "<termo> (<coluna>)",
+    /// ou nulo se nenhum termo for encontrado ou se algum termo de `excluir` o for.
+    pub fn termo_encontrado(&self) -> Result<Expr, String> {
+        if self.colunas.is_empty() {
+            return Err("nenhuma coluna informada".to_string());
+        }
+
+        if self.incluir.iter().all(|termo| termo.trim().is_empty()) {
+            return Err("nenhum termo a incluir".to_string());
+        }
+
+        let mut encontrados: Vec<Expr> = Vec::new();
+        let mut excluidos: Vec<Expr> = Vec::new();
+
+        for referencia in &self.colunas {
+            let nome: &str = resolver_coluna(referencia)?;
+            let texto: Expr = sem_acentos(col(nome));
+
+            let termos: Vec<Expr> = termos_validos(&self.incluir)
+                .map(|termo| {
+                    when(texto.clone().str().contains(lit(padrao(termo)), false))
+                        .then(lit(format!("{} ({nome})", termo.trim())))
+                        .otherwise(lit(NULL).cast(DataType::String))
+                })
+                .collect();
+
+            encontrados.push(coalesce(&termos));
+
+            excluidos.extend(termos_validos(&self.excluir).map(|termo| {
+                texto
+                    .clone()
+                    .str()
+                    .contains(lit(padrao(termo)), false)
+                    .fill_null(lit(false))
+            }));
+        }
+
+        let encontrado: Expr = if self.todas_as_colunas {
+            let todas: Expr = encontrados
+                .iter()
+                .map(|expr| expr.clone().is_not_null())
+                .reduce(|a, b| a.and(b))
+                .unwrap_or(lit(false));
+            when(todas)
+                .then(coalesce(&encontrados))
+                .otherwise(lit(NULL).cast(DataType::String))
+        } else {
+            coalesce(&encontrados)
+        };
+
+        let excluido: Expr = excluidos
+            .into_iter()
+            .reduce(|a, b| a.or(b))
+            .unwrap_or(lit(false));
+
+        Ok(when(excluido)
+            .then(lit(NULL).cast(DataType::String))
+            .otherwise(encontrado))
+    }
+}
+
+fn termos_validos(termos: &[String]) -> impl Iterator<Item = &String> {
+    termos.iter().filter(|termo| !termo.trim().is_empty())
+}
+
+/// Texto em minúsculas e sem acentos: "Refeição" -> "refeicao".
+pub fn remover_acentos(texto: &str) -> String {
+    texto
+        .to_lowercase()
+        .chars()
+        .map(|letra| {
+            ACENTOS
+                .iter()
+                .find(|(acentuadas, _)| acentuadas.contains(letra))
+                .map_or(letra, |(_, sem_acento)| *sem_acento)
+        })
+        .collect()
+}
+
+/// Expressão equivalente a [`remover_acentos`] para colunas de texto.
+fn sem_acentos(expr: Expr) -> Expr {
+    ACENTOS.iter().fold(
+        expr.str().to_lowercase(),
+        |expr, (acentuadas, sem_acento)| {
+            expr.str().replace_all(
+                lit(format!("[{acentuadas}]")),
+                lit(sem_acento.to_string()),
+                false,
+            )
+        },
+    )
+}
+
+/// Expressão regular do termo: texto literal, sem acentos, com espaços flexíveis.
+fn padrao(termo: &str) -> String {
+    remover_acentos(termo.trim())
+        .split_whitespace()
+        .map(escape)
+        .collect::<Vec<String>>()
+        .join(r"\s+")
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_dicionario_de_termos
+#[cfg(test)]
+mod tests_dicionario_de_termos {
+    use super::*;
+    use crate::{Side::Left, coluna};
+
+    #[test]
+    /// `cargo test -- --show-output test_remover_acentos`
+    fn test_remover_acentos() {
+        assert_eq!(remover_acentos("Vale Refeição"), "vale refeicao");
+        assert_eq!(remover_acentos("VEICULAÇÃO"), "veiculacao");
+        assert_eq!(padrao(" vale  refeição "), r"vale\s+refeicao");
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_termo_encontrado`
+    fn test_termo_encontrado() -> JoinResult<()> {
+        let dicionario = DicionarioDeTermos::padrao()?;
+        assert!(dicionario.vocabulario("propaganda").is_some());
+
+        let texto = r#"
+            [vocabulario.teste]
+            colunas = ["left.item_desc", "left.contabil"]
+            incluir = ["vale refeição", "propaganda"]
+            excluir = ["eleitoral"]
+        "#;
+        let dicionario = DicionarioDeTermos::from_toml(texto)?;
+        let vocabulario = dicionario
+            .vocabulario("teste")
+            .ok_or(JoinError::Other("vocabulário ausente".to_string()))?;
+
+        let item_desc: &str = coluna(Left, "item_desc");
+        let contabil: &str = coluna(Left, "contabil");
+
+        let df = df! {
+            item_desc => [Some("VALE  REFEICAO"), Some("Papel"), Some("Propaganda Eleitoral"), None],
+            contabil => [Some("Despesas"), Some("PROPAGANDA E PUBLICIDADE"), Some("Propaganda"), None],
+        }?;
+
+        let expr: Expr = vocabulario.termo_encontrado().map_err(JoinError::Other)?;
+        let resultado = df.lazy().select([expr.alias("termo")]).collect()?;
+        println!("resultado: {resultado}");
+
+        let termos: Vec<Option<String>> = resultado
+            .column("termo")?
+            .str()?
+            .iter()
+            .map(|termo| termo.map(str::to_string))
+            .collect();
+
+        assert_eq!(
+            termos,
+            [
+                Some(format!("vale refeição ({item_desc})")),
+                Some(format!("propaganda ({contabil})")),
+                None,
+                None,
+            ]
+        );
+
+        let sem_termos = r#"
+            [vocabulario.vazio]
+            colunas = ["left.item_desc"]
+        "#;
+        assert!(DicionarioDeTermos::from_toml(sem_termos).is_err());
+
+        Ok(())
+    }
+}
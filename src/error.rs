@@ -91,6 +91,9 @@ pub enum JoinError {
     #[error("TOML error: {0}")]
     Toml(#[from] toml::ser::Error),
 
+    #[error("TOML parsing error: {0}")]
+    TomlDe(#[from] toml::de::Error),
+
     // Adicione esta variante:
     #[error("Excel error: {0}")]
     Xlsx(#[from] rust_xlsxwriter::XlsxError),
@@ -99,6 +102,24 @@ pub enum JoinError {
     #[error("Invalid DataFrame count: expected {expected}, found {found}")]
     InvalidDataFrameCount { expected: usize, found: usize },
 
+    /// Error returned when one of the main sheets (Itens, EFD original, EFD após auditoria) is missing.
+    #[error("Missing required sheet: '{sheet}'")]
+    MissingSheet { sheet: String },
+
+    /// Error returned when the CSV files contain more rejected values than allowed.
+    #[error(
+        "Rejected values limit exceeded: {total} values rejected while reading the CSV files (limit: {limite}). See 'df_linhas_rejeitadas'."
+    )]
+    RejectedLimitExceeded { total: usize, limite: usize },
+
+    /// Error returned when a glosa rule of the rules file cannot be compiled.
+    #[error("Invalid glosa rule '{id}': {motivo}")]
+    InvalidGlosaRule { id: String, motivo: String },
+
+    /// Error returned when an auxiliary input file (e.g. `--creditos-estornados`) is invalid.
+    #[error("Invalid input file {path:?}: {motivo}")]
+    InvalidInputFile { path: PathBuf, motivo: String },
+
     // Adicione esta variante em JoinError dentro de error.rs:
     #[error(
         "Mapping error: The column '{name}' is present in the DataFrame but was not defined in MyColumn."
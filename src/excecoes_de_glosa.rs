@@ -0,0 +1,624 @@
+//! # Exceções de Glosa
+//!
+//! Relação das operações retiradas de situações de glosa específicas
+//! (`--excecoes`), por exemplo, quando o Contribuinte comprova, em resposta a
+//! uma Intimação Fiscal, que o frete contratado é insumo essencial.
+//!
+//! Cada exceção identifica a operação pelo número da linha do arquivo de
+//! entrada (coluna "Linhas"), pelo par Chave do Documento + Nº do Item ou apenas pela
+//! Chave do Documento (todos os itens), e informa as situações afastadas e a
+//! justificativa.
+//!
+//! Formato TOML:
+//!
+//! ```toml
+//! [[excecao]]
+//! chave = "35230112345678000190550010000012341000012345"
+//! item = 2
+//! situacoes = ["07"]
+//! justificativa = "Frete essencial à produção (Resposta ao TIF nº 3/2024)"
+//!
+//! [[excecao]]
+//! linha = 95217
+//! situacoes = ["10", "11"]
+//! justificativa = "Nota de crédito comprovada em diligência"
+//! ```
+//!
+//! Formato CSV (delimitador `;`, `,`, `|` ou tabulação; campos entre aspas
+//! podem conter o delimitador), com as colunas `linha` ou `chave` (e
+//! opcionalmente `item`), `situacoes` e `justificativa`.
+//! As situações são separadas por espaço, `/` ou `,` (se este não for o
+//! delimitador):
+//!
+//! ```text
+//! linha;chave;item;situacoes;justificativa
+//! 95217;;;10/11;Nota de crédito comprovada em diligência
+//! ;35230112345678000190550010000012341000012345;2;07;Frete essencial à produção
+//! ```
+//!
+//! `glosar_bc` não aplica a situação às operações da relação. Cada exceção
+//! efetivamente aplicada (linha que seria glosada) é registrada na coluna
+//! `Exceção Aplicada: Situação <id>` e listada em `df_excecoes_aplicadas`.
+
+use polars::prelude::*;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    Arguments, GlosaRule, JoinError, JoinResult, PREFIXO_EXCECAO_APLICADA, RegrasDeGlosa,
+    Side::Left, coluna, regras_de_glosa::corresponde, tabela_csv::TabelaCsv,
+};
+
+/// Coluna temporária: justificativa da exceção da operação para a regra em aplicação.
+const COLUNA_JUSTIFICATIVA: &str = "Justificativa da Exceção Temp";
+
+/// Colunas da relação de exceções aplicadas (além das colunas de identificação da linha).
+pub const COLUNAS_EXCECOES: [&str; 4] =
+    ["Ordem de Aplicação", "Regra", "Situação", "Justificativa"];
+
+/// Relação de exceções lida do arquivo `--excecoes`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ExcecoesDeGlosa {
+    #[serde(default, rename = "excecao")]
+    pub excecoes: Vec<Excecao>,
+
+    /// Arquivo de origem das exceções.
+    #[serde(skip)]
+    pub origem: PathBuf,
+}
+
+/// Operação retirada das situações de glosa informadas.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Excecao {
+    pub linha: Option<u64>,
+    pub chave: Option<String>,
+    /// Sem o Nº do Item, a exceção abrange todos os itens da Chave.
+    pub item: Option<i64>,
+    pub situacoes: Vec<String>,
+    pub justificativa: String,
+}
+
+/// Nome da coluna com a justificativa das exceções aplicadas por uma regra.
+pub fn coluna_excecao_aplicada(id: &str) -> String {
+    format!("{PREFIXO_EXCECAO_APLICADA}{id}")
+}
+
+impl ExcecoesDeGlosa {
+    /// Lê o arquivo informado em `--excecoes`, se houver, e verifica se as
+    /// situações informadas existem nas regras de glosa.
+    pub fn carregar(args: &Arguments, regras: &RegrasDeGlosa) -> JoinResult<Option<Self>> {
+        let Some(path) = &args.excecoes else {
+            return Ok(None);
+        };
+
+        let excecoes: ExcecoesDeGlosa = Self::from_path(path)?;
+
+        for excecao in &excecoes.excecoes {
+            for id in &excecao.situacoes {
+                if !regras.todas().any(|regra| corresponde(regra, id)) {
+                    return Err(JoinError::InvalidInputFile {
+                        path: path.clone(),
+                        motivo: format!("situação '{id}' não encontrada nas regras de glosa"),
+                    });
+                }
+            }
+        }
+
+        Ok(Some(excecoes))
+    }
+
+    /// Lê o arquivo no formato TOML (extensão `.toml`) ou CSV.
+    pub fn from_path(path: &Path) -> JoinResult<Self> {
+        let texto: String = fs::read_to_string(path)?;
+
+        let erro = |motivo: String| JoinError::InvalidInputFile {
+            path: path.to_path_buf(),
+            motivo,
+        };
+
+        let is_toml: bool = path
+            .extension()
+            .is_some_and(|extensao| extensao.eq_ignore_ascii_case("toml"));
+
+        let mut excecoes: ExcecoesDeGlosa = if is_toml {
+            toml::from_str(&texto)?
+        } else {
+            Self::from_csv(&texto).map_err(erro)?
+        };
+
+        excecoes.validar().map_err(erro)?;
+        excecoes.origem = path.to_path_buf();
+
+        println!(
+            "Exceções de glosa: '{}' ({} exceções)\n",
+            path.display(),
+            excecoes.excecoes.len()
+        );
+
+        Ok(excecoes)
+    }
+
+    /// Interpreta o texto CSV. O delimitador é identificado pelo cabeçalho.
+    pub fn from_csv(texto: &str) -> Result<Self, String> {
+        let tabela = TabelaCsv::from_texto(texto)?;
+
+        if !tabela.contem("linha") && !tabela.contem("chave") {
+            return Err("o cabeçalho deve conter a coluna 'linha' ou a coluna 'chave'".to_string());
+        }
+
+        if !tabela.contem("situacoes") || !tabela.contem("justificativa") {
+            return Err(
+                "o cabeçalho deve conter as colunas 'situacoes' e 'justificativa'".to_string(),
+            );
+        }
+
+        let mut excecoes: Vec<Excecao> = Vec::new();
+
+        for indice in 0..tabela.height() {
+            let campo = |nome: &str| tabela.campo(nome, indice);
+            let numero = |valor: &str| format!("valor inválido '{valor}' na linha {}", indice + 2);
+
+            excecoes.push(Excecao {
+                linha: campo("linha")
+                    .map(|v| v.parse::<u64>().map_err(|_| numero(v)))
+                    .transpose()?,
+                chave: campo("chave").map(str::to_string),
+                item: campo("item")
+                    .map(|v| v.parse::<i64>().map_err(|_| numero(v)))
+                    .transpose()?,
+                situacoes: campo("situacoes")
+                    .unwrap_or_default()
+                    .split([' ', '/', ','])
+                    .filter(|id| !id.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+                justificativa: campo("justificativa").unwrap_or_default().to_string(),
+            });
+        }
+
+        Ok(ExcecoesDeGlosa {
+            excecoes,
+            origem: PathBuf::new(),
+        })
+    }
+
+    /// Cada exceção deve informar a linha ou a chave, as situações e a justificativa.
+    fn validar(&self) -> Result<(), String> {
+        for (indice, excecao) in self.excecoes.iter().enumerate() {
+            let numero: usize = indice + 1;
+
+            if excecao.linha.is_none() && excecao.chave.is_none() {
+                return Err(format!("exceção nº {numero} sem 'linha' e sem 'chave'"));
+            }
+
+            if excecao.situacoes.is_empty() {
+                return Err(format!("exceção nº {numero} sem 'situacoes'"));
+            }
+
+            if excecao.justificativa.trim().is_empty() {
+                return Err(format!("exceção nº {numero} sem 'justificativa'"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Exceções que afastam a regra informada.
+    pub fn da_regra(&self, regra: &dyn GlosaRule) -> Vec<&Excecao> {
+        self.excecoes
+            .iter()
+            .filter(|excecao| excecao.situacoes.iter().any(|id| corresponde(regra, id)))
+            .collect()
+    }
+}
+
+/// Regra de glosa que não se aplica às operações relacionadas em `--excecoes`.
+///
+/// Nas linhas em que a regra seria aplicada, a justificativa da exceção é
+/// registrada na coluna `Exceção Aplicada: Situação <id>`.
+#[derive(Debug)]
+pub(crate) struct RegraComExcecoes<'a> {
+    regra: &'a dyn GlosaRule,
+    excecoes: Vec<&'a Excecao>,
+}
+
+impl<'a> RegraComExcecoes<'a> {
+    pub(crate) fn new(regra: &'a dyn GlosaRule, excecoes: Vec<&'a Excecao>) -> Self {
+        RegraComExcecoes { regra, excecoes }
+    }
+
+    /// Justificativas por número da linha (coluna "Linhas").
+    fn df_por_linha(&self) -> JoinResult<DataFrame> {
+        let mapa = agrupar(
+            self.excecoes
+                .iter()
+                .copied()
+                .filter_map(|e| e.linha.map(|l| (l, e))),
+        );
+        let (linhas, justificativas): (Vec<u64>, Vec<String>) = mapa.into_iter().unzip();
+
+        Ok(df! {
+            coluna(Left, "num_linha") => linhas,
+            "Justificativa por Linha" => justificativas,
+        }?)
+    }
+
+    /// Justificativas por Chave + Nº do Item (exceções sem linha).
+    fn df_por_item(&self) -> JoinResult<DataFrame> {
+        let mapa = agrupar(self.excecoes.iter().copied().filter_map(|e| {
+            match (e.linha, &e.chave, e.item) {
+                (None, Some(chave), Some(item)) => Some(((chave.clone(), item), e)),
+                _ => None,
+            }
+        }));
+
+        let mut chaves: Vec<String> = Vec::new();
+        let mut itens: Vec<i64> = Vec::new();
+        let mut justificativas: Vec<String> = Vec::new();
+
+        for ((chave, item), justificativa) in mapa {
+            chaves.push(chave);
+            itens.push(item);
+            justificativas.push(justificativa);
+        }
+
+        Ok(df! {
+            coluna(Left, "chave") => chaves,
+            coluna(Left, "item_num") => itens,
+            "Justificativa por Item" => justificativas,
+        }?)
+    }
+
+    /// Justificativas por Chave (exceções sem linha e sem item).
+    fn df_por_chave(&self) -> JoinResult<DataFrame> {
+        let mapa = agrupar(self.excecoes.iter().copied().filter_map(|e| {
+            match (e.linha, &e.chave, e.item) {
+                (None, Some(chave), None) => Some((chave.clone(), e)),
+                _ => None,
+            }
+        }));
+
+        let (chaves, justificativas): (Vec<String>, Vec<String>) = mapa.into_iter().unzip();
+
+        Ok(df! {
+            coluna(Left, "chave") => chaves,
+            "Justificativa por Chave" => justificativas,
+        }?)
+    }
+}
+
+/// Agrupa as justificativas por chave de identificação, sem repetições.
+fn agrupar<'a, K: Ord>(pares: impl Iterator<Item = (K, &'a Excecao)>) -> BTreeMap<K, String> {
+    let mut mapa: BTreeMap<K, Vec<&str>> = BTreeMap::new();
+
+    for (chave, excecao) in pares {
+        let justificativas = mapa.entry(chave).or_default();
+        if !justificativas.contains(&excecao.justificativa.as_str()) {
+            justificativas.push(&excecao.justificativa);
+        }
+    }
+
+    mapa.into_iter()
+        .map(|(chave, justificativas)| (chave, justificativas.join(" | ")))
+        .collect()
+}
+
+impl GlosaRule for RegraComExcecoes<'_> {
+    fn id(&self) -> &str {
+        self.regra.id()
+    }
+
+    fn situation(&self) -> &str {
+        self.regra.situation()
+    }
+
+    fn description(&self) -> &str {
+        self.regra.description()
+    }
+
+    fn legal_basis(&self) -> Option<&str> {
+        self.regra.legal_basis()
+    }
+
+    fn enabled(&self) -> bool {
+        self.regra.enabled()
+    }
+
+    fn precedence(&self) -> i32 {
+        self.regra.precedence()
+    }
+
+    /// Preparo da regra, seguido da identificação das operações excetuadas.
+    fn prepare(&self, lazyframe: LazyFrame, args: &Arguments) -> JoinResult<LazyFrame> {
+        let num_linha: &str = coluna(Left, "num_linha");
+        let chave: &str = coluna(Left, "chave");
+        let item_num: &str = coluna(Left, "item_num");
+
+        let lazyframe: LazyFrame = self
+            .regra
+            .prepare(lazyframe, args)?
+            .join(
+                self.df_por_linha()?.lazy(),
+                vec![col(num_linha)],
+                vec![col(num_linha)],
+                JoinType::Left.into(),
+            )
+            .join(
+                self.df_por_item()?.lazy(),
+                vec![col(chave), col(item_num)],
+                vec![col(chave), col(item_num)],
+                JoinType::Left.into(),
+            )
+            .join(
+                self.df_por_chave()?.lazy(),
+                vec![col(chave)],
+                vec![col(chave)],
+                JoinType::Left.into(),
+            )
+            .with_column(
+                coalesce(&[
+                    col("Justificativa por Linha"),
+                    col("Justificativa por Item"),
+                    col("Justificativa por Chave"),
+                ])
+                .alias(COLUNA_JUSTIFICATIVA),
+            )
+            .drop(by_name(
+                [
+                    "Justificativa por Linha",
+                    "Justificativa por Item",
+                    "Justificativa por Chave",
+                ],
+                true,
+                true,
+            ));
+
+        // Exceção aplicada: a regra seria aplicada à linha excetuada.
+        let aplicada: Expr = self
+            .regra
+            .condition()?
+            .fill_null(lit(false))
+            .and(col(COLUNA_JUSTIFICATIVA).is_not_null());
+
+        Ok(lazyframe.with_column(
+            when(aplicada)
+                .then(col(COLUNA_JUSTIFICATIVA))
+                .otherwise(lit(NULL).cast(DataType::String))
+                .alias(coluna_excecao_aplicada(self.id())),
+        ))
+    }
+
+    fn temporary_columns(&self) -> Vec<String> {
+        let mut colunas: Vec<String> = self.regra.temporary_columns();
+        colunas.push(COLUNA_JUSTIFICATIVA.to_string());
+        colunas
+    }
+
+    fn condition(&self) -> JoinResult<Expr> {
+        Ok(self
+            .regra
+            .condition()?
+            .and(col(COLUNA_JUSTIFICATIVA).is_null()))
+    }
+
+    fn message(&self) -> JoinResult<Expr> {
+        self.regra.message()
+    }
+
+    fn adjustment(&self) -> JoinResult<Expr> {
+        self.regra.adjustment()
+    }
+}
+
+/// Relação das exceções aplicadas: uma linha por linha da EFD e regra afastada.
+pub fn obter_excecoes_aplicadas(
+    df_itens_result: &DataFrame,
+    regras: &RegrasDeGlosa,
+) -> JoinResult<DataFrame> {
+    let identificacao: [&str; 5] = [
+        coluna(Left, "num_linha"),
+        coluna(Left, "efd_linha"),
+        coluna(Left, "chave"),
+        coluna(Left, "item_num"),
+        coluna(Left, "pa"),
+    ];
+
+    let lazyframe: LazyFrame = df_itens_result.clone().lazy();
+
+    let mut lazyframes: Vec<LazyFrame> = Vec::new();
+
+    for (indice, regra) in regras.ativas().enumerate() {
+        let excecao: String = coluna_excecao_aplicada(regra.id());
+
+        // Coluna ausente: nenhuma exceção informada para a regra.
+        if df_itens_result.column(&excecao).is_err() {
+            continue;
+        }
+
+        let colunas: Vec<Expr> = identificacao
+            .iter()
+            .map(|nome| col(*nome))
+            .chain([
+                lit(indice as u32 + 1).alias(COLUNAS_EXCECOES[0]),
+                lit(regra.id()).alias(COLUNAS_EXCECOES[1]),
+                lit(regra.situation()).alias(COLUNAS_EXCECOES[2]),
+                col(&excecao).alias(COLUNAS_EXCECOES[3]),
+            ])
+            .collect();
+
+        lazyframes.push(
+            lazyframe
+                .clone()
+                .filter(col(&excecao).is_not_null())
+                .select(colunas),
+        );
+    }
+
+    if lazyframes.is_empty() {
+        let mut df: DataFrame = df_itens_result.select(identificacao)?.clear();
+
+        for (indice, nome) in COLUNAS_EXCECOES.iter().enumerate() {
+            let dtype: DataType = if indice == 0 {
+                DataType::UInt32
+            } else {
+                DataType::String
+            };
+            df.with_column(Column::new_empty((*nome).into(), &dtype))?;
+        }
+
+        return Ok(df);
+    }
+
+    let df_excecoes: DataFrame = concat(lazyframes, UnionArgs::default())?
+        .sort(
+            [identificacao[0], COLUNAS_EXCECOES[0]],
+            SortMultipleOptions::default().with_maintain_order(true),
+        )
+        .collect()?;
+
+    println!("Exceções de glosa aplicadas: {}\n", df_excecoes.height());
+
+    Ok(df_excecoes)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_excecoes_de_glosa
+#[cfg(test)]
+mod tests_excecoes_de_glosa {
+    use super::*;
+    use crate::RegraDeGlosa;
+
+    #[test]
+    /// `cargo test -- --show-output test_excecoes_csv`
+    fn test_excecoes_csv() -> JoinResult<()> {
+        let texto = "linha;chave;item;situacoes;justificativa\n\
+                     95217;;;10/11;Nota de crédito comprovada\n\
+                     ;35230112345678000190550010000012341000012345;2;7;Frete essencial\n\
+                     ;35230112345678000190550010000012341000012345;;07;Documento diligenciado\n";
+
+        let excecoes = ExcecoesDeGlosa::from_csv(texto).map_err(JoinError::Other)?;
+        excecoes.validar().map_err(JoinError::Other)?;
+
+        assert_eq!(excecoes.excecoes.len(), 3);
+        assert_eq!(excecoes.excecoes[0].situacoes, ["10", "11"]);
+        assert_eq!(excecoes.excecoes[2].item, None);
+
+        let regras = RegrasDeGlosa::padrao()?;
+        let regra = |id: &str| -> JoinResult<&RegraDeGlosa> {
+            regras
+                .regras
+                .iter()
+                .find(|regra| regra.id == id)
+                .ok_or_else(|| JoinError::Other(format!("regra '{id}' não encontrada")))
+        };
+
+        // "7" e "07" referem-se à mesma situação.
+        assert_eq!(excecoes.da_regra(regra("07")?).len(), 2);
+        assert_eq!(excecoes.da_regra(regra("11")?).len(), 1);
+        assert!(excecoes.da_regra(regra("01")?).is_empty());
+
+        // A justificativa entre aspas pode conter o delimitador.
+        let entre_aspas = "linha;situacoes;justificativa\n\
+                           95217;07;\"Resposta ao TIF nº 3/2024; frete essencial\"\n";
+        let excecoes = ExcecoesDeGlosa::from_csv(entre_aspas).map_err(JoinError::Other)?;
+        assert_eq!(
+            excecoes.excecoes[0].justificativa,
+            "Resposta ao TIF nº 3/2024; frete essencial"
+        );
+
+        let sem_justificativa = "linha;situacoes;justificativa\n95217;07;\n";
+        let excecoes = ExcecoesDeGlosa::from_csv(sem_justificativa).map_err(JoinError::Other)?;
+        assert!(excecoes.validar().is_err());
+
+        Ok(())
+    }
+
+    /// Regra de teste: glosa as linhas com Base de Cálculo positiva.
+    #[derive(Debug)]
+    struct BaseDeCalculoPositiva;
+
+    impl GlosaRule for BaseDeCalculoPositiva {
+        fn id(&self) -> &str {
+            "07"
+        }
+
+        fn description(&self) -> &str {
+            "Regra de teste"
+        }
+
+        fn condition(&self) -> JoinResult<Expr> {
+            Ok(col(coluna(Left, "valor_bc")).gt(lit(0.0)))
+        }
+
+        fn message(&self) -> JoinResult<Expr> {
+            Ok(lit("Regra de teste."))
+        }
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_regra_com_excecoes`
+    fn test_regra_com_excecoes() -> JoinResult<()> {
+        let valor_bc: &str = coluna(Left, "valor_bc");
+
+        let dataframe: DataFrame = df! {
+            coluna(Left, "num_linha") => [1u64, 2, 3, 4],
+            coluna(Left, "chave") => ["a", "a", "b", "c"],
+            coluna(Left, "item_num") => [1i64, 2, 1, 1],
+            valor_bc => [100.0, 200.0, 300.0, -50.0],
+        }?;
+
+        let excecao = |linha: Option<u64>, chave: Option<&str>, item: Option<i64>| Excecao {
+            linha,
+            chave: chave.map(str::to_string),
+            item,
+            situacoes: vec!["07".to_string()],
+            justificativa: "Comprovado".to_string(),
+        };
+
+        // Linha 2 (chave + item), linha 3 (chave) e linha 4 (não seria glosada).
+        let excecoes: [Excecao; 3] = [
+            excecao(None, Some("a"), Some(2)),
+            excecao(None, Some("b"), None),
+            excecao(Some(4), None, None),
+        ];
+
+        let regra = RegraComExcecoes::new(&BaseDeCalculoPositiva, excecoes.iter().collect());
+        let args = Arguments::default();
+
+        let df: DataFrame = regra
+            .prepare(dataframe.lazy(), &args)?
+            .with_column(
+                when(regra.condition()?)
+                    .then(lit(0.0))
+                    .otherwise(col(valor_bc))
+                    .alias(valor_bc),
+            )
+            .collect()?;
+
+        println!("df: {df}");
+
+        let valores: Vec<Option<f64>> = df.column(valor_bc)?.f64()?.iter().collect();
+        assert_eq!(valores, [Some(0.0), Some(200.0), Some(300.0), Some(-50.0)]);
+
+        // A exceção da linha 4 não foi aplicada: a regra não a glosaria.
+        let aplicadas: Vec<Option<&str>> = df
+            .column(&coluna_excecao_aplicada("07"))?
+            .str()?
+            .iter()
+            .collect();
+        assert_eq!(
+            aplicadas,
+            [None, Some("Comprovado"), Some("Comprovado"), None]
+        );
+
+        Ok(())
+    }
+}
@@ -17,7 +17,7 @@ use crate::{
     all_data::AllData,
     coluna,
     format::{COLOR_SALDO_GREEN, COLOR_SALDO_RED, FormatKey, FormatRegistry, RowStyle},
-    format_dataframe,
+    format_dataframe, is_coluna_de_glosa, listas_como_texto,
 };
 
 const MAX_NUMBER_OF_ROWS: usize = 1_000_000;
@@ -53,9 +53,33 @@ pub enum SheetContext {
 
     /// Corresponds to post-audit analysis sheets, changing balance row colors to green.
     EfdAuditoria,
+
+    /// Values rejected while typing the input CSV files (file, line, column, raw value, error).
+    Rejeitados,
+
+    /// Glosa rules, stating which were applied and the selection criterion.
+    Regras,
+
+    /// Audit trail in long format: one row per EFD line and glosa rule.
+    Trilha,
+
+    /// Per-rule impact summary: base glosed and PIS/COFINS credit lost by period and CNPJ base.
+    Impacto,
+
+    /// Exemptions applied (`--excecoes`): one row per EFD line and glosa rule.
+    Excecoes,
+
+    /// Situation 06a diagnostics: documents (keys) used in more than one accounting period.
+    Situacao06a,
+
+    /// Situation 06b diagnostics: documents (CNPJ and number) used in more than one accounting period.
+    Situacao06b,
 }
 
 impl SheetContext {
+    /// Main sheets, required in every Excel file: Itens, EFD original and EFD after audit.
+    pub const OBRIGATORIAS: [Self; 3] = [Self::Itens, Self::EfdOriginal, Self::EfdAuditoria];
+
     /// Determines whether this context corresponds to the "Itens de Docs Fiscais" scope.
     #[inline]
     pub fn is_itens(self) -> bool {
@@ -78,6 +102,30 @@ impl SheetContext {
             Self::Itens => "Itens de Docs Fiscais",
             Self::EfdOriginal => "EFD (original)",
             Self::EfdAuditoria => "EFD (após auditoria)",
+            Self::Rejeitados => "Linhas Rejeitadas",
+            Self::Regras => "Regras de Glosa",
+            Self::Trilha => "Trilha de Auditoria",
+            Self::Impacto => "Impacto das Glosas",
+            Self::Excecoes => "Exceções Aplicadas",
+            Self::Situacao06a => "Situação 06a (Chave)",
+            Self::Situacao06b => "Situação 06b (CNPJ e Número)",
+        }
+    }
+
+    /// Returns the basename used for the CSV and Parquet files of this context.
+    #[inline]
+    pub const fn basename(self) -> &'static str {
+        match self {
+            Self::Itens => "df_itens_de_docs_fiscais_result",
+            Self::EfdOriginal => "df_consolidacao_natureza_da_bcalc",
+            Self::EfdAuditoria => "df_consolidacao_natureza_da_bcalc_result",
+            Self::Rejeitados => "df_linhas_rejeitadas",
+            Self::Regras => "df_regras_de_glosa",
+            Self::Trilha => "df_trilha_de_auditoria",
+            Self::Impacto => "df_impacto_das_glosas",
+            Self::Excecoes => "df_excecoes_aplicadas",
+            Self::Situacao06a => "df_situacao06a",
+            Self::Situacao06b => "df_situacao06b",
         }
     }
 
@@ -89,6 +137,20 @@ impl SheetContext {
             Ok(Self::EfdAuditoria)
         } else if name.contains(Self::EfdOriginal.as_str()) {
             Ok(Self::EfdOriginal)
+        } else if name.contains(Self::Rejeitados.as_str()) {
+            Ok(Self::Rejeitados)
+        } else if name.contains(Self::Regras.as_str()) {
+            Ok(Self::Regras)
+        } else if name.contains(Self::Trilha.as_str()) {
+            Ok(Self::Trilha)
+        } else if name.contains(Self::Impacto.as_str()) {
+            Ok(Self::Impacto)
+        } else if name.contains(Self::Excecoes.as_str()) {
+            Ok(Self::Excecoes)
+        } else if name.contains(Self::Situacao06a.as_str()) {
+            Ok(Self::Situacao06a)
+        } else if name.contains(Self::Situacao06b.as_str()) {
+            Ok(Self::Situacao06b)
         } else {
             Err(JoinError::Other(format!(
                 "Failed to resolve SheetContext from name: {name}"
@@ -98,20 +160,17 @@ impl SheetContext {
 }
 
 /// Orquestra a geração final do arquivo Excel, distribuindo conforme a estratégia de memória.
-pub fn write_xlsx(dfs: &[DataFrame], memory_mode: Option<ExcelMemoryMode>) -> JoinResult<()> {
+pub fn write_xlsx(
+    sheets: &[(DataFrame, SheetContext)],
+    memory_mode: Option<ExcelMemoryMode>,
+) -> JoinResult<()> {
     let output = "EFD Contribuicoes x Documentos Fiscais.xlsx";
     println!("Generating Excel file: {output}\n");
 
-    // Validação estrita para evitar pânico de índice fora dos limites
-    if dfs.len() != 3 {
-        return Err(JoinError::InvalidDataFrameCount {
-            expected: 3,
-            found: dfs.len(),
-        });
-    }
+    verificar_abas_obrigatorias(sheets)?;
 
     let mut workbook = Workbook::new();
-    let all_data = AllData::new(&dfs[0], &dfs[1], &dfs[2]);
+    let all_data = AllData::new(sheets);
 
     match memory_mode.unwrap_or_default() {
         ExcelMemoryMode::InMemory => {
@@ -151,6 +210,19 @@ pub fn write_xlsx(dfs: &[DataFrame], memory_mode: Option<ExcelMemoryMode>) -> Jo
     Ok(())
 }
 
+/// Verifica se as abas principais ([`SheetContext::OBRIGATORIAS`]) foram informadas.
+fn verificar_abas_obrigatorias(sheets: &[(DataFrame, SheetContext)]) -> JoinResult<()> {
+    for obrigatoria in SheetContext::OBRIGATORIAS {
+        if !sheets.iter().any(|(_, context)| *context == obrigatoria) {
+            return Err(JoinError::MissingSheet {
+                sheet: obrigatoria.as_str().to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
 /// Processa um DataFrame de origem para criar coleções de Worksheets em paralelo.
 pub fn process_sheet_type(df: &DataFrame, context: SheetContext) -> JoinResult<Vec<Worksheet>> {
     // df.is_empty()
@@ -240,7 +312,7 @@ pub fn populate_worksheet_data(
 ) -> JoinResult<()> {
     let context = SheetContext::from_name(sheet_name)?;
     let df_formatted: DataFrame = format_dataframe(df, context.is_itens())?;
-    let df_to_excel: DataFrame = format_to_excel(&df_formatted)?;
+    let df_to_excel: DataFrame = format_to_excel(&listas_como_texto(&df_formatted)?)?;
 
     let headers = df_to_excel.get_column_names();
     let col_configs: Vec<FormatKey> = headers
@@ -324,7 +396,7 @@ fn add_segmented_tables(worksheet: &mut Worksheet, df: &DataFrame) -> JoinResult
         .map(|name| {
             let n = name.as_str();
 
-            let side = if n == coluna_auditada {
+            let side = if n == coluna_auditada || is_coluna_de_glosa(n) {
                 Side::Middle
             } else {
                 side_map
@@ -506,3 +578,43 @@ fn format_to_excel(df: &DataFrame) -> PolarsResult<DataFrame> {
 
     df.clone().lazy().with_columns(exprs).collect()
 }
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_excel
+#[cfg(test)]
+mod tests_excel {
+    use super::*;
+
+    #[test]
+    /// `cargo test -- --show-output test_verificar_abas_obrigatorias`
+    fn test_verificar_abas_obrigatorias() -> JoinResult<()> {
+        let df = DataFrame::empty();
+
+        // Três abas, mas sem a EFD após auditoria.
+        let sheets = [
+            (df.clone(), SheetContext::Itens),
+            (df.clone(), SheetContext::EfdOriginal),
+            (df.clone(), SheetContext::Rejeitados),
+        ];
+
+        match verificar_abas_obrigatorias(&sheets) {
+            Err(JoinError::MissingSheet { sheet }) => {
+                assert_eq!(sheet, SheetContext::EfdAuditoria.as_str())
+            }
+            resultado => panic!("Esperava JoinError::MissingSheet, obteve: {resultado:?}"),
+        }
+
+        let sheets = [
+            (df.clone(), SheetContext::EfdAuditoria),
+            (df.clone(), SheetContext::Itens),
+            (df, SheetContext::EfdOriginal),
+        ];
+        verificar_abas_obrigatorias(&sheets)?;
+
+        Ok(())
+    }
+}
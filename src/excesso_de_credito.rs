@@ -0,0 +1,133 @@
+//! # Excesso de Crédito (Situação 17)
+//!
+//! Base de Cálculo do crédito superior ao valor do documento fiscal vinculado.
+//!
+//! O valor do documento fiscal atribuído ao item é obtido da NF-e/CT-e. O
+//! Valor da Nota Proporcional do item já é líquido dos descontos e inclui o
+//! seguro rateado, sendo comparado diretamente com a Base de Cálculo.
+//!
+//! Na ausência do valor proporcional, utiliza-se o Valor Total da nota fiscal.
+//! A Base de Cálculo do ICMS não é utilizada: pode ser nula ou reduzida
+//! (isenção, redução de base, substituição tributária) sem que o valor da
+//! operação o seja. Sem ambos os valores, a linha não é avaliada.
+//!
+//! Apenas o excesso (Base de Cálculo - Valor do Documento Fiscal) é glosado.
+
+use polars::prelude::*;
+
+use crate::{
+    Arguments, ExprExtension, JoinResult,
+    Side::{Left, Right},
+    coluna,
+};
+
+/// Coluna temporária: valor do documento fiscal atribuído ao item.
+pub const COLUNA_VALOR_DO_DOCUMENTO: &str = "Valor do Documento Fiscal Temp";
+
+/// Coluna temporária: Base de Cálculo excedente ao valor do documento fiscal.
+pub const COLUNA_EXCESSO_DE_CREDITO: &str = "Excesso de Crédito Temp";
+
+/// Adiciona as colunas temporárias da Situação 17.
+pub fn calcular_excesso_de_credito(
+    lazyframe: LazyFrame,
+    _args: &Arguments,
+) -> JoinResult<LazyFrame> {
+    let valor_bc: Expr = col(coluna(Left, "valor_bc"));
+
+    let valor_item: Expr = col(coluna(Right, "valor_item"));
+    let valor_total: Expr = col(coluna(Right, "valor_total"));
+
+    let valor_do_documento: Expr = coalesce(&[valor_item, valor_total]).round_expr(2);
+
+    let lazyframe: LazyFrame = lazyframe
+        .with_column(valor_do_documento.alias(COLUNA_VALOR_DO_DOCUMENTO))
+        .with_column(
+            (valor_bc - col(COLUNA_VALOR_DO_DOCUMENTO))
+                .round_expr(2)
+                .alias(COLUNA_EXCESSO_DE_CREDITO),
+        );
+
+    Ok(lazyframe)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_excesso_de_credito
+#[cfg(test)]
+mod tests_excesso_de_credito {
+    use super::*;
+
+    #[test]
+    /// `cargo test -- --show-output test_excesso_de_credito`
+    fn test_excesso_de_credito() -> JoinResult<()> {
+        // Linha 1: sem excesso.
+        // Linha 2: item com desconto; o valor proporcional já é líquido do desconto.
+        // Linha 3: seguro de 20 na NF, já incluído no valor proporcional.
+        // Linha 4: sem valor proporcional; utiliza-se o Valor Total da NF.
+        // Linha 5: sem NF-e.
+        let lazyframe = df! {
+            coluna(Left, "valor_bc") => [100.0, 90.0, 120.0, 205.0, 50.0],
+            coluna(Right, "valor_item") => [Some(100.0), Some(90.0), Some(100.0), None, None],
+            coluna(Right, "valor_bc_icms") => [Some(100.0), Some(100.0), Some(100.0), Some(100.0), None],
+            coluna(Right, "valor_total") => [Some(100.0), Some(90.0), Some(200.0), Some(200.0), None],
+            coluna(Right, "valor_desconto") => [None, Some(10.0), None, Some(10.0), None],
+            coluna(Right, "valor_seguro") => [None, None, Some(20.0), Some(20.0), None],
+        }?
+        .lazy();
+
+        let df = calcular_excesso_de_credito(lazyframe, &Arguments::default())?.collect()?;
+        println!("df: {df}");
+
+        let documento: Vec<Option<f64>> = df
+            .column(COLUNA_VALOR_DO_DOCUMENTO)?
+            .f64()?
+            .iter()
+            .collect();
+        let excesso: Vec<Option<f64>> = df
+            .column(COLUNA_EXCESSO_DE_CREDITO)?
+            .f64()?
+            .iter()
+            .collect();
+
+        assert_eq!(
+            documento,
+            [Some(100.0), Some(90.0), Some(100.0), Some(200.0), None]
+        );
+        assert_eq!(excesso, [Some(0.0), Some(0.0), Some(20.0), Some(5.0), None]);
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_excesso_de_credito_sem_bc_do_icms`
+    fn test_excesso_de_credito_sem_bc_do_icms() -> JoinResult<()> {
+        // Operações isentas de ICMS: Base de Cálculo do ICMS zero.
+        // Linha 1: com valor proporcional. Linha 2: sem valor proporcional (Valor Total da NF).
+        let lazyframe = df! {
+            coluna(Left, "valor_bc") => [80.0, 150.0],
+            coluna(Right, "valor_item") => [Some(80.0), None],
+            coluna(Right, "valor_bc_icms") => [Some(0.0), Some(0.0)],
+            coluna(Right, "valor_total") => [Some(150.0), Some(150.0)],
+            coluna(Right, "valor_desconto") => [Some(5.0), Some(5.0)],
+            coluna(Right, "valor_seguro") => [None::<f64>, None],
+        }?
+        .lazy();
+
+        let df = calcular_excesso_de_credito(lazyframe, &Arguments::default())?.collect()?;
+        println!("df: {df}");
+
+        let excesso: Vec<Option<f64>> = df
+            .column(COLUNA_EXCESSO_DE_CREDITO)?
+            .f64()?
+            .iter()
+            .collect();
+
+        // Nada é glosado: a Base de Cálculo do ICMS não limita o crédito.
+        assert_eq!(excesso, [Some(0.0), Some(0.0)]);
+
+        Ok(())
+    }
+}
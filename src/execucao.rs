@@ -0,0 +1,159 @@
+//! # Modo de Execução
+//!
+//! Seleciona o motor de execução do Polars para todo o processamento:
+//!
+//! - `InMemory`: os resultados intermediários são mantidos em memória
+//!   (comportamento original, mais rápido para arquivos pequenos).
+//! - `Streaming`: o plano é executado em lotes, permitindo processar arquivos
+//!   maiores que a memória RAM.
+//!
+//! O modo `Streaming` é ativado por `--streaming true` ou por `--memory-budget`.
+//! O orçamento de memória é repassado ao Polars (`POLARS_OOC_MEMORY_BUDGET_MB`):
+//! ao excedê-lo, o motor `Streaming` grava dados temporários em disco
+//! (`--spill-dir`). O motor `InMemory` não respeita este limite.
+//!
+//! Os planos permanecem lazy até o pareamento (Munkres); os resultados
+//! reutilizados em mais de uma etapa são executados uma única vez (`cache`).
+
+use polars::prelude::*;
+use std::{env, fs, path::PathBuf, sync::OnceLock};
+
+use crate::{Arguments, JoinResult};
+
+/// Motor de execução escolhido no início do programa.
+static ENGINE: OnceLock<Engine> = OnceLock::new();
+
+/// Número de linhas exibidas nas amostras impressas no terminal.
+pub const LINHAS_DE_AMOSTRA: u32 = 10;
+
+/// Configura o motor de execução do Polars a partir dos argumentos.
+///
+/// Deve ser chamada uma única vez, antes da leitura dos arquivos CSV.
+pub fn configurar_execucao(args: &Arguments) -> JoinResult<Engine> {
+    let tamanho_mb: u64 = tamanho_dos_arquivos_em_mb(&[&args.file1, &args.file2]);
+
+    let engine: Engine = escolher_engine(args.streaming, args.memory_budget);
+
+    println!("Tamanho dos arquivos de entrada: {tamanho_mb} MB");
+    if let Some(orcamento_mb) = args.memory_budget {
+        println!("Orçamento de memória: {orcamento_mb} MB");
+    }
+    println!("Motor de execução do Polars: {engine:?}\n");
+
+    Ok(*ENGINE.get_or_init(|| engine))
+}
+
+/// Configura as variáveis de ambiente lidas pelo Polars:
+///
+/// - diretório em que são gravados dados temporários em disco (`--spill-dir`);
+/// - orçamento de memória do motor `Streaming` (`--memory-budget`).
+///
+/// O Polars lê estas opções apenas de variáveis de ambiente. Como `env::set_var` não
+/// é seguro com múltiplas threads, esta função deve ser chamada no início do programa,
+/// antes de qualquer thread ser criada (Polars, sysinfo).
+pub fn configurar_variaveis_do_polars(args: &Arguments) -> JoinResult<()> {
+    if let Some(dir) = &args.spill_dir {
+        fs::create_dir_all(dir)?;
+
+        // SAFETY: chamada no início de `main`, com uma única thread em execução.
+        unsafe {
+            env::set_var("POLARS_TEMP_DIR", dir);
+            env::set_var("POLARS_OOC_SPILL_DIR", dir);
+        }
+    }
+
+    if let Some(orcamento_mb) = args.memory_budget {
+        // SAFETY: chamada no início de `main`, com uma única thread em execução.
+        unsafe {
+            env::set_var("POLARS_OOC_MEMORY_BUDGET_MB", orcamento_mb.to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Escolhe o motor de execução: `Streaming` se solicitado (`--streaming true`)
+/// ou se houver orçamento de memória (`--memory-budget`), respeitado apenas
+/// por este motor.
+fn escolher_engine(streaming: Option<bool>, memory_budget: Option<u64>) -> Engine {
+    if streaming == Some(true) || memory_budget.is_some() {
+        Engine::Streaming
+    } else {
+        Engine::InMemory
+    }
+}
+
+/// Motor de execução configurado (ou `InMemory`, se não configurado).
+pub fn engine() -> Engine {
+    ENGINE.get().copied().unwrap_or(Engine::InMemory)
+}
+
+/// Verifica se o processamento ocorre em modo `Streaming`.
+pub fn streaming_ativado() -> bool {
+    engine() == Engine::Streaming
+}
+
+/// Soma dos tamanhos dos arquivos, em megabytes.
+fn tamanho_dos_arquivos_em_mb(paths: &[&Option<PathBuf>]) -> u64 {
+    let bytes: u64 = paths
+        .iter()
+        .filter_map(|path| path.as_ref())
+        .filter_map(|path| fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    bytes.div_ceil(1024 * 1024)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_execucao
+#[cfg(test)]
+mod tests_execucao {
+    use super::*;
+    use std::{fs::File, io::Write};
+    use tempfile::tempdir;
+
+    #[test]
+    /// `cargo test -- --show-output test_tamanho_dos_arquivos_em_mb`
+    fn test_tamanho_dos_arquivos_em_mb() -> JoinResult<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("efd.csv");
+        File::create(&file_path)?.write_all(&[b'a'; 1024 * 1024 + 1])?;
+
+        let existente: Option<PathBuf> = Some(file_path);
+        let inexistente: Option<PathBuf> = Some(dir.path().join("nfe.csv"));
+
+        assert_eq!(tamanho_dos_arquivos_em_mb(&[&existente, &None]), 2);
+        assert_eq!(tamanho_dos_arquivos_em_mb(&[&inexistente]), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_escolher_engine`
+    fn test_escolher_engine() {
+        // Sem opções: processamento em memória
+        assert_eq!(escolher_engine(None, None), Engine::InMemory);
+        assert_eq!(escolher_engine(Some(false), None), Engine::InMemory);
+
+        // --streaming true
+        assert_eq!(escolher_engine(Some(true), None), Engine::Streaming);
+        assert_eq!(escolher_engine(Some(true), Some(1_000)), Engine::Streaming);
+
+        // --memory-budget: o orçamento é respeitado apenas pelo motor Streaming
+        assert_eq!(escolher_engine(None, Some(1_000)), Engine::Streaming);
+        assert_eq!(escolher_engine(Some(false), Some(600)), Engine::Streaming);
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_engine_nao_configurado`
+    fn test_engine_nao_configurado() {
+        // Os testes não chamam `configurar_execucao`: o motor padrão é `InMemory`.
+        assert_eq!(engine(), Engine::InMemory);
+        assert!(!streaming_ativado());
+    }
+}
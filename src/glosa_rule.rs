@@ -0,0 +1,213 @@
+//! # GlosaRule
+//!
+//! Interface pública das situações de glosa.
+//!
+//! As regras do arquivo TOML ([`RegraDeGlosa`]) implementam este trait, assim
+//! como as regras próprias de bibliotecas clientes, acrescentadas ao registro
+//! com [`RegrasDeGlosa::registrar`]. `glosar_bc` percorre o registro sem
+//! distinguir a origem das regras: seleção (`--situacoes`), precedência,
+//! mensagem, valor glosado por regra, trilha de auditoria e resumo de impacto
+//! valem igualmente para todas.
+//!
+//! [`RegraDeGlosa`]: crate::RegraDeGlosa
+
+use polars::prelude::*;
+use std::fmt::Debug;
+
+use crate::{Arguments, JoinError, JoinResult};
+
+/**
+Situação de glosa aplicada por `glosar_bc`.
+
+Apenas `id`, `description`, `condition` e `message` são obrigatórios; por
+padrão, a regra está ativa, tem precedência zero e glosa integralmente a
+Base de Cálculo das linhas em que `condition` é verdadeira.
+
+### Example of usage:
+```
+use polars::prelude::*;
+use join_with_assignments::{GlosaRule, JoinResult, RegrasDeGlosa};
+
+#[derive(Debug)]
+struct FreteSemConhecimento;
+
+impl GlosaRule for FreteSemConhecimento {
+    fn id(&self) -> &str {
+        "x01"
+    }
+
+    fn description(&self) -> &str {
+        "Frete na operação de venda sem CT-e vinculado"
+    }
+
+    fn condition(&self) -> JoinResult<Expr> {
+        Ok(col("Natureza da Base de Cálculo dos Créditos")
+            .eq(lit(7))
+            .and(col("Chave do CT-e").is_null()))
+    }
+
+    fn message(&self) -> JoinResult<Expr> {
+        Ok(lit("Frete sem Conhecimento de Transporte vinculado."))
+    }
+}
+
+fn main() -> JoinResult<()> {
+    let regras = RegrasDeGlosa::padrao()?.registrar(FreteSemConhecimento)?;
+
+    let ids: Vec<&str> = regras.ativas().map(|regra| regra.id()).collect();
+    assert_eq!(ids.last(), Some(&"x01"));
+
+    // Identificadores duplicados são rejeitados.
+    assert!(regras.registrar(FreteSemConhecimento).is_err());
+
+    Ok(())
+}
+```
+*/
+pub trait GlosaRule: Debug + Send + Sync {
+    /// Identificador único da regra (ex.: "01", "06a").
+    fn id(&self) -> &str;
+
+    /// Número da situação exibido na mensagem. Por padrão, igual ao `id`.
+    fn situation(&self) -> &str {
+        self.id()
+    }
+
+    fn description(&self) -> &str;
+
+    fn legal_basis(&self) -> Option<&str> {
+        None
+    }
+
+    /// Regras inativas são aplicadas apenas quando selecionadas com `--situacoes`.
+    fn enabled(&self) -> bool {
+        true
+    }
+
+    /// Regras de maior precedência são aplicadas primeiro
+    /// (empates mantêm a ordem de registro).
+    fn precedence(&self) -> i32 {
+        0
+    }
+
+    /// Procedimento executado antes da condição (agrupamentos, joins,
+    /// colunas auxiliares).
+    fn prepare(&self, lazyframe: LazyFrame, _args: &Arguments) -> JoinResult<LazyFrame> {
+        Ok(lazyframe)
+    }
+
+    /// Colunas criadas em `prepare`, removidas após a aplicação da regra.
+    fn temporary_columns(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Condição de aplicação da glosa.
+    fn condition(&self) -> JoinResult<Expr>;
+
+    /// Texto acrescentado à coluna `glosar` após "Situação <situação>:".
+    fn message(&self) -> JoinResult<Expr>;
+
+    /// Novo valor da Base de Cálculo nas linhas glosadas.
+    /// Por padrão, zero (glosa integral).
+    fn adjustment(&self) -> JoinResult<Expr> {
+        Ok(lit(0.0))
+    }
+}
+
+/// Compila as expressões da regra a fim de detectar erros antes da execução.
+pub(crate) fn validar_regra(regra: &dyn GlosaRule) -> JoinResult<()> {
+    if regra.id().trim().is_empty() {
+        return Err(JoinError::InvalidGlosaRule {
+            id: regra.id().to_string(),
+            motivo: "identificador vazio".to_string(),
+        });
+    }
+
+    let _ = regra.condition()?;
+    let _ = regra.message()?;
+    let _ = regra.adjustment()?;
+
+    Ok(())
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_glosa_rule
+#[cfg(test)]
+mod tests_glosa_rule {
+    use super::*;
+    use crate::{RegrasDeGlosa, SelecaoDeSituacoes};
+
+    #[derive(Debug)]
+    struct RegraDeTeste {
+        id: &'static str,
+        precedencia: i32,
+    }
+
+    impl GlosaRule for RegraDeTeste {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn description(&self) -> &str {
+            "Regra de teste"
+        }
+
+        fn enabled(&self) -> bool {
+            false
+        }
+
+        fn precedence(&self) -> i32 {
+            self.precedencia
+        }
+
+        fn condition(&self) -> JoinResult<Expr> {
+            Ok(col("Valor da Base de Cálculo das Contribuições").gt(lit(100)))
+        }
+
+        fn message(&self) -> JoinResult<Expr> {
+            Ok(lit("Regra de teste."))
+        }
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_registro_de_regras`
+    fn test_registro_de_regras() -> JoinResult<()> {
+        let regras = RegrasDeGlosa::padrao()?
+            .registrar(RegraDeTeste {
+                id: "x01",
+                precedencia: 0,
+            })?
+            .registrar(RegraDeTeste {
+                id: "x02",
+                precedencia: 10,
+            })?;
+
+        // Regras inativas não são aplicadas sem seleção explícita.
+        assert!(regras.ativas().all(|regra| !regra.id().starts_with('x')));
+
+        let incluir: Vec<String> = vec!["01".to_string(), "x01".to_string(), "x02".to_string()];
+        let selecao = SelecaoDeSituacoes::new(Some(&incluir), None);
+        let regras = regras.com_selecao(selecao)?;
+
+        // Precedência: "x02" antes das demais.
+        let ids: Vec<&str> = regras.ativas().map(|regra| regra.id()).collect();
+        assert_eq!(ids, ["x02", "01", "x01"]);
+
+        let df = regras.to_dataframe()?;
+        println!("df: {df}");
+        assert_eq!(df.height(), RegrasDeGlosa::padrao()?.regras.len() + 2);
+
+        // Identificador já existente no arquivo de regras.
+        let duplicada = RegraDeTeste {
+            id: "01",
+            precedencia: 0,
+        };
+        assert!(regras.registrar(duplicada).is_err());
+
+        Ok(())
+    }
+}
@@ -1,12 +1,14 @@
-use std::{env, fs::File, io::Write};
-
 use crate::{
-    Arguments, DataFrameExtension, ExprExtension, JoinResult, LazyFrameExtension,
+    Arguments, DataFrameExtension, Excecao, ExcecoesDeGlosa, ExprExtension, GlosaRule, JoinResult,
+    LazyFrameExtension, PREFIXO_LINHA_GLOSADA, RegrasDeGlosa,
     Side::{Left, Middle, Right},
-    ToLiteralListExpr, adicionar_coluna_periodo_de_apuracao_inicial_e_final, codigo_nat_01, coluna,
-    configure_the_environment, cst_50_a_56, equal, format_list_dates, operacoes_de_credito,
+    ToLiteralListExpr, adicionar_coluna_limite_de_decadencia,
+    adicionar_coluna_periodo_de_apuracao_inicial_e_final, calcular_excesso_de_credito, coluna,
+    coluna_valor_glosado, confrontar_cfop,
+    excecoes_de_glosa::RegraComExcecoes,
+    format_list_dates, marcar_creditos_estornados, marcar_despesas_de_revenda,
+    operacoes_de_credito,
     regime_fiscal::*,
-    unequal,
 };
 use polars::prelude::*;
 
@@ -17,6 +19,12 @@ pub const CFOP_DE_ARMAZENAGEM: [i32; 10] =
 /// CFOP de Industrialização por encomenda
 pub const CFOP_DE_INDUSTRIALIZACAO: [i32; 8] = [1124, 1125, 2124, 2125, 5124, 5125, 6124, 6125];
 
+/// Temporary column with the accounting periods of each Fiscal Document (Situations 06a, 06b and 06c).
+const PERIODOS_DE_APURACAO: &str = "Períodos de Apuração";
+
+/// Temporary column with the unified EFD and NFe keys (Situations 06a and 06c).
+const CHAVES_UNIFICADAS: &str = "Chaves de Documentos Fiscais";
+
 /*
 // ### --- cte_valor.csv --- ###
 let myschema = Schema::from_iter([
@@ -39,48 +47,126 @@ println!("cte_valor_lazyframe: {:?}", cte_valor_lazyframe.collect()?);
 
 /// Performs tax basis adjustments (Glosas) based on multiple fiscal compliance rules.
 ///
-/// This function chains several analysis steps, each representing a "Situation"
-/// where the tax credit might be disallowed or adjusted according to Brazilian legislation.
-pub fn glosar_bc(dataframe: &DataFrame, args: &Arguments) -> JoinResult<DataFrame> {
+/// Each "Situation" where the tax credit might be disallowed or adjusted according to
+/// Brazilian legislation is declared in the rules file (see `regras_de_glosa.rs`) or
+/// registered by library users (see `GlosaRule`). The selected rules
+/// (see `RegrasDeGlosa::ativas`) are compiled, in order, into the same lazy pipeline.
+///
+/// The operations exempted from specific situations (`--excecoes`) are loaded once
+/// by the caller (see `ExcecoesDeGlosa::carregar`).
+pub fn glosar_bc(
+    dataframe: &DataFrame,
+    regras: &RegrasDeGlosa,
+    excecoes: Option<&ExcecoesDeGlosa>,
+    args: &Arguments,
+) -> JoinResult<DataFrame> {
+    regras.imprimir_selecao();
+
     let lazyframe: LazyFrame = dataframe.clone().lazy();
 
     // Inject tax-related auxiliary columns
     let lazyframe: LazyFrame = adicionar_coluna_de_aliquota_zero(lazyframe)?;
     let lazyframe: LazyFrame = adicionar_coluna_de_credito_presumido(lazyframe)?;
     let lazyframe: LazyFrame = adicionar_coluna_de_incidencia_monofasica(lazyframe)?;
+    let lazyframe: LazyFrame = adicionar_coluna_de_suspensao(lazyframe)?;
+    let lazyframe: LazyFrame = adicionar_coluna_de_zona_franca(lazyframe)?;
+
+    // CBS/IBS (LC nº 214/2025): períodos a partir de 01/2027
+    let lazyframe: LazyFrame = adicionar_coluna_de_cbs_ibs_aliquota_zero(lazyframe)?;
+    let lazyframe: LazyFrame = adicionar_coluna_de_cbs_ibs_aliquota_reduzida(lazyframe)?;
+    let lazyframe: LazyFrame = adicionar_coluna_de_cbs_ibs_credito_presumido(lazyframe)?;
+
+    let valor_bc: &str = coluna(Left, "valor_bc");
+    let valor_glosado: &str = coluna(Middle, "valor_glosado");
+
+    // Keep the original tax basis to compute the total glosed amount.
+    let mut lazyframe: LazyFrame = lazyframe
+        .adicionar_colunas_auxiliares()
+        .with_column(col(valor_bc).alias(valor_glosado));
+
+    // Sequence of analysis "Situations", in order of precedence
+    for regra in regras.ativas() {
+        let excecoes_da_regra: Vec<&Excecao> = excecoes
+            .iter()
+            .flat_map(|excecoes| excecoes.da_regra(regra))
+            .collect();
+
+        lazyframe = if excecoes_da_regra.is_empty() {
+            aplicar_regra(lazyframe, regra, args)?
+        } else {
+            aplicar_regra(
+                lazyframe,
+                &RegraComExcecoes::new(regra, excecoes_da_regra),
+                args,
+            )?
+        };
+    }
 
-    let lazyframe: LazyFrame = lazyframe.adicionar_colunas_auxiliares();
-
-    // Sequence of analysis "Situations"
-    let lazyframe: LazyFrame = analisar_situacao01(lazyframe)?;
-    let lazyframe: LazyFrame = analisar_situacao02(lazyframe, args)?;
-    let lazyframe: LazyFrame = analisar_situacao03(lazyframe)?;
-    let lazyframe: LazyFrame = analisar_situacao04(lazyframe)?;
-    // let lazyframe: LazyFrame = analisar_situacao05(lazyframe)?;
-    let lazyframe: LazyFrame = analisar_situacao06a(lazyframe)?;
-    let lazyframe: LazyFrame = analisar_situacao06b(lazyframe)?;
-    let lazyframe: LazyFrame = analisar_situacao07(lazyframe)?;
-    let lazyframe: LazyFrame = analisar_situacao08(lazyframe)?;
-    let lazyframe: LazyFrame = analisar_situacao09(lazyframe)?;
-    let lazyframe: LazyFrame = analisar_situacao10(lazyframe)?;
-    let lazyframe: LazyFrame = analisar_situacao11(lazyframe)?;
-    let lazyframe: LazyFrame = analisar_situacao12(lazyframe)?;
-    // let lazyframe: LazyFrame = analisar_situacao13(lazyframe)?;
-    // let lazyframe: LazyFrame = analisar_situacao14(lazyframe)?;
+    let ids: Vec<&str> = regras.ativas().map(|regra| regra.id()).collect();
+    let lazyframe: LazyFrame =
+        adicionar_situacoes_de_glosa(lazyframe, &ids, args.colunas_por_situacao == Some(true))?;
 
     Ok(lazyframe
         //.remover_colunas_auxiliares()
+        .with_column(
+            (col(valor_glosado) - col(valor_bc))
+                .round_expr(2)
+                .alias(valor_glosado),
+        )
         .format_values()
-        .collect()?
+        .coletar()?
         .sort_by_columns(None)?)
 }
 
+/// Adds the structured glosa columns, built from the per-rule glosed amounts:
+///
+/// - "Situações de Glosa": list of the situations that hit the line, in order of application;
+/// - "Linha Glosada: Situação <id>": one boolean column per rule (optional).
+fn adicionar_situacoes_de_glosa(
+    lazyframe: LazyFrame,
+    ids: &[&str],
+    colunas_por_situacao: bool,
+) -> JoinResult<LazyFrame> {
+    let situacoes: &str = coluna(Middle, "situacoes");
+
+    // A rule hits the line when its glosed amount is not null.
+    let glosada = |id: &str| col(coluna_valor_glosado(id)).is_not_null();
+
+    let codigos: Vec<Expr> = ids
+        .iter()
+        .map(|&id| {
+            when(glosada(id))
+                .then(lit(id))
+                .otherwise(lit(NULL).cast(DataType::String))
+        })
+        .collect();
+
+    let lista: Expr = if codigos.is_empty() {
+        lit(NULL).cast(DataType::List(Box::new(DataType::String)))
+    } else {
+        concat_list(codigos)?.list().drop_nulls()
+    };
+
+    let mut lazyframe: LazyFrame = lazyframe.with_column(lista.alias(situacoes));
+
+    if colunas_por_situacao {
+        let booleanas: Vec<Expr> = ids
+            .iter()
+            .map(|&id| glosada(id).alias(format!("{PREFIXO_LINHA_GLOSADA}{id}")))
+            .collect();
+
+        lazyframe = lazyframe.with_columns(booleanas);
+    }
+
+    Ok(lazyframe)
+}
+
 /// Checks if the tax regime (CRT) belongs to 'Simples Nacional' or MEI (1 or 4).
 ///
 /// Código de Regime Tributário (CRT) igual a 1 ou 4 com direito a crédito
 ///
 /// Returns a boolean Expression.
-fn optante_do_simples_nacional_ou_mei() -> JoinResult<Expr> {
+pub(crate) fn optante_do_simples_nacional_ou_mei() -> JoinResult<Expr> {
     let regime_tributario: &str = coluna(Right, "regime_tributario"); // "CRT : NF (Todos)"
 
     let series = Series::from_iter([1, 4]);
@@ -90,358 +176,146 @@ fn optante_do_simples_nacional_ou_mei() -> JoinResult<Expr> {
     Ok(col(regime_tributario).is_in(literal_series, true))
 }
 
-/// **Situation 01:** Disallows credits from canceled NF-e/CT-e documents.
-///
-/// Based on CONFAZ Sinief adjustments and RIPI Art. 327.
-fn analisar_situacao01(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let cancelada: &str = coluna(Right, "cancelada");
-
-    let pattern: Expr = lit(r"(?i)^\s*Sim"); // regex
-    let docs_cancelados: Expr = col(cancelada).str().contains(pattern, false);
-
-    let situacao_01: Expr = operacoes_de_credito()?
-        .and(col(cancelada).is_not_null())
-        .and(docs_cancelados);
-
-    println!("situacao_01: {situacao_01:?}\n");
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 01:"),
-            lit("Documento Fiscal (NFe/CTe) Cancelado"),
-            lit("(Art. 1º, § 3º, inciso V, alínea “a” das Leis nº 10.637/2002 e nº 10.833/2003)."),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
-
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_01, mensagem, lit(0))?;
-
-    Ok(lf_result)
+/// Procedimento executado antes do filtro de uma regra (campo `preparo`).
+pub(crate) type Preparo = fn(LazyFrame, &Arguments) -> JoinResult<LazyFrame>;
+
+/// Procedimentos disponíveis no arquivo de regras.
+pub(crate) fn obter_preparo(nome: &str) -> Result<Preparo, String> {
+    match nome {
+        "periodo_de_apuracao_inicial_e_final" => {
+            Ok(adicionar_coluna_periodo_de_apuracao_inicial_e_final)
+        }
+        "limite_de_decadencia" => Ok(adicionar_coluna_limite_de_decadencia),
+        "documentos_em_multiplos_periodos_por_chave" => Ok(preparar_situacao06a),
+        "documentos_em_multiplos_periodos_por_cnpj_e_numero" => Ok(preparar_situacao06b),
+        "documentos_em_multiplos_estabelecimentos_por_chave" => Ok(preparar_situacao06c),
+        "creditos_estornados" => Ok(marcar_creditos_estornados),
+        "correspondencia_de_cfop" => Ok(confrontar_cfop),
+        "excesso_de_credito" => Ok(calcular_excesso_de_credito),
+        "despesas_de_revenda" => Ok(marcar_despesas_de_revenda),
+        _ => Err(format!("preparo desconhecido '{nome}'")),
+    }
 }
 
-/// **Situation 02:** Disallows extemporaneous credits (issued outside the calculation period).
-fn analisar_situacao02(lazyframe: LazyFrame, args: &Arguments) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let dia_emissao: &str = coluna(Right, "dia_emissao"); // "Dia da Emissão : NF Item (Todos)",
+/// Aplica uma regra de glosa: preparo, condição, mensagem e ajuste.
+fn aplicar_regra(
+    lazyframe: LazyFrame,
+    regra: &dyn GlosaRule,
+    args: &Arguments,
+) -> JoinResult<LazyFrame> {
+    let lazyframe: LazyFrame = regra.prepare(lazyframe, args)?;
 
-    // Adicionar 2 colunas temporárias: "Período de Apuração Inicial" e "Período de Apuração Final".
-    let pa_ini: &str = "Período de Apuração Inicial";
-    let pa_fim: &str = "Período de Apuração Final";
+    let colunas_temporarias: Vec<String> = regra.temporary_columns();
 
-    let lazyframe: LazyFrame =
-        adicionar_coluna_periodo_de_apuracao_inicial_e_final(lazyframe, args)?;
-
-    let situacao_02: Expr = operacoes_de_credito()?
-        .and(col(dia_emissao).is_not_null())
-        .and(
-            col(dia_emissao)
-                .lt(col(pa_ini))
-                .or(col(dia_emissao).gt(col(pa_fim))),
-        );
+    let situacao: Expr = regra.condition()?;
 
-    println!("situacao_02: {situacao_02:?}\n");
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 02:"),
-            lit("Crédito extemporâneo."),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
+    println!("situacao_{}: {situacao:?}\n", regra.id());
 
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_02, mensagem, lit(0))?;
+    let lf_result: LazyFrame = aplicar_situacao(
+        lazyframe,
+        situacao,
+        mensagem_de_glosa(regra)?,
+        regra.adjustment()?,
+        &coluna_valor_glosado(regra.id()),
+    )?;
 
-    // Remover 2 colunas temporárias
-    let lf_result: LazyFrame = lf_result.drop_columns(&[pa_ini, pa_fim])?;
+    // Remover colunas temporárias
+    if colunas_temporarias.is_empty() {
+        return Ok(lf_result);
+    }
 
-    Ok(lf_result)
+    let colunas_temporarias: Vec<&str> = colunas_temporarias.iter().map(String::as_str).collect();
+
+    Ok(lf_result.drop_columns(&colunas_temporarias)?)
 }
 
-/// **Situation 03:** Disallows credits for items with zero tax rate or monophasic incidence.
-///
-/// Based on Laws 10.637/2002 and 10.833/2003.
-fn analisar_situacao03(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let verificacao: &str = coluna(Middle, "verificacao"); // "Verificação dos Valores: EFD x Docs Fiscais";
+/// Mensagem acrescentada à coluna `glosar` nas linhas glosadas:
+/// "Situação <situação>: <mensagem da regra> &".
+fn mensagem_de_glosa(regra: &dyn GlosaRule) -> JoinResult<Expr> {
     let glosar: &str = coluna(Middle, "glosar");
-    let cfop: &str = coluna(Right, "cfop");
-    let origem_do_item: &str = coluna(Right, "origem"); // "Registro de Origem do Item : NF Item (Todos)"
-    let regime_tributario: &str = coluna(Right, "regime_tributario"); // "CRT : NF (Todos)"
 
-    let aliq_pis: &str = coluna(Right, "aliq_pis");
-    let aliq_cof: &str = coluna(Right, "aliq_cof");
-
-    let columns: Vec<&str> = vec![
-        "Alíquota Zero",
-        "Alíquota Zero Temp", // coluna temporária
-        "Incidência Monofásica",
-        "Incidência Monofásica Temp", // coluna temporária
+    let segmentos: [Expr; 4] = [
+        col(glosar),
+        lit(format!("Situação {}:", regra.situation())),
+        regra.message()?,
+        lit("&"),
     ];
 
-    let pattern01: Expr = lit(r"(?i)NFe"); // regex
-    let nfe: Expr = col(origem_do_item).str().contains(pattern01, false);
-
-    // CFOPs relacionados a Serviços de Armazanagem ou Industrialização por encomenda.
-    let cfop_valido = [
-        CFOP_DE_ARMAZENAGEM.as_slice(),
-        CFOP_DE_INDUSTRIALIZACAO.as_slice(),
-    ]
-    .concat();
-    let series: Series = cfop_valido.iter().collect();
-    let literal_series: Expr = series.to_list_expr()?;
-
-    // Estes serviços são insumos com direito à crédito das Contribuições que devem ser excluídos das glosas.
-    let cfop_de_insumos: Expr = col(cfop).is_in(literal_series, true);
-
-    // Alíquotas de PIS/PASEP e de COFINS com valores não positivos
-    let aliquotas_pis_sem_valor: Expr = col(aliq_pis).is_null().or(col(aliq_pis).eq(lit(0)));
-    let aliquotas_cof_sem_valor: Expr = col(aliq_cof).is_null().or(col(aliq_cof).eq(lit(0)));
-
-    let aliquotas_sem_valor: Expr = aliquotas_pis_sem_valor.and(aliquotas_cof_sem_valor);
-
-    // No Regime Monofásico não é permitida a constituição de créditos sobre o custo
-    // de aquisição de bens sujeitos à tributação monofásica quando destinados à revenda.
-    let aquisicao_de_monofasico_para_revenda: Expr = codigo_nat_01()?;
-
-    let filter: Expr = operacoes_de_credito()?
-        .and(cst_50_a_56()?) // Excluir crédito Presumido da Agroindústria
-        .and(
-            col(regime_tributario)
-                .is_null()
-                .or(optante_do_simples_nacional_ou_mei()?.not()),
-        )
-        .and(col(origem_do_item).is_null().or(nfe))
-        .and(col(cfop).is_null().or(cfop_de_insumos.not()))
-        .and(col(verificacao).is_not_null())
-        .and(aliquotas_sem_valor);
-
-    // Adicionar coluna temporária
-    let lazyframe: LazyFrame = lazyframe
-        .with_column(
-            when(filter.clone())
-                .then(columns[0]) // keep original value: "Alíquota Zero"
-                .otherwise(lit(NULL)) // replace by null
-                .alias(columns[1]), // Coluna Temporária
-        )
-        .with_column(
-            when(filter.and(aquisicao_de_monofasico_para_revenda))
-                .then(columns[2]) // keep original value: "Incidência Monofásica"
-                .otherwise(lit(NULL)) // replace by null
-                .alias(columns[3]), // Coluna Temporária
-        );
-
-    let situacao_03: Expr = col(columns[1])
-        .is_not_null()
-        .or(col(columns[3]).is_not_null());
-
-    println!("situacao_03: {situacao_03:?}\n");
-
-    // Construção das mensagens específicas para incidência monofásica
-    let msg_monofasica: Expr = when(col(columns[3]).is_not_null())
-        .then(concat_str(
-            [
-                lit("Bens sujeitos à tributação monofásica destinados à revenda:"),
-                col(columns[3]),
-            ],
-            " ",
-            true,
-        ))
-        .otherwise(lit(NULL));
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 03:"),
-            lit("Aquisição de bens ou serviços não sujeitos ao pagamento da contribuição."),
-            lit("De acordo com o inciso II do § 2º do art. 3º das Leis 10.637/2002 e 10.833/2003,"),
-            lit("não dará direito a crédito o valor da aquisição de bens ou serviços"),
-            lit("não sujeitos ao pagamento da contribuição."),
-            lit("Conforme NFe/CTe de aquisição,"),
-            lit("foram constatadas alíquotas de PIS/PASEP e de COFINS iguais a Zero."),
-            col(columns[1]),
-            msg_monofasica,
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
-
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_03, mensagem, lit(0))?;
-
-    // Remover coluna temporária
-    let lf_result: LazyFrame = lf_result.drop_columns(&[columns[1], columns[3]])?;
-
-    Ok(lf_result)
+    Ok(concat_str(segmentos, " ", true))
 }
 
-/// **Situation 04:** Checks if freight was improperly added to the input basis when paid by the sender.
-fn analisar_situacao04(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let valor_total_do_item: &str = coluna(Left, "valor_item"); // "Valor Total do Item"
-    let valor_bc: &str = coluna(Left, "valor_bc"); // "Valor da Base de Cálculo das Contribuições"
-    let valor_da_nota_proporcional_nfe: &str = coluna(Right, "valor_item"); // "Valor da Nota Proporcional : NF Item (Todos) SOMA"
-
-    let tomador1: &str = coluna(Right, "tomador_papel1");
-    let tomador2: &str = coluna(Right, "tomador_papel2");
-
-    let cnpj_base_do_contribuinte = "CNPJ Base do Contribuinte";
-    let cnpj_base_do_remetente = "CNPJ Base do Remetente";
-    let cnpj_base_do_destinatario = "CNPJ Base do Destinatário";
-    let valor_cte_vinculado = "Valor Total de Documentos Vinculados";
-
-    // "CNPJ Base do Contribuinte" eq "CNPJ Base do Destinatário"
-    // O Contribuinte é o Destinatário das operações.
-    let destinatario_das_operacoes: Expr =
-        equal(cnpj_base_do_contribuinte, cnpj_base_do_destinatario);
-
-    // "CNPJ Base do Remetente"  eq "CNPJ Base do Destinatário": operação de transferência
-    // "CNPJ Base do Remetente" neq "CNPJ Base do Destinatário": operação de compra
-    let cnpjs_distintos: Expr = unequal(cnpj_base_do_remetente, cnpj_base_do_contribuinte);
-
-    let operacao_de_compra: Expr = destinatario_das_operacoes.and(cnpjs_distintos);
-
-    let valores_iguais: Expr = equal(valor_bc, valor_da_nota_proporcional_nfe);
-
-    // O Tomador do CTe é o Remetente
-    let pattern: Expr = lit(r"(?i)Remetente"); // regex
-    let tomador_remetente1: Expr = col(tomador1).str().contains(pattern.clone(), false);
-    let tomador_remetente2: Expr = col(tomador2).str().contains(pattern, false);
-    let tomador_remetente: Expr = tomador_remetente1.or(tomador_remetente2);
-
-    let cte_valor_minimo = col(valor_cte_vinculado).gt(lit(10));
-    let delta: Expr = col(valor_bc) - col(valor_total_do_item) - col(valor_cte_vinculado);
-    let base_calculo_superestimada = delta.gt_eq(lit(-0.02));
-    let valor_justo: Expr = col(valor_bc) - col(valor_cte_vinculado);
-
-    let situacao_04: Expr = operacoes_de_credito()?
-        .and(optante_do_simples_nacional_ou_mei()?.not())
-        .and(valores_iguais)
-        .and(operacao_de_compra)
-        .and(tomador_remetente)
-        .and(cte_valor_minimo)
-        .and(base_calculo_superestimada);
-
-    println!("situacao_04: {situacao_04:?}\n");
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 04:"),
-            lit("Acréscimo Indevido do Frete na Base de Cálculo do Crédito,"),
-            lit(
-                "tal que o fornecedor do insumo quem efetuou o pagamento do frete, remetente tomador.",
-            ),
-            lit(
-                "Ver colunas: [CTe - Remetente das mercadorias transportadas: CNPJ/CPF de Conhecimento] e",
-            ),
-            lit("[Descrição CTe - Indicador do 'papel' do tomador do serviço de Conhecimento] e"),
-            lit(
-                "[CNPJ Base do Remetente] e [CNPJ Base do Destinatário] e [Valor Total de Documentos Vinculados].",
-            ),
-            lit("Valor da Base de Cálculo = "),
-            col(valor_bc).round_expr(2),
-            lit("-"),
-            col(valor_cte_vinculado).round_expr(2),
-            lit("="),
-            valor_justo.clone().round_expr(2),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
-
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_04, mensagem, valor_justo)?;
-
-    Ok(lf_result)
+/// Diagnostic table of Situation 06a: documents (keys) used in more than one accounting period.
+///
+/// One row per key, with the periods, the valid (first) period, the invalid periods and
+/// the sum of the item values. Written as a dedicated sheet (see `SheetContext::Situacao06a`).
+///
+/// The table is empty when the rule "06a" is not active.
+pub fn obter_documentos_em_multiplos_periodos_por_chave(
+    df_itens: &DataFrame,
+    regras: &RegrasDeGlosa,
+) -> JoinResult<DataFrame> {
+    let lazyframe: LazyFrame = unificar_chaves(df_itens.clone().lazy());
+
+    obter_diagnostico(
+        agrupar_situacao06a(lazyframe),
+        regras,
+        "06a",
+        "Documentos Fiscais (Chave) utilizados em Períodos de Apuração distintos",
+    )
 }
 
-#[allow(dead_code)]
-fn analisar_situacao05(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let valor_bc: &str = coluna(Left, "valor_bc"); // "Valor da Base de Cálculo das Contribuições"
-    let valor_da_nota_proporcional_nfe: &str = coluna(Right, "valor_item"); // "Valor da Nota Proporcional : NF Item (Todos) SOMA";
-    // let valor_da_base_calculo_icms_nfe: &str = coluna(Right, "valor_bc_icms"); // "ICMS: Base de Cálculo : NF Item (Todos) SOMA"
-
-    let valores_iguais_nota_prop: Expr = equal(valor_bc, valor_da_nota_proporcional_nfe);
-    //let valores_iguais_base_icms: Expr = col(valor_da_bcal_da_efd).eq(col(valor_da_base_calculo_icms_nfe));
-
-    let delta: Expr = col(valor_bc) - col("ICMS: Valor do Tributo : NF Item (Todos) SOMA");
-    let boolean = col("ICMS: Valor do Tributo : NF Item (Todos) SOMA").gt(lit(0));
-
-    let situacao_05: Expr = operacoes_de_credito()?
-        .and(valores_iguais_nota_prop)
-        .and(boolean);
-
-    println!("situacao_05: {situacao_05:?}\n");
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 05:"),
-            lit(
-                "Excluir valor do ICMS destacado em Nota Fiscal da Base de Cálculo das Contribuições.",
-            ),
-            lit("O valor da Base de Cálculo foi alterado de"),
-            col(valor_bc).round_expr(2),
-            lit("para"),
-            delta.clone().round_expr(2),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
+/// Diagnostic table of Situation 06b: documents (CNPJ and number) used in more than
+/// one accounting period.
+///
+/// Written as a dedicated sheet (see `SheetContext::Situacao06b`).
+/// The table is empty when the rule "06b" is not active.
+pub fn obter_documentos_em_multiplos_periodos_por_cnpj_e_numero(
+    df_itens: &DataFrame,
+    regras: &RegrasDeGlosa,
+) -> JoinResult<DataFrame> {
+    obter_diagnostico(
+        agrupar_situacao06b(df_itens.clone().lazy()),
+        regras,
+        "06b",
+        "Documentos Fiscais (CNPJ e Número) utilizados em Períodos de Apuração distintos",
+    )
+}
 
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_05, mensagem, delta)?;
+/// Collects the grouped table of a rule, without the formatted periods
+/// (the list of periods is kept), or an empty table if the rule is not active.
+fn obter_diagnostico(
+    lz_groupby: LazyFrame,
+    regras: &RegrasDeGlosa,
+    id: &str,
+    descricao: &str,
+) -> JoinResult<DataFrame> {
+    let ativa: bool = regras.ativas().any(|regra| regra.id() == id);
+
+    let lz_groupby: LazyFrame = if ativa {
+        lz_groupby
+    } else {
+        lz_groupby.limit(0)
+    };
+
+    let df_diagnostico: DataFrame = lz_groupby
+        .drop_columns(&["Períodos Formatados"])?
+        .coletar()?;
+
+    if ativa {
+        println!("{descricao}: {}\n", df_diagnostico.height());
+    }
 
-    Ok(lf_result)
+    Ok(df_diagnostico)
 }
 
-/// **Situation 06a:** Identifies Fiscal Documents (Keys) used across multiple accounting periods.
+/// Unifies EFD and NFe keys into the temporary column "Chaves de Documentos Fiscais".
 ///
-/// This analysis checks if the same unique access key (from EFD or NF-e) appears in different
-/// months. According to Brazilian tax law, a credit should generally be claimed in the period
-/// of the fiscal event, unless it is a formal extemporaneous credit.
-///
-/// This function unifies EFD and NF-e keys, identifies duplicates across periods,
-/// and disallows the credit in subsequent periods if the sum of items exceeds the total
-/// value of the document.
-fn analisar_situacao06a(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let periodo_de_apuracao: &str = coluna(Left, "pa"); // "Período de Apuração",
+/// Prioritize the EFD key if it is not null, otherwise retain the NFe key.
+fn unificar_chaves(lazyframe: LazyFrame) -> LazyFrame {
     let chave_efd: &str = coluna(Left, "chave");
     let chave_nfe: &str = coluna(Right, "chave");
-    let valor_item = coluna(Left, "valor_item");
-    let soma_dos_itens: &str = "Soma dos Valores dos Itens";
-    let valor_total: &str = coluna(Right, "valor_total"); // "Valor Total : NF (Todos) SOMA"
-    let len_min = 10;
-
-    // Temporary column names for internal processing
-    let chaves_unificadas = "Chaves de Documentos Fiscais";
-    let period_count = "Nº de Períodos";
-    let periodos = "Períodos de Apuração";
-    let periodo_valido = "Período Válido";
-    let periodos_invalidos = "Períodos Inválidos";
-    let periodos_formatados = "Períodos Formatados";
-
-    // Collect all temporary column names for later removal
-    let colunas_temporarias: Vec<&str> = vec![
-        chaves_unificadas,
-        period_count,
-        soma_dos_itens,
-        periodos,
-        periodo_valido,
-        periodos_invalidos,
-        periodos_formatados,
-    ];
 
     /*
-    // --- Step 1: Unify EFD and NFe keys into a single temporary column ---
     // Unificar duas colunas em uma coluna escalar (String) sem alterar o número de linhas
     let lazyframe = lazyframe.with_column(
         // Criar uma lista com os valores de ambas as chaves para cada linha
@@ -454,156 +328,50 @@ fn analisar_situacao06a(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
             .sort(SortOptions::default()) // Ordena os elementos de texto alfabeticamente (crescente)
             .list()
             .first() // Extrai o primeiro elemento ordenado como String de forma determinística
-            .alias(chaves_unificadas),
+            .alias(CHAVES_UNIFICADAS),
     );
     */
 
-    // --- Step 1: Unify EFD and NFe keys into a single temporary column ---
-    // Prioritize the EFD key if it is not null, otherwise retain the NFe key
-    let lazyframe = lazyframe.with_column(
+    lazyframe.with_column(
         when(col(chave_efd).is_not_null())
             .then(col(chave_efd))
             .otherwise(col(chave_nfe))
-            .alias(chaves_unificadas),
-    );
+            .alias(CHAVES_UNIFICADAS),
+    )
+}
 
-    // Select columns in this order
-    let selected: [Expr; 3] = [
-        col(periodo_de_apuracao),
-        col(chaves_unificadas),
-        col(valor_item),
-    ];
+/// **Situation 06a:** Identifies Fiscal Documents (Keys) used across multiple accounting periods.
+///
+/// This analysis checks if the same unique access key (from EFD or NF-e) appears in different
+/// months. According to Brazilian tax law, a credit should generally be claimed in the period
+/// of the fiscal event, unless it is a formal extemporaneous credit.
+///
+/// This function unifies EFD and NF-e keys, identifies duplicates across periods and
+/// joins the result back to the LazyFrame. The condition and the message are declared
+/// in the rule "06a" of the rules file.
+fn preparar_situacao06a(lazyframe: LazyFrame, _args: &Arguments) -> JoinResult<LazyFrame> {
+    // --- Step 1: Unify EFD and NFe keys into a single temporary column ---
+    let lazyframe: LazyFrame = unificar_chaves(lazyframe);
 
     // --- Step 2: Group by unified keys to find keys used in multiple accounting periods ---
-    let mut df_groupby_chaves = lazyframe
-        .clone()
-        .select(&selected)
-        .filter(col(periodo_de_apuracao).is_not_null())
-        .filter(col(chaves_unificadas).is_not_null())
-        .filter(col(chaves_unificadas).str().len_bytes().gt(len_min))
-        .group_by([col(chaves_unificadas)])
-        .agg([
-            // Collect all unique accounting periods for each key, sorted
-            col(periodo_de_apuracao)
-                .unique()
-                .sort(SortOptions::default())
-                //.dt()
-                //.strftime("%d/%m/%Y")
-                .alias(periodos),
-            // Count how many unique accounting periods each key appears in
-            col(periodo_de_apuracao)
-                .unique()
-                .count()
-                .alias(period_count),
-            col(valor_item).sum().alias(soma_dos_itens),
-        ])
-        .filter(col(period_count).gt(1)) // Filter duplicated keys
-        // Add a column for the first (smallest) accounting period for each key
-        .with_column(
-            col(periodos)
-                .list()
-                .first() // Get the first period (which is the smallest due to sorting)
-                .alias(periodo_valido),
-        )
-        // Add a column for subsequent (invalid) accounting periods for each key
-        .with_column(
-            col(periodos)
-                .list()
-                //.tail(col(periodos).list().len() - lit(1)) // Exclude the first period
-                // Slice from index 1 to the end of the list using u32::MAX
-                .slice(lit(1), lit(u32::MAX)) // Exclude the first period
-                .alias(periodos_invalidos),
-        )
-        // Add a column with all unique periods formatted as a comma-separated string
-        .with_column(format_list_dates(periodos).alias(periodos_formatados))
-        .sort_by_exprs(
-            vec![col(periodo_valido), col(chaves_unificadas)],
-            // https://github.com/pola-rs/polars/pull/15590
-            SortMultipleOptions::default()
-                .with_maintain_order(true)
-                .with_multithreaded(true)
-                .with_order_descending(false)
-                .with_nulls_last(false),
-        )
-        .collect()?;
-
-    let number_of_rows = df_groupby_chaves.height();
-
-    // Early exit: If no duplicates found, only drop the unified key column added at the start
-    if number_of_rows == 0 {
-        // No início, foi adicionada 'chaves_unificadas' em lazyframe, então só ela existe para ser removida.
-        let lazyframe = lazyframe.drop_columns(&[chaves_unificadas])?;
-        return Ok(lazyframe);
-    }
+    let lz_groupby_chaves: LazyFrame = agrupar_situacao06a(lazyframe.clone());
 
     // --- Step 3: Join the analysis results back to the original LazyFrame ---
-    let lz_unificado: LazyFrame = lazyframe.clone().join(
-        df_groupby_chaves.clone().lazy(),
-        vec![col(chaves_unificadas)], // Left join key
-        vec![col(chaves_unificadas)], // Right join key
-        JoinType::Left.into(),
-    );
-
-    // --- Step 4: Define 'Situation 06' condition and generate 'glosa' message ---
-    let situacao_06a: Expr = operacoes_de_credito()?
-        .and(col(period_count).is_not_null())
-        .and(col(periodo_de_apuracao).is_not_null())
-        .and(col(period_count).gt(1)) // Multipla utilização de Docs Fiscais
-        .and(col(periodo_de_apuracao).neq(col(periodo_valido)))
-        .and(
-            col(soma_dos_itens)
-                .round_expr(2)
-                .gt(col(valor_total).round_expr(2))
-                .or(col(soma_dos_itens).is_null())
-                .or(col(valor_total).is_null()),
-        );
-
-    println!("situacao_06a: {situacao_06a:?}\n");
-    // std::process::exit(1);
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 06:"),
-            lit("Duplicidade de Escrituração de Documento Fiscal."),
-            lit("Documento Fiscal utilizado em mais de um Período de Apuração (PA) distinto."),
-            lit("A chave"),
-            col(chaves_unificadas),
-            lit("pertence a"),
-            col(period_count),
-            lit("Períodos de Apuração distintos:"),
-            col(periodos_formatados),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
-
-    let lf_result: LazyFrame = aplicar_situacao(lz_unificado, situacao_06a, mensagem, lit(0))?;
-
-    // Remove all temporary columns created during this analysis
-    let lf_result = lf_result.drop_columns(&colunas_temporarias)?;
-
-    // Remover a coluna periodos_formatados de DataFrame
-    df_groupby_chaves.drop_in_place(periodos_formatados)?;
-
-    // Imprimir todas as linhas do DataFrame
-    unsafe {
-        env::set_var("POLARS_FMT_MAX_ROWS", number_of_rows.to_string()); // maximum number of rows shown when formatting DataFrames.
-    }
-    println!(
-        "Documentos Fiscais utilizados em Períodos de Apuração distintos: {df_groupby_chaves}\n"
-    );
+    Ok(juntar_documentos(
+        lazyframe,
+        lz_groupby_chaves,
+        &[CHAVES_UNIFICADAS],
+    ))
+}
 
-    // Criar e escrever DataFrame para um arquivo .txt
-    let filename = "dataframe_situacao06a.txt";
-    let mut file = File::create(filename)?;
-    file.write_all(df_groupby_chaves.to_string().as_bytes())?; // Escreve os bytes da string no arquivo
-    println!("DataFrame salvo em '{filename}'\n");
+/// Groups the unified keys (see `unificar_chaves`) used in more than one accounting period.
+fn agrupar_situacao06a(lazyframe: LazyFrame) -> LazyFrame {
+    let len_min = 10;
 
-    configure_the_environment(); // Retornar à configuração padrão.
+    let lazyframe: LazyFrame =
+        lazyframe.filter(col(CHAVES_UNIFICADAS).str().len_bytes().gt(len_min));
 
-    Ok(lf_result)
+    agrupar_por_periodos(lazyframe, &[CHAVES_UNIFICADAS])
 }
 
 /// **Situation 06b:** Identifies duplicated CNPJ and Document Number combinations used across multiple periods.
@@ -612,89 +380,78 @@ fn analisar_situacao06a(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
 /// and the Document Number. This is useful for capturing documents that might not have
 /// a standard electronic key or where the key format differs but the document identity
 /// (Issuer + Number) remains the same.
-fn analisar_situacao06b(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let periodo_de_apuracao: &str = coluna(Left, "pa"); // "Período de Apuração",
+fn preparar_situacao06b(lazyframe: LazyFrame, _args: &Arguments) -> JoinResult<LazyFrame> {
+    let cnpj_particip: &str = coluna(Left, "cnpj_particip");
+    let num_doc: &str = coluna(Left, "num_doc");
+
+    // --- Step 1: Group by CNPJ and number to find documents used in multiple accounting periods ---
+    let lz_groupby_cnpj: LazyFrame = agrupar_situacao06b(lazyframe.clone());
+
+    // --- Step 2: Join the analysis results back to the original LazyFrame ---
+    Ok(juntar_documentos(
+        lazyframe,
+        lz_groupby_cnpj,
+        &[cnpj_particip, num_doc],
+    ))
+}
+
+/// Groups the CNPJ and Document Number combinations used in more than one accounting period.
+fn agrupar_situacao06b(lazyframe: LazyFrame) -> LazyFrame {
     let registro: &str = coluna(Left, "registro");
     let cnpj_particip: &str = coluna(Left, "cnpj_particip");
     let num_doc: &str = coluna(Left, "num_doc");
+
+    let lazyframe: LazyFrame = lazyframe.filter(col(registro).is_not_null());
+
+    agrupar_por_periodos(lazyframe, &[cnpj_particip, num_doc])
+}
+
+/// Groups the documents identified by `chaves` used in more than one accounting period
+/// (Situations 06a and 06b).
+///
+/// The first (smallest) period is the valid one ("Período Válido"); the others are
+/// listed in "Períodos Inválidos".
+fn agrupar_por_periodos(lazyframe: LazyFrame, chaves: &[&str]) -> LazyFrame {
+    let periodo_de_apuracao: &str = coluna(Left, "pa"); // "Período de Apuração",
     let valor_item = coluna(Left, "valor_item");
     let soma_dos_itens: &str = "Soma dos Valores dos Itens";
-    let valor_total: &str = coluna(Right, "valor_total"); // "Valor Total : NF (Todos) SOMA"
 
-    // Define temporary column names
+    // Temporary column names for internal processing
     let period_count = "Nº de Períodos";
-    let periodos = "Períodos de Apuração";
     let periodo_valido = "Período Válido";
     let periodos_invalidos = "Períodos Inválidos";
-    let periodos_formatados = "Períodos Formatados";
 
-    // Collect all temporary column names for later removal
-    let colunas_temporarias: Vec<&str> = vec![
-        soma_dos_itens,
-        period_count,
-        periodos,
-        periodo_valido,
-        periodos_invalidos,
-        periodos_formatados,
+    let agregacoes: Vec<Expr> = vec![
+        // Count how many unique accounting periods each key appears in
+        col(periodo_de_apuracao)
+            .unique()
+            .count()
+            .alias(period_count),
+        col(valor_item).sum().alias(soma_dos_itens),
     ];
 
-    // Selecionar colunas nesta ordem
-    let selected: [Expr; 5] = [
-        col(periodo_de_apuracao),
-        col(registro),
-        col(cnpj_particip),
-        col(num_doc),
-        col(valor_item),
-    ];
+    let sort_exprs: Vec<Expr> = std::iter::once(col(periodo_valido))
+        .chain(chaves.iter().map(|chave| col(*chave)))
+        .collect();
 
-    // --- Step 1: Group by unified keys to find keys used in multiple accounting periods ---
-    let mut df_groupby_cnpj = lazyframe
-        .clone()
-        .select(&selected)
-        .filter(col(periodo_de_apuracao).is_not_null())
-        .filter(col(registro).is_not_null())
-        .filter(col(cnpj_particip).is_not_null())
-        .filter(col(num_doc).is_not_null())
-        .group_by([
-            // col(registro),
-            col(cnpj_particip),
-            col(num_doc),
-        ])
-        .agg([
-            // Collect all unique accounting periods for each key, sorted
-            col(periodo_de_apuracao)
-                .unique()
-                .sort(SortOptions::default())
-                //.dt()
-                //.strftime("%d/%m/%Y")
-                .alias(periodos),
-            // Count how many unique accounting periods each key appears in
-            col(periodo_de_apuracao)
-                .unique()
-                .count()
-                .alias(period_count),
-            col(valor_item).sum().alias(soma_dos_itens),
-        ])
-        .filter(col(period_count).gt(1)) // filtar chaves repetidas
+    agrupar_documentos(lazyframe, chaves, agregacoes, col(period_count).gt(1))
         // Add a column for the first (smallest) accounting period for each key
         .with_column(
-            col(periodos)
+            col(PERIODOS_DE_APURACAO)
                 .list()
                 .first() // Get the first period (which is the smallest due to sorting)
                 .alias(periodo_valido),
         )
         // Add a column for subsequent (invalid) accounting periods for each key
         .with_column(
-            col(periodos)
+            col(PERIODOS_DE_APURACAO)
                 .list()
+                // Slice from index 1 to the end of the list using u32::MAX
                 .slice(lit(1), lit(u32::MAX)) // Exclude the first period
                 .alias(periodos_invalidos),
         )
-        // Add a column with all unique periods formatted as a comma-separated string
-        .with_column(format_list_dates(periodos).alias(periodos_formatados))
         .sort_by_exprs(
-            vec![col(periodo_valido), col(cnpj_particip), col(num_doc)],
+            sort_exprs,
             // https://github.com/pola-rs/polars/pull/15590
             SortMultipleOptions::default()
                 .with_maintain_order(true)
@@ -702,504 +459,152 @@ fn analisar_situacao06b(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
                 .with_order_descending(false)
                 .with_nulls_last(false),
         )
-        .collect()?;
-
-    let number_of_rows = df_groupby_cnpj.height();
-
-    // Early exit: No columns were added to the original lazyframe yet, so just return it
-    if number_of_rows == 0 {
-        // Na '06b', nao foi executado 'with_column' no lazyframe original antes do agrupamento.
-        // Então basta retornar o lazyframe como ele veio.
-        return Ok(lazyframe);
-    }
-
-    // --- Step 2: Join the analysis results back to the original LazyFrame ---
-    let lz_unificado: LazyFrame = lazyframe.clone().join(
-        df_groupby_cnpj.clone().lazy(),
-        vec![
-            //col(registro),
-            col(cnpj_particip),
-            col(num_doc),
-        ], // Left join key
-        vec![
-            //col(registro),
-            col(cnpj_particip),
-            col(num_doc),
-        ], // Right join key
-        JoinType::Left.into(),
-    );
-
-    // --- Step 3: Define 'Situation 06' condition and generate 'glosa' message ---
-    let situacao_06b: Expr = operacoes_de_credito()?
-        .and(col(period_count).is_not_null())
-        .and(col(periodo_de_apuracao).is_not_null())
-        .and(col(period_count).gt(1)) // Multipla utilização de Docs Fiscais
-        .and(col(periodo_de_apuracao).neq(col(periodo_valido)))
-        .and(
-            col(soma_dos_itens)
-                .round_expr(2)
-                .gt(col(valor_total).round_expr(2))
-                .or(col(soma_dos_itens).is_null())
-                .or(col(valor_total).is_null()),
-        );
-
-    println!("situacao_06b: {situacao_06b:?}\n");
-    // std::process::exit(1);
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 06:"),
-            lit("Duplicidade de Escrituração de Documento Fiscal."),
-            lit("Documento Fiscal utilizado em mais de um Período de Apuração (PA) distinto."),
-            lit("O Documento Fiscal de CNPJ"),
-            col(cnpj_particip),
-            lit("de número"),
-            col(num_doc),
-            lit("registrado em"),
-            col(registro),
-            lit("pertence a"),
-            col(period_count),
-            lit("Períodos de Apuração distintos:"),
-            col(periodos_formatados),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
-
-    let lf_result: LazyFrame = aplicar_situacao(lz_unificado, situacao_06b, mensagem, lit(0))?;
-
-    // Remove all temporary columns created during this analysis
-    let lf_result = lf_result.lazy().drop_columns(&colunas_temporarias)?;
-
-    // Remover a coluna periodos_formatados de DataFrame
-    df_groupby_cnpj.drop_in_place(periodos_formatados)?;
-
-    // Imprimir todas as linhas do DataFrame
-    unsafe {
-        env::set_var("POLARS_FMT_MAX_ROWS", number_of_rows.to_string()); // maximum number of rows shown when formatting DataFrames.
-    }
-    println!(
-        "Documentos Fiscais utilizados em Períodos de Apuração distintos: {df_groupby_cnpj}\n"
-    );
-
-    // Criar e escrever DataFrame para um arquivo .txt
-    let filename = "dataframe_situacao06b.txt";
-    let mut file = File::create(filename)?;
-    file.write_all(df_groupby_cnpj.to_string().as_bytes())?; // Escreve os bytes da string no arquivo
-    println!("DataFrame salvo em '{filename}'\n");
-
-    configure_the_environment(); // Retornar à configuração padrão.
-
-    Ok(lf_result)
-}
-
-/// **Situation 07:** Freight on purchases where inputs are not subject to PIS/COFINS.
-fn analisar_situacao07(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let origem_do_item: &str = coluna(Right, "origem"); // "Registro de Origem do Item : NF Item (Todos)"
-
-    let columns: Vec<&str> = vec![
-        "Código de Situação Tributária (CST)",
-        "Código CFOP : NF Item (Todos)",
-        "Alíquota Zero",
-        "Alíquota Zero Temp", // coluna temporária
-        "Incidência Monofásica",
-        "Incidência Monofásica Temp", // coluna temporária
-        "Crédito Presumido",          // Coluna auxiliar
-        "CNPJ Base do Contribuinte",  // Coluna auxiliar
-        "CNPJ Base do Remetente",     // Coluna auxiliar
-        "CNPJ Base do Destinatário",  // Coluna auxiliar
-    ];
-
-    let pattern: Expr = lit(r"(?i)CTe"); // regex
-    let cte: Expr = col(origem_do_item).str().contains(pattern, false);
-
-    // CFOPs relacionados a Serviços de Armazanagem ou Industrialização por encomenda.
-    let cfop_valido = [
-        CFOP_DE_ARMAZENAGEM.as_slice(),
-        CFOP_DE_INDUSTRIALIZACAO.as_slice(),
-    ]
-    .concat();
-    let series: Series = cfop_valido.iter().collect();
-    let literal_series: Expr = series.to_list_expr()?;
-
-    // Estes serviços são insumos com direito à crédito das Contribuições que devem ser excluídos das glosas.
-    let cfop_de_insumos: Expr = col(columns[1])
-        .is_not_null()
-        .and(col(columns[1]).is_in(literal_series, true));
-
-    let not_credito_presumido: Expr = col(columns[6]).is_null();
-
-    // "CNPJ Base do Contribuinte" eq "CNPJ Base do Destinatário"
-    // O Contribuinte é o Destinatário das operações.
-    let destinatario_das_operacoes: Expr = equal(columns[7], columns[9]);
-
-    // "CNPJ Base do Remetente"  eq "CNPJ Base do Destinatário": operação de transferência
-    // "CNPJ Base do Remetente" neq "CNPJ Base do Destinatário": operação de compra
-    let cnpjs_distintos: Expr = unequal(columns[8], columns[9]);
-
-    let operacao_de_compra: Expr = destinatario_das_operacoes.and(cnpjs_distintos);
-
-    let filter: Expr = operacoes_de_credito()?
-        .and(cst_50_a_56()?)
-        .and(cte)
-        .and(cfop_de_insumos.not())
-        .and(not_credito_presumido)
-        .and(operacao_de_compra);
-
-    // Adicionar coluna temporária
-    let lazyframe: LazyFrame = lazyframe
-        .with_column(
-            when(filter.clone())
-                .then(columns[2]) // keep original value: "Alíquota Zero"
-                .otherwise(lit(NULL)) // replace by null
-                .alias(columns[3]), // Coluna Temporária
-        )
-        .with_column(
-            when(filter)
-                .then(columns[4]) // keep original value: "Incidência Monofásica"
-                .otherwise(lit(NULL)) // replace by null
-                .alias(columns[5]), // Coluna Temporária
-        );
-
-    let situacao_07: Expr = col(columns[3])
-        .is_not_null()
-        .or(col(columns[5]).is_not_null());
-
-    println!("situacao_07: {situacao_07:?}\n");
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 07:"),
-            lit(
-                "Frete sobre Aquisições Desoneradas (bens ou serviços não sujeitos ao pagamento das contribuições).",
-            ),
-            col(columns[3]),
-            col(columns[5]),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
-
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_07, mensagem, lit(0))?;
-
-    // Remover coluna temporária
-    let lf_result = lf_result.drop_columns(&[columns[3], columns[5]])?;
-
-    Ok(lf_result)
 }
 
-/// **Situation 08:** Freight on Sales involving internal transfers between company branches.
+/// **Situation 06c:** Identifies Fiscal Documents (Keys) credited by more than one establishment.
 ///
-/// Based on PN Cosit 05/2018 and IN RFB 2121/2022.
-fn analisar_situacao08(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let contabil: &str = coluna(Left, "contabil");
-
-    let columns: Vec<&str> = vec![
-        "CNPJ Base do Remetente", // Coluna auxiliar adicionada em fn adicionar_colunas_auxiliares()
-        "CNPJ Base do Destinatário", // Coluna auxiliar adicionada em fn adicionar_colunas_auxiliares()
-    ];
-
-    let pattern: Expr = lit(r"(?i)Frete.*Venda"); // regex
-    let frete_sobre_vendas: Expr = col(contabil).str().contains(pattern, false);
-
-    // "CNPJ Base do Remetente" eq "CNPJ Base do Destinatário"
-    let operacao_de_transferencia: Expr = equal(columns[0], columns[1]);
-
-    let situacao_08: Expr = operacoes_de_credito()?
-        .and(frete_sobre_vendas)
-        .and(operacao_de_transferencia);
-
-    println!("situacao_08: {situacao_08:?}\n");
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 08:"),
-            lit("Frete sobre Vendas em Operações de Transferência ou Remessa."),
-            lit(
-                "Conforme Parecer Normativo Cosit nº 5/2018 e § 2º do art. 176 da IN RFB nº 2121 de 2022:",
-            ),
-            lit("Não são considerados insumos os serviços de transporte de produtos"),
-            lit("acabados realizados em ou entre estabelecimentos da pessoa jurídica."),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
-
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_08, mensagem, lit(0))?;
-
-    Ok(lf_result)
-}
-
-/// **Situation 09:** Advertising and Marketing services (not considered essential inputs).
-fn analisar_situacao09(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let item_descricao: &str = coluna(Left, "item_desc");
-    let escri_contabil: &str = coluna(Left, "contabil");
-
-    let pattern1: Expr = lit(r"(?i)Marketing|MKT|Propaganda|Veiculacao");
-    let pattern2: Expr = lit(r"(?i)Marketing|MKT|Propaganda|Veiculacao");
-
-    let condicao1: Expr = col(item_descricao).str().contains(pattern1, false);
-    let condicao2: Expr = col(escri_contabil).str().contains(pattern2, false);
-
-    let situacao_09: Expr = operacoes_de_credito()?.and(condicao1.or(condicao2));
-
-    println!("situacao_09: {situacao_09:?}\n");
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 09:"),
-            lit("Despesas com Propaganda, Publicidade e Marketing."),
-            lit(
-                "Os gastos com Serviço de Propaganda e Marketing não são insumos geradores de crédito das Contribuições",
-            ),
-            lit(
-                "segundo os critérios da Essencialidade ou da Relevância (Ver Parecer Normativo nº 5 de 2018).",
-            ),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
-
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_09, mensagem, lit(0))?;
-
-    Ok(lf_result)
-}
-
-/// **Situation 10:** Disallows credits on Nullification, Samples, Gifts or Return of Containers.
-fn analisar_situacao10(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let origem_do_item: &str = coluna(Right, "origem"); // "Registro de Origem do Item : NF Item (Todos)"
-    let descricao_cfop: &str = coluna(Right, "descricao_cfop");
-
-    let pattern1: Expr = lit(r"(?i)CTe");
-    let pattern2: Expr = lit(r"(?i)Anula|Amostra|Brinde|Vasilhame");
-    let pattern3: Expr = lit(r"(?i)Simb|Venda|Compra|Export");
-
-    let condicao1: Expr = col(origem_do_item).str().contains(pattern1, false);
-    let condicao2: Expr = col(descricao_cfop).str().contains(pattern2, false);
-    let condicao3: Expr = col(descricao_cfop).str().contains(pattern3, false);
-
-    let situacao_10: Expr = operacoes_de_credito()?.and(condicao1).and(condicao2).and(
-        condicao3.not(), // crédito válido: venda com retorno simbólico de mercadoria do armazém para a empresa.
-    );
-
-    println!("situacao_10: {situacao_10:?}\n");
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 10:"),
-            lit("Anulação ou Amostras e Brindes ou Retorno de Vasilhame."),
-            lit("Ver coluna <Descrição CFOP : NF Item (Todos)>."),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
+/// This analysis complements 06a by grouping the unified key across the Contributor's
+/// establishments (`contribuinte_cnpj`). The same acquisition must generate credit in a
+/// single establishment: the first one to claim it (smallest period, then smallest CNPJ)
+/// keeps the credit, the others are flagged as duplicates.
+///
+/// Only credit operations are grouped, since a transfer between establishments is
+/// legitimately registered by both the sender (output) and the receiver (input).
+fn preparar_situacao06c(lazyframe: LazyFrame, _args: &Arguments) -> JoinResult<LazyFrame> {
+    let periodo_de_apuracao: &str = coluna(Left, "pa"); // "Período de Apuração",
+    let contribuinte_cnpj: &str = coluna(Left, "contribuinte_cnpj");
+    let len_min = 10;
 
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_10, mensagem, lit(0))?;
+    // Temporary column names for internal processing
+    let establishment_count = "Nº de Estabelecimentos";
+    let estabelecimento_valido = "Estabelecimento Válido";
+    let estabelecimentos_formatados = "Estabelecimentos Formatados";
 
-    Ok(lf_result)
-}
+    // --- Step 1: Unify EFD and NFe keys into a single temporary column ---
+    let lazyframe: LazyFrame = unificar_chaves(lazyframe);
+
+    // --- Step 2: Group by unified keys to find keys credited by multiple establishments ---
+    let agregacoes: Vec<Expr> = vec![
+        // Count how many distinct establishments credit each key
+        col(contribuinte_cnpj).n_unique().alias(establishment_count),
+        // The first establishment to claim the credit keeps it
+        col(contribuinte_cnpj)
+            .sort_by(
+                [col(periodo_de_apuracao), col(contribuinte_cnpj)],
+                SortMultipleOptions::default(),
+            )
+            .first()
+            .alias(estabelecimento_valido),
+        // All establishments, sorted, as a comma-separated string
+        col(contribuinte_cnpj)
+            .unique()
+            .sort(SortOptions::default())
+            .str()
+            .join(", ", true)
+            .alias(estabelecimentos_formatados),
+    ];
 
-/// **Situation 11:** Labor-related activities (Food, Clothing, Transport for workers).
-fn analisar_situacao11(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let item_descricao: &str = coluna(Left, "item_desc");
-    let escri_contabil: &str = coluna(Left, "contabil");
-
-    let pattern1: Expr = lit(r"(?i)Vale (Refeicao|Transporte)|Seguro");
-    let pattern2: Expr = lit(r"(?i)Vale (Refeicao|Transporte)|Seguro");
-
-    let condicao1: Expr = col(item_descricao).str().contains(pattern1, false);
-    let condicao2: Expr = col(escri_contabil).str().contains(pattern2, false);
-
-    let situacao_11: Expr = operacoes_de_credito()?.and(condicao1.or(condicao2));
-
-    println!("situacao_11: {situacao_11:?}\n");
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 11:"),
-            lit("Atividades da Mão de Obra."),
-            lit(
-                "Conforme Parecer Normativo SRFB n° 5 de 2018, linhas 55 e 168, não são considerados insumos os itens destinados",
-            ),
-            lit(
-                "a viabilizar a atividade da mão de obra empregada pela pessoa jurídica em qualquer de suas áreas, inclusive em",
-            ),
-            lit(
-                "seu processo de produção de bens ou de prestação de serviços, tais como alimentação, vestimenta e transporte.",
-            ),
-            lit("&"),
-        ],
-        " ",
-        true,
+    let lz_groupby_chaves: LazyFrame = agrupar_documentos(
+        lazyframe
+            .clone()
+            .filter(operacoes_de_credito()?)
+            .filter(col(contribuinte_cnpj).is_not_null())
+            .filter(col(CHAVES_UNIFICADAS).str().len_bytes().gt(len_min)),
+        &[CHAVES_UNIFICADAS],
+        agregacoes,
+        col(establishment_count).gt(1),
     );
 
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_11, mensagem, lit(0))?;
-
-    Ok(lf_result)
+    // --- Step 3: Join the analysis results back to the original LazyFrame ---
+    Ok(juntar_documentos(
+        lazyframe,
+        lz_groupby_chaves,
+        &[CHAVES_UNIFICADAS],
+    ))
 }
 
-/// **Situation 12:** Checks for non-existent Document Keys.
-fn analisar_situacao12(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let chave: &str = coluna(Left, "chave"); // "Chave do Documento"
-
-    let series: Series = [
-        // CTe:
-        "12345678901234567890123456789012345678901234",
-        "01234567890123456789012345678901234567890123",
-        "90123456789012345678901234567890123456789012",
-        "89012345678901234567890123456789012345678901",
-    ]
-    .iter()
-    .map(|doc| doc.to_string())
-    .collect();
-
-    let literal_series: Expr = series.to_list_expr()?;
-
-    let chave_inexistente: Expr = col(chave).is_in(literal_series, true);
-
-    let situacao_12: Expr = operacoes_de_credito()?.and(chave_inexistente);
-
-    println!("situacao_12: {situacao_12:?}\n");
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 12:"),
-            lit(
-                "Documento Fiscal inexistente conforme www.nfe.fazenda.gov.br ou www.cte.fazenda.gov.br",
-            ),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
-
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_12, mensagem, lit(0))?;
-
-    Ok(lf_result)
-}
+/// Groups the Fiscal Documents identified by `chaves` (Situations 06a, 06b and 06c).
+///
+/// Lines without accounting period or key are ignored. Besides `agregacoes`, each group
+/// gets the sorted list of its accounting periods ("Períodos de Apuração") and the same
+/// list formatted as text ("Períodos Formatados"). Only the groups that satisfy
+/// `duplicidade` (e.g. more than one period) are kept.
+fn agrupar_documentos(
+    lazyframe: LazyFrame,
+    chaves: &[&str],
+    agregacoes: Vec<Expr>,
+    duplicidade: Expr,
+) -> LazyFrame {
+    let periodo_de_apuracao: &str = coluna(Left, "pa"); // "Período de Apuração",
+    let periodos_formatados = "Períodos Formatados";
 
-#[allow(dead_code)]
-fn analisar_situacao13(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let num_linha: &str = coluna(Left, "num_linha"); // "Linhas"
-
-    let series: Series = [
-        95217, 95222, 95223, 95225, 105758, 116292, 116294, 116575, 127290, 127291, 127292, 137505,
-        147797, 157909, 157914, 168540, 178655, 188135, 188136, 188140, 188141, 196421, 197989,
-        199430, 199913, 201399, 202588, 203362, 203832, 203833, 203836, 204524, 204570, 205051,
-        206238, 206249, 206253, 207449, 208792, 210039, 211260, 212538, 213786, 213787, 213788,
-        215102,
-    ]
-    .iter()
+    let documentos_validos: Expr = chaves
+        .iter()
+        .fold(col(periodo_de_apuracao).is_not_null(), |expr, chave| {
+            expr.and(col(*chave).is_not_null())
+        });
+
+    let agregacoes: Vec<Expr> = std::iter::once(
+        // Collect all unique accounting periods for each key, sorted
+        col(periodo_de_apuracao)
+            .unique()
+            .sort(SortOptions::default())
+            .alias(PERIODOS_DE_APURACAO),
+    )
+    .chain(agregacoes)
     .collect();
 
-    let literal_series: Expr = series.to_list_expr()?;
-
-    let linhas: Expr = col(num_linha).is_in(literal_series, true);
-
-    let situacao_13: Expr = operacoes_de_credito()?.and(linhas);
-
-    println!("situacao_13: {situacao_13:?}\n");
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 13:"),
-            lit("Créditos Estornados, conforme respostas do Contribuinte às Intimações Fiscais."),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
-
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_13, mensagem, lit(0))?;
-
-    Ok(lf_result)
+    lazyframe
+        .filter(documentos_validos)
+        .group_by(
+            chaves
+                .iter()
+                .map(|chave| col(*chave))
+                .collect::<Vec<Expr>>(),
+        )
+        .agg(agregacoes)
+        .filter(duplicidade) // Filter duplicated keys
+        // Add a column with all unique periods formatted as a comma-separated string
+        .with_column(format_list_dates(PERIODOS_DE_APURACAO).alias(periodos_formatados))
 }
 
-#[allow(dead_code)]
-fn analisar_situacao14(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
-    let glosar: &str = coluna(Middle, "glosar");
-    let item_tipo: &str = coluna(Left, "item_tipo");
-    let escri_contabil: &str = coluna(Left, "contabil");
-
-    let pattern1: Expr = lit(r"(?i)Embalage(m|ns)");
-    let pattern2: Expr = lit(r"(?i)Embalage(m|ns)");
-
-    let condicao1: Expr = col(item_tipo).str().contains(pattern1, false);
-    let condicao2: Expr = col(escri_contabil).str().contains(pattern2, false);
-
-    let situacao_14: Expr = operacoes_de_credito()?.and(condicao1.and(condicao2));
-
-    println!("situacao_14: {situacao_14:?}\n");
-
-    /*
-    PARECER NORMATIVO Nº 5, DE 17 DE DEZEMBRO DE 2018
-
-    2. INEXISTÊNCIA DE INSUMOS NA ATIVIDADE COMERCIAL
-
-    42.Em razão disso, exemplificativamente, não constituem insumos geradores de créditos para pessoas jurídicas
-    dedicadas à atividade de revenda de bens: a) combustíveis e lubrificantes utilizados em veículos próprios de entrega de
-    mercadorias2; b) transporte de mercadorias entre centros de distribuição próprios; c) embalagens para transporte das mercadorias;
-    etc.
-
-    5. GASTOS POSTERIORES À FINALIZAÇÃO DO PROCESSO DE PRODUÇÃO OU DE PRESTAÇÃO
-
-    56.Destarte, exemplificativamente não podem ser considerados insumos gastos com transporte (frete) de produtos
-    acabados (mercadorias) de produção própria entre estabelecimentos da pessoa jurídica, para centros de distribuição ou para entrega
-    direta ao adquirente6, como: a) combustíveis utilizados em frota própria de veículos; b) embalagens para transporte de mercadorias
-    acabadas; c) contratação de transportadoras.
-    */
-
-    let mensagem: Expr = concat_str(
-        [
-            col(glosar),
-            lit("Situação 14:"),
-            lit("Embalagens."),
-            lit("Conforme Parecer Normativo SRFB n° 5 de 2018, linha 42,"),
-            lit("não constituem insumos geradores de créditos para pessoas jurídicas"),
-            lit("dedicadas à atividade de revenda de bens:"),
-            lit("c) embalagens para transporte das mercadorias;"),
-            lit("&"),
-        ],
-        " ",
-        true,
-    );
-
-    let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_14, mensagem, lit(0))?;
-
-    Ok(lf_result)
+/// Joins the grouped Fiscal Documents (see `agrupar_documentos`) back to the LazyFrame.
+///
+/// Without duplicates, the joined columns are null and the rule does not apply.
+fn juntar_documentos(lazyframe: LazyFrame, agrupamento: LazyFrame, chaves: &[&str]) -> LazyFrame {
+    let chaves: Vec<Expr> = chaves.iter().map(|chave| col(*chave)).collect();
+
+    lazyframe.join(
+        agrupamento,
+        chaves.clone(), // Left join key
+        chaves,         // Right join key
+        JoinType::Left.into(),
+    )
 }
 
-/// Helper function to update the 'glosar' message column and reset 'valor_bc' if a condition is met.
+/// Helper function to update the 'glosar' message column and replace 'valor_bc'
+/// by `new_value` if a condition is met.
+///
+/// The glosed amount (`valor_bc - new_value`) is recorded in the column `valor_glosado`
+/// (null where the condition is not met). Since 'valor_bc' is updated in sequence,
+/// a line hit by several situations is not glosed twice.
 fn aplicar_situacao(
     lazyframe: LazyFrame,
     situacao: Expr,
     mensagem: Expr,
     new_value: Expr,
+    valor_glosado: &str,
 ) -> JoinResult<LazyFrame> {
     let glosar: &str = coluna(Middle, "glosar");
     let valor_bc: &str = coluna(Left, "valor_bc");
 
     let lf_result: LazyFrame = lazyframe
+        .with_column(
+            when(situacao.clone())
+                .then((col(valor_bc) - new_value.clone()).round_expr(2))
+                .otherwise(lit(NULL).cast(DataType::Float64))
+                .alias(valor_glosado),
+        )
         .with_column(
             when(situacao.clone())
                 .then(mensagem)
@@ -1309,12 +714,21 @@ mod tests_slice_list {
 #[cfg(test)]
 mod tests_glosar_base_de_calculo {
     use super::*;
-    use crate::configure_the_environment;
+    use crate::{JoinError, RegraDeGlosa, SelecaoDeSituacoes, configure_the_environment};
 
     // cargo test -- --help
     // cargo test -- --nocapture
     // cargo test -- --show-output
 
+    /// Regra do arquivo de regras padrão.
+    fn regra_padrao(id: &str) -> JoinResult<RegraDeGlosa> {
+        RegrasDeGlosa::padrao()?
+            .regras
+            .into_iter()
+            .find(|regra| regra.id == id)
+            .ok_or_else(|| JoinError::Other(format!("regra '{id}' não encontrada")))
+    }
+
     #[test]
     /// `cargo test -- --show-output test_analisar_situacao02`
     fn test_analisar_situacao02() -> JoinResult<()> {
@@ -1364,7 +778,7 @@ mod tests_glosar_base_de_calculo {
         let args: Arguments = Arguments::default();
         println!("args: {args:#?}\n");
 
-        let lz: LazyFrame = analisar_situacao02(lazyframe, &args)?
+        let lz: LazyFrame = aplicar_regra(lazyframe, &regra_padrao("02")?, &args)?
             .with_column(col(glosar))
             .format_values();
 
@@ -1390,6 +804,63 @@ mod tests_glosar_base_de_calculo {
         Ok(())
     }
 
+    #[test]
+    /// `cargo test -- --show-output test_extemporaneo_por_linha_e_decadencia`
+    fn test_extemporaneo_por_linha_e_decadencia() -> JoinResult<()> {
+        configure_the_environment();
+
+        let glosar: &str = coluna(Middle, "glosar");
+
+        // Linha 1: dentro da janela do próprio PA. Linha 2: 3 meses antes do PA.
+        // Linha 3: sem data na EFD (emissão da NF-e). Linha 4: mais de 5 anos antes do PA.
+        let dataframe: DataFrame = df! [
+            coluna(Left, "tipo_operacao") => [1, 1, 1, 1],
+            coluna(Left, "pa") => ["2022-06-01", "2022-06-01", "2022-06-01", "2022-06-01"],
+            coluna(Left, "cst") => [56, 56, 56, 56],
+            coluna(Left, "natureza") => [2, 2, 2, 2],
+            coluna(Left, "valor_bc") => [100.0, 100.0, 100.0, 100.0],
+            glosar => ["", "", "", ""],
+            coluna(Left, "data_entrada") => [Some("2022-05-10"), Some("2022-03-10"), None, Some("2017-01-10")],
+            coluna(Left, "data_emissao") => [Some("2022-05-09"), Some("2022-03-09"), None, Some("2017-01-09")],
+            coluna(Right, "dia_emissao") => [Some("2022-05-09"), Some("2022-03-09"), Some("2022-08-15"), None],
+        ]?;
+
+        let lazyframe: LazyFrame = dataframe.lazy().with_column(
+            col("^(Período|Data|Dia).*$")
+                .str()
+                .to_date(StrptimeOptions::default()),
+        );
+
+        let args = Arguments {
+            extemporaneo_por_linha: Some(true),
+            ..Arguments::default()
+        };
+
+        let lz: LazyFrame = aplicar_regra(lazyframe, &regra_padrao("02")?, &args)?;
+        let lz: LazyFrame = aplicar_regra(lz, &regra_padrao("20")?, &args)?;
+
+        let result = lz.collect()?;
+        println!("result:\n{result}\n");
+
+        let mensagens: Vec<bool> = result
+            .column(glosar)?
+            .str()?
+            .iter()
+            .map(|mensagem| mensagem.is_some_and(|m| m.contains("Situação 02")))
+            .collect();
+        assert_eq!(mensagens, [false, true, true, true]);
+
+        let decaidos: Vec<bool> = result
+            .column(glosar)?
+            .str()?
+            .iter()
+            .map(|mensagem| mensagem.is_some_and(|m| m.contains("Situação 20")))
+            .collect();
+        assert_eq!(decaidos, [false, false, false, true]);
+
+        Ok(())
+    }
+
     #[test]
     /// test columns with nulls
     ///
@@ -1492,7 +963,10 @@ mod tests_glosar_base_de_calculo {
             true,
         );
 
-        let lf_result: LazyFrame = aplicar_situacao(lazyframe, situacao_03, mensagem, lit(0))?;
+        let valor_glosado: &str = "Valor Glosado: Situação 03";
+
+        let lf_result: LazyFrame =
+            aplicar_situacao(lazyframe, situacao_03, mensagem, lit(0.0), valor_glosado)?;
 
         let dataframe02: DataFrame = lf_result.collect()?;
 
@@ -1512,6 +986,60 @@ mod tests_glosar_base_de_calculo {
 
         assert_eq!(vec_bcal_values, vec![0.0, 0.3, 10.0, 89.01, -3.41, 0.0]);
 
+        let glosado: Vec<Option<f64>> = dataframe02.column(valor_glosado)?.f64()?.iter().collect();
+        assert_eq!(glosado, [Some(23.6), None, None, None, None, Some(52.07)]);
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_situacoes_de_glosa`
+    fn test_situacoes_de_glosa() -> JoinResult<()> {
+        let situacoes: &str = coluna(Middle, "situacoes");
+        let glosado_03: String = coluna_valor_glosado("03");
+        let glosado_07: String = coluna_valor_glosado("07");
+
+        // A Situação 07, aplicada após a 03, glosa apenas o saldo remanescente.
+        let lazyframe: LazyFrame = df! {
+            glosado_03.as_str() => [Some(100.0), None, None],
+            glosado_07.as_str() => [Some(0.0), Some(50.0), None],
+        }?
+        .lazy();
+
+        let df = adicionar_situacoes_de_glosa(lazyframe, &["03", "07"], true)?.collect()?;
+        println!("df: {df}");
+
+        let coluna_situacoes = df.column(situacoes)?.list()?;
+        let listas: Vec<Vec<String>> = (0..coluna_situacoes.len())
+            .map(|indice| {
+                coluna_situacoes
+                    .get_as_series(indice)
+                    .map(|series| {
+                        series
+                            .str()
+                            .map(|ca| ca.iter().flatten().map(String::from).collect())
+                            .unwrap_or_default()
+                    })
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        assert_eq!(
+            listas,
+            [
+                vec!["03".to_string(), "07".to_string()],
+                vec!["07".to_string()],
+                vec![]
+            ]
+        );
+
+        let linha_glosada_07: Vec<Option<bool>> = df
+            .column(&format!("{PREFIXO_LINHA_GLOSADA}07"))?
+            .bool()?
+            .iter()
+            .collect();
+        assert_eq!(linha_glosada_07, [Some(true), Some(true), Some(false)]);
+
         Ok(())
     }
 
@@ -1543,7 +1071,10 @@ mod tests_glosar_base_de_calculo {
 
         let lazyframe: LazyFrame = dataframe.lazy();
 
-        let lf_itens_de_docs_fiscais_result: LazyFrame = analisar_situacao10(lazyframe)?;
+        let args: Arguments = Arguments::default();
+
+        let lf_itens_de_docs_fiscais_result: LazyFrame =
+            aplicar_regra(lazyframe, &regra_padrao("10")?, &args)?;
 
         let df_itens_de_docs_fiscais_result: DataFrame =
             lf_itens_de_docs_fiscais_result.collect()?;
@@ -1578,6 +1109,250 @@ mod tests_glosar_base_de_calculo {
         Ok(())
     }
 
+    #[test]
+    /// `cargo test -- --show-output test_documentos_em_multiplos_estabelecimentos`
+    fn test_documentos_em_multiplos_estabelecimentos() -> JoinResult<()> {
+        configure_the_environment();
+
+        let periodo_de_apuracao: &str = coluna(Left, "pa");
+        let contribuinte_cnpj: &str = coluna(Left, "contribuinte_cnpj");
+        let valor_bc: &str = coluna(Left, "valor_bc");
+        let glosar: &str = coluna(Middle, "glosar");
+
+        let chave_1 = "35220100000000000001550010000000011000000011";
+        let chave_2 = "35220100000000000001550010000000021000000022";
+        let chave_3 = "35220100000000000001550010000000031000000033";
+
+        // chave_1: creditada pelo CNPJ "A" em fev/2022 e pelo CNPJ "B" em jan/2022:
+        // o primeiro período prevalece sobre a ordem dos CNPJs.
+        // chave_2: creditada por "A" e "B" no mesmo período: prevalece o menor CNPJ.
+        // chave_1 escriturada por "C" como saída (transferência): não é crédito.
+        // chave_3: um único estabelecimento.
+        let dataframe: DataFrame = df! [
+            periodo_de_apuracao => ["2022-02-01", "2022-01-01", "2022-01-01", "2022-01-01", "2022-01-01", "2022-01-01"],
+            contribuinte_cnpj => ["A", "B", "A", "B", "C", "A"],
+            coluna(Left, "chave") => [chave_1, chave_1, chave_2, chave_2, chave_1, chave_3],
+            coluna(Right, "chave") => [None::<&str>, None, None, None, None, None],
+            coluna(Left, "tipo_operacao") => [1, 1, 1, 1, 2, 1],
+            coluna(Left, "cst") => [50, 50, 50, 50, 1, 50],
+            coluna(Left, "natureza") => [1, 1, 1, 1, 1, 1],
+            valor_bc => [100.0, 100.0, 200.0, 200.0, 300.0, 400.0],
+            glosar => ["", "", "", "", "", ""],
+        ]?
+        .lazy()
+        .with_column(
+            col(periodo_de_apuracao)
+                .str()
+                .to_date(StrptimeOptions::default()),
+        )
+        .collect()?;
+
+        let args: Arguments = Arguments::default();
+
+        let df_result: DataFrame =
+            aplicar_regra(dataframe.lazy(), &regra_padrao("06c")?, &args)?.collect()?;
+
+        println!("df_result: {df_result}\n");
+
+        let bcal_values: Vec<Option<f64>> = df_result.column(valor_bc)?.f64()?.iter().collect();
+
+        // Glosados: chave_1 no CNPJ "A" (período posterior) e chave_2 no CNPJ "B".
+        assert_eq!(
+            bcal_values,
+            [
+                Some(0.0),
+                Some(100.0),
+                Some(200.0),
+                Some(0.0),
+                Some(300.0),
+                Some(400.0)
+            ]
+        );
+
+        let mensagem: Option<&str> = df_result.column(glosar)?.str()?.get(0);
+        assert!(mensagem.is_some_and(|m| m.contains("crédito mantido no estabelecimento B")));
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_aquisicao_de_pessoa_fisica`
+    fn test_aquisicao_de_pessoa_fisica() -> JoinResult<()> {
+        configure_the_environment();
+
+        let valor_bc: &str = coluna(Left, "valor_bc");
+        let glosar: &str = coluna(Middle, "glosar");
+        let cpf = Some("123.456.789-09");
+
+        // Linha 0: CPF sem CNPJ: glosada.
+        // Linha 1: CPF e CNPJ do participante informados.
+        // Linha 2: crédito presumido pelo CST (60 a 66).
+        // Linha 3: crédito presumido pela legislação (coluna "Crédito Presumido").
+        // Linha 4: participante sem CPF.
+        let dataframe: DataFrame = df! [
+            coluna(Left, "tipo_operacao") => [1, 1, 1, 1, 1],
+            coluna(Left, "cst") => [50, 50, 60, 50, 50],
+            coluna(Left, "natureza") => [1, 1, 1, 1, 1],
+            coluna(Left, "cpf_particip") => [cpf, cpf, cpf, cpf, None],
+            coluna(Left, "cnpj_particip") => [None, Some("12.345.678/0001-95"), None, None, None],
+            "Crédito Presumido" => [None, None, None, Some("Art. 8º da Lei nº 10.925/2004"), None],
+            "CBS/IBS Crédito Presumido" => [None::<&str>, None, None, None, None],
+            valor_bc => [100.0, 200.0, 300.0, 400.0, 500.0],
+            glosar => ["", "", "", "", ""],
+        ]?;
+
+        let args: Arguments = Arguments::default();
+
+        let df_result: DataFrame =
+            aplicar_regra(dataframe.lazy(), &regra_padrao("18")?, &args)?.collect()?;
+
+        println!("df_result: {df_result}\n");
+
+        let bcal_values: Vec<Option<f64>> = df_result.column(valor_bc)?.f64()?.iter().collect();
+        assert_eq!(
+            bcal_values,
+            [
+                Some(0.0),
+                Some(200.0),
+                Some(300.0),
+                Some(400.0),
+                Some(500.0)
+            ]
+        );
+
+        let mensagem: Option<&str> = df_result.column(glosar)?.str()?.get(0);
+        assert!(mensagem.is_some_and(|m| m.contains("Aquisição de Pessoa Física")));
+
+        Ok(())
+    }
+
+    /// Regra personalizada: glosa 40% da Base de Cálculo acima de 100.
+    #[derive(Debug)]
+    struct UsoMisto;
+
+    impl GlosaRule for UsoMisto {
+        fn id(&self) -> &str {
+            "x01"
+        }
+
+        fn description(&self) -> &str {
+            "Item de uso misto"
+        }
+
+        fn prepare(&self, lazyframe: LazyFrame, _args: &Arguments) -> JoinResult<LazyFrame> {
+            let valor_bc: &str = coluna(Left, "valor_bc");
+            Ok(lazyframe.with_column(col(valor_bc).gt(lit(100.0)).alias("Uso Misto")))
+        }
+
+        fn temporary_columns(&self) -> Vec<String> {
+            vec!["Uso Misto".to_string()]
+        }
+
+        fn condition(&self) -> JoinResult<Expr> {
+            Ok(col("Uso Misto"))
+        }
+
+        fn message(&self) -> JoinResult<Expr> {
+            Ok(lit("Item de uso misto."))
+        }
+
+        fn adjustment(&self) -> JoinResult<Expr> {
+            Ok(col(coluna(Left, "valor_bc")) * lit(0.6))
+        }
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_regra_personalizada`
+    fn test_regra_personalizada() -> JoinResult<()> {
+        let valor_bc: &str = coluna(Left, "valor_bc");
+        let glosar: &str = coluna(Middle, "glosar");
+
+        let dataframe: DataFrame = df! [
+            valor_bc => [500.0, 80.0],
+            glosar   => ["", ""],
+        ]?;
+
+        let args: Arguments = Arguments::default();
+
+        let df: DataFrame = aplicar_regra(dataframe.lazy(), &UsoMisto, &args)?.collect()?;
+        println!("df: {df}\n");
+
+        // A coluna temporária é removida após a aplicação da regra.
+        assert!(df.column("Uso Misto").is_err());
+
+        let valores: Vec<Option<f64>> = df.column(valor_bc)?.f64()?.iter().collect();
+        assert_eq!(valores, [Some(300.0), Some(80.0)]);
+
+        let glosado: Vec<Option<f64>> = df
+            .column(&coluna_valor_glosado("x01"))?
+            .f64()?
+            .iter()
+            .collect();
+        assert_eq!(glosado, [Some(200.0), None]);
+
+        let mensagens: Vec<Option<&str>> = df.column(glosar)?.str()?.iter().collect();
+        assert_eq!(
+            mensagens,
+            [Some(" Situação x01: Item de uso misto. &"), Some("")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_documentos_em_multiplos_periodos`
+    fn test_documentos_em_multiplos_periodos() -> JoinResult<()> {
+        configure_the_environment();
+
+        let periodo_de_apuracao: &str = coluna(Left, "pa");
+        let cnpj_particip: &str = coluna(Left, "cnpj_particip");
+        let num_doc: &str = coluna(Left, "num_doc");
+
+        // O documento 10 do CNPJ "A" é escriturado em dois Períodos de Apuração.
+        let dataframe: DataFrame = df! [
+            periodo_de_apuracao => ["2022-01-01", "2022-03-01", "2022-01-01", "2022-02-01"],
+            coluna(Left, "registro") => ["C170", "C170", "C170", "C170"],
+            cnpj_particip => ["A", "A", "B", "B"],
+            num_doc => [10, 10, 10, 20],
+            coluna(Left, "valor_item") => [100.0, 100.0, 50.0, 70.0],
+        ]?
+        .lazy()
+        .with_column(
+            col(periodo_de_apuracao)
+                .str()
+                .to_date(StrptimeOptions::default()),
+        )
+        .collect()?;
+
+        let regras = RegrasDeGlosa::padrao()?;
+        let df_situacao06b =
+            obter_documentos_em_multiplos_periodos_por_cnpj_e_numero(&dataframe, &regras)?;
+        println!("df_situacao06b: {df_situacao06b}");
+
+        assert_eq!(df_situacao06b.height(), 1);
+        assert!(df_situacao06b.column("Períodos Formatados").is_err());
+
+        let periodos: Vec<Option<u32>> = df_situacao06b
+            .column("Nº de Períodos")?
+            .cast(&DataType::UInt32)?
+            .u32()?
+            .iter()
+            .collect();
+        assert_eq!(periodos, [Some(2)]);
+
+        // Regra inativa: tabela vazia, com as mesmas colunas.
+        let excluir: Vec<String> = vec!["06b".to_string()];
+        let selecao = SelecaoDeSituacoes::new(None, Some(&excluir));
+        let regras = RegrasDeGlosa::padrao()?.com_selecao(selecao)?;
+        let df_vazio =
+            obter_documentos_em_multiplos_periodos_por_cnpj_e_numero(&dataframe, &regras)?;
+
+        assert_eq!(df_vazio.height(), 0);
+        assert_eq!(df_vazio.schema(), df_situacao06b.schema());
+
+        Ok(())
+    }
+
     #[test]
     /// cargo test -- --show-output identificar_elementos_em_uma_lista
     fn identificar_elementos_em_uma_lista() -> PolarsResult<()> {
@@ -0,0 +1,424 @@
+//! # Impacto das Glosas
+//!
+//! Resumo, por regra de glosa, do valor da Base de Cálculo glosada e dos
+//! créditos de PIS/PASEP e COFINS perdidos, por CNPJ Base e período de apuração.
+//!
+//! Os créditos são obtidos com `obter_consolidacao_nat`, a mesma função que
+//! produz a aba "EFD (após auditoria)", somando as linhas mensais das naturezas
+//! 300 (Base de Cálculo dos Créditos), 201 (Crédito de PIS/PASEP) e 205
+//! (Crédito de COFINS).
+//!
+//! ## Contribuição de cada regra
+//!
+//! As regras são aplicadas em sequência (Ordem de Aplicação). A contribuição
+//! da regra `k` é a diferença entre os créditos apurados antes e após a sua
+//! aplicação, reconstruídos a partir das colunas `Valor Glosado: Situação <id>`.
+//! Assim, a soma das contribuições coincide com a diferença entre os créditos
+//! sem glosa e os créditos da aba "EFD (após auditoria)".
+//!
+//! ## Efeito marginal
+//!
+//! Com `--efeito-marginal true`, `glosar_bc` é executado novamente sem cada
+//! regra, com as mesmas exceções (`--excecoes`), lidas uma única vez. O efeito
+//! marginal é o crédito restabelecido caso a regra seja retirada. Difere da
+//! contribuição quando uma linha é atingida por mais de uma regra: sem a
+//! primeira, as regras seguintes passam a glosar o saldo.
+
+use polars::prelude::*;
+
+use crate::{
+    Arguments, ExcecoesDeGlosa, ExprExtension, GlosaRule, JoinResult, RegrasDeGlosa, Side::Left,
+    coluna, coluna_valor_glosado, glosar_bc, obter_consolidacao_nat,
+};
+
+/// Natureza "Base de Cálculo dos Créditos: Valor Total".
+const NATUREZA_BC_DOS_CREDITOS: i64 = 300;
+/// Natureza "Crédito Apurado no Período (PIS/PASEP)".
+const NATUREZA_CREDITO_PIS: i64 = 201;
+/// Natureza "Crédito Apurado no Período (COFINS)".
+const NATUREZA_CREDITO_COFINS: i64 = 205;
+
+/// Chaves de agregação: CNPJ Base e período de apuração mensal.
+const CHAVES: [&str; 3] = [
+    "CNPJ Base",
+    "Ano do Período de Apuração",
+    "Mês do Período de Apuração",
+];
+
+/// Colunas do resumo de impacto (além das chaves de agregação).
+pub const COLUNAS_IMPACTO: [&str; 10] = [
+    "Ordem de Aplicação",
+    "Regra",
+    "Situação",
+    "Descrição",
+    "Valor da Base de Cálculo Glosada",
+    "Valor do Crédito de PIS/PASEP Glosado",
+    "Valor do Crédito de COFINS Glosado",
+    "Efeito Marginal: Valor da Base de Cálculo",
+    "Efeito Marginal: Valor do Crédito de PIS/PASEP",
+    "Efeito Marginal: Valor do Crédito de COFINS",
+];
+
+/// Colunas de valores dos créditos apurados por período.
+const CREDITOS: [&str; 3] = ["Base de Cálculo", "PIS/PASEP", "COFINS"];
+
+/// Obtém o resumo do impacto de cada regra de glosa.
+///
+/// Cada regra aplicada gera uma linha por CNPJ Base e mês, seguida de uma linha
+/// de total da regra (chaves nulas). A última linha ("Total") soma todas as regras.
+pub fn obter_impacto_das_glosas(
+    df_itens: &DataFrame,
+    df_itens_result: &DataFrame,
+    regras: &RegrasDeGlosa,
+    excecoes: Option<&ExcecoesDeGlosa>,
+    args: &Arguments,
+) -> JoinResult<DataFrame> {
+    let valor_bc: &str = coluna(Left, "valor_bc");
+
+    let aplicadas: Vec<(usize, &dyn GlosaRule, String)> = regras
+        .ativas()
+        .enumerate()
+        .map(|(indice, regra)| (indice, regra, coluna_valor_glosado(regra.id())))
+        .filter(|(_, _, glosado)| df_itens_result.column(glosado).is_ok())
+        .collect();
+
+    // creditos[k]: créditos antes da k-ésima regra aplicada.
+    // creditos[n]: créditos após todas as regras ("EFD (após auditoria)").
+    let mut creditos: Vec<DataFrame> = vec![creditos_por_periodo(df_itens_result)?];
+    let mut restabelecido: Expr = lit(0.0);
+
+    for (_, _, glosado) in aplicadas.iter().rev() {
+        restabelecido = restabelecido + col(glosado).fill_null(lit(0.0));
+
+        let df_estado: DataFrame = df_itens_result
+            .clone()
+            .lazy()
+            .with_column((col(valor_bc) + restabelecido.clone()).alias(valor_bc))
+            .collect()?;
+
+        creditos.push(creditos_por_periodo(&df_estado)?);
+    }
+
+    creditos.reverse();
+
+    let auditado: &DataFrame = &creditos[aplicadas.len()];
+
+    if aplicadas.is_empty() {
+        return Ok(dataframe_vazio(auditado, args)?);
+    }
+
+    let mut lazyframes: Vec<LazyFrame> = Vec::new();
+
+    for (k, (indice, regra, _)) in aplicadas.iter().enumerate() {
+        let mut partes: Vec<LazyFrame> = vec![diferenca(
+            &creditos[k],
+            &creditos[k + 1],
+            &COLUNAS_IMPACTO[4..7],
+        )?];
+
+        if args.efeito_marginal == Some(true) {
+            let mut sem_a_regra: RegrasDeGlosa = regras.clone();
+            sem_a_regra.selecao.excluir.push(regra.id().to_string());
+
+            let df_sem_a_regra: DataFrame = glosar_bc(df_itens, &sem_a_regra, excecoes, args)?;
+            let creditos_sem_a_regra: DataFrame = creditos_por_periodo(&df_sem_a_regra)?;
+
+            partes.push(diferenca(
+                &creditos_sem_a_regra,
+                auditado,
+                &COLUNAS_IMPACTO[7..10],
+            )?);
+        }
+
+        lazyframes.push(
+            somar_por(concat_diagonal(partes)?, &CHAVES, &valores(args)).with_columns([
+                lit(*indice as u32 + 1).alias(COLUNAS_IMPACTO[0]),
+                lit(regra.id()).alias(COLUNAS_IMPACTO[1]),
+                lit(regra.situation()).alias(COLUNAS_IMPACTO[2]),
+                lit(regra.description()).alias(COLUNAS_IMPACTO[3]),
+            ]),
+        );
+    }
+
+    let df_impacto: DataFrame = adicionar_totais(concat_diagonal(lazyframes)?, args)?
+        .select(colunas_de_saida(args))
+        .collect()?;
+
+    println!("Impacto das Glosas: {} regras aplicadas\n", aplicadas.len());
+
+    Ok(df_impacto)
+}
+
+/// Soma, por CNPJ Base e mês, a Base de Cálculo e os créditos apurados em
+/// `obter_consolidacao_nat` (linhas trimestrais excluídas).
+fn creditos_por_periodo(df_itens: &DataFrame) -> JoinResult<DataFrame> {
+    let natureza: &str = coluna(Left, "natureza");
+    let valor_bc: &str = coluna(Left, "valor_bc");
+
+    let somar = |codigo: i64| -> Expr { col(valor_bc).filter(col(natureza).eq(lit(codigo))).sum() };
+
+    let df_creditos: DataFrame = obter_consolidacao_nat(df_itens, true)?
+        .lazy()
+        .filter(col(CHAVES[2]).is_not_null())
+        .group_by(CHAVES.map(col))
+        .agg([
+            somar(NATUREZA_BC_DOS_CREDITOS).alias(CREDITOS[0]),
+            somar(NATUREZA_CREDITO_PIS).alias(CREDITOS[1]),
+            somar(NATUREZA_CREDITO_COFINS).alias(CREDITOS[2]),
+        ])
+        .collect()?;
+
+    Ok(df_creditos)
+}
+
+/// Diferença `minuendo - subtraendo` por CNPJ Base e mês, nomeada com `nomes`.
+///
+/// Períodos ausentes em um dos lados são tratados como zero; períodos sem
+/// diferença são descartados.
+fn diferenca(
+    minuendo: &DataFrame,
+    subtraendo: &DataFrame,
+    nomes: &[&str],
+) -> JoinResult<LazyFrame> {
+    let renomear = |sinal: f64| -> Vec<Expr> {
+        CHAVES
+            .iter()
+            .map(|chave| col(*chave))
+            .chain(CREDITOS.iter().zip(nomes).map(|(credito, nome)| {
+                (lit(sinal) * col(*credito).fill_null(lit(0.0))).alias(*nome)
+            }))
+            .collect()
+    };
+
+    let lazyframe: LazyFrame = concat(
+        [
+            minuendo.clone().lazy().select(renomear(1.0)),
+            subtraendo.clone().lazy().select(renomear(-1.0)),
+        ],
+        UnionArgs::default(),
+    )?;
+
+    let alguma_diferenca: Expr = nomes
+        .iter()
+        .map(|nome| col(*nome).abs().gt_eq(lit(0.005)))
+        .reduce(|acc, expr| acc.or(expr))
+        .unwrap_or(lit(false));
+
+    Ok(somar_por(lazyframe, &CHAVES, nomes).filter(alguma_diferenca))
+}
+
+/// Adiciona as linhas de total de cada regra e a linha de total geral.
+fn adicionar_totais(lazyframe: LazyFrame, args: &Arguments) -> JoinResult<LazyFrame> {
+    let regra: [&str; 4] = [
+        COLUNAS_IMPACTO[0],
+        COLUNAS_IMPACTO[1],
+        COLUNAS_IMPACTO[2],
+        COLUNAS_IMPACTO[3],
+    ];
+
+    let total_por_regra: LazyFrame = somar_por(lazyframe.clone(), &regra, &valores(args));
+
+    let total_geral: LazyFrame = lazyframe
+        .clone()
+        .select([cols(valores(args)).as_expr().sum().round_expr(2)])
+        .with_column(lit("Total").alias(COLUNAS_IMPACTO[1]));
+
+    let ordenar_por: Vec<&str> = [COLUNAS_IMPACTO[0]].into_iter().chain(CHAVES).collect();
+
+    Ok(
+        concat_diagonal(vec![lazyframe, total_por_regra, total_geral])?.sort(
+            ordenar_por,
+            SortMultipleOptions::default()
+                .with_nulls_last(true)
+                .with_maintain_order(true),
+        ),
+    )
+}
+
+/// Agrupa por `chaves` somando as colunas `valores`.
+fn somar_por(lazyframe: LazyFrame, chaves: &[&str], valores: &[&str]) -> LazyFrame {
+    let chaves: Vec<Expr> = chaves.iter().map(|chave| col(*chave)).collect();
+
+    lazyframe
+        .group_by(chaves)
+        .agg([cols(valores.iter().copied()).as_expr().sum().round_expr(2)])
+}
+
+/// Concatena LazyFrames com colunas distintas, preenchendo as ausentes com nulo.
+fn concat_diagonal(lazyframes: Vec<LazyFrame>) -> PolarsResult<LazyFrame> {
+    let union_args = UnionArgs {
+        diagonal: true,
+        to_supertypes: true,
+        ..Default::default()
+    };
+
+    concat(lazyframes, union_args)
+}
+
+/// Colunas de valores do resumo (com ou sem o efeito marginal).
+fn valores(args: &Arguments) -> Vec<&'static str> {
+    let fim: usize = if args.efeito_marginal == Some(true) {
+        10
+    } else {
+        7
+    };
+    COLUNAS_IMPACTO[4..fim].to_vec()
+}
+
+/// Ordem das colunas na aba "Impacto das Glosas".
+fn colunas_de_saida(args: &Arguments) -> Vec<Expr> {
+    COLUNAS_IMPACTO[0..4]
+        .iter()
+        .chain(CHAVES.iter())
+        .chain(valores(args).iter())
+        .map(|nome| col(*nome))
+        .collect()
+}
+
+/// Resumo sem linhas, com as mesmas colunas.
+fn dataframe_vazio(auditado: &DataFrame, args: &Arguments) -> PolarsResult<DataFrame> {
+    let mut df: DataFrame = auditado.select(CHAVES)?.clear();
+
+    for (indice, nome) in COLUNAS_IMPACTO.iter().enumerate() {
+        let dtype: DataType = match indice {
+            0 => DataType::UInt32,
+            1..=3 => DataType::String,
+            _ => DataType::Float64,
+        };
+        df.with_column(Column::new_empty((*nome).into(), &dtype))?;
+    }
+
+    df.lazy().select(colunas_de_saida(args)).collect()
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_impacto_das_glosas
+#[cfg(test)]
+mod tests_impacto_das_glosas {
+    use super::*;
+
+    /// Créditos por período no formato de `creditos_por_periodo`.
+    fn creditos(cnpj: &[&str], mes: &[i64], bc: &[f64]) -> JoinResult<DataFrame> {
+        let pis: Vec<f64> = bc.iter().map(|valor| valor * 0.0165).collect();
+        let cofins: Vec<f64> = bc.iter().map(|valor| valor * 0.076).collect();
+
+        Ok(df! {
+            CHAVES[0] => cnpj,
+            CHAVES[1] => vec![2023i64; cnpj.len()],
+            CHAVES[2] => mes,
+            CREDITOS[0] => bc,
+            CREDITOS[1] => pis,
+            CREDITOS[2] => cofins,
+        }?)
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_diferenca_de_creditos`
+    fn test_diferenca_de_creditos() -> JoinResult<()> {
+        // Sem glosa: três períodos. Após a glosa: o mês 2 foi integralmente glosado.
+        let antes = creditos(
+            &["12345678", "12345678", "87654321"],
+            &[1, 2, 1],
+            &[1000.0, 500.0, 200.0],
+        )?;
+        let apos = creditos(&["12345678", "87654321"], &[1, 1], &[900.0, 200.0])?;
+
+        let df_diferenca: DataFrame = diferenca(&antes, &apos, &COLUNAS_IMPACTO[4..7])?
+            .sort(CHAVES, SortMultipleOptions::default())
+            .collect()?;
+
+        println!("df_diferenca: {df_diferenca}");
+
+        // O CNPJ Base 87654321 não teve glosa.
+        assert_eq!(df_diferenca.height(), 2);
+
+        let bc: Vec<Option<f64>> = df_diferenca
+            .column(COLUNAS_IMPACTO[4])?
+            .f64()?
+            .iter()
+            .collect();
+        let pis: Vec<Option<f64>> = df_diferenca
+            .column(COLUNAS_IMPACTO[5])?
+            .f64()?
+            .iter()
+            .collect();
+        let cofins: Vec<Option<f64>> = df_diferenca
+            .column(COLUNAS_IMPACTO[6])?
+            .f64()?
+            .iter()
+            .collect();
+
+        assert_eq!(bc, [Some(100.0), Some(500.0)]);
+        assert_eq!(pis, [Some(1.65), Some(8.25)]);
+        assert_eq!(cofins, [Some(7.6), Some(38.0)]);
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_totais_do_impacto`
+    fn test_totais_do_impacto() -> JoinResult<()> {
+        let args = Arguments::default();
+
+        let sem_glosa = creditos(&["12345678", "12345678"], &[1, 2], &[1000.0, 500.0])?;
+        let apos_03 = creditos(&["12345678", "12345678"], &[1, 2], &[1000.0, 0.0])?;
+        let apos_04 = creditos(&["12345678", "12345678"], &[1, 2], &[700.0, 0.0])?;
+
+        let por_regra = |indice: u32, id: &str, antes: &DataFrame, apos: &DataFrame| {
+            Ok::<LazyFrame, crate::JoinError>(
+                diferenca(antes, apos, &COLUNAS_IMPACTO[4..7])?.with_columns([
+                    lit(indice).alias(COLUNAS_IMPACTO[0]),
+                    lit(id).alias(COLUNAS_IMPACTO[1]),
+                    lit(id).alias(COLUNAS_IMPACTO[2]),
+                    lit("").alias(COLUNAS_IMPACTO[3]),
+                ]),
+            )
+        };
+
+        let lazyframe = concat_diagonal(vec![
+            por_regra(1, "03", &sem_glosa, &apos_03)?,
+            por_regra(2, "04", &apos_03, &apos_04)?,
+        ])?;
+
+        let df_impacto: DataFrame = adicionar_totais(lazyframe, &args)?
+            .select(colunas_de_saida(&args))
+            .collect()?;
+
+        println!("df_impacto: {df_impacto}");
+
+        // Uma linha por regra e período, um total por regra e o total geral.
+        assert_eq!(df_impacto.height(), 5);
+        assert_eq!(df_impacto.width(), 4 + CHAVES.len() + 3);
+
+        let regra: Vec<Option<&str>> = df_impacto
+            .column(COLUNAS_IMPACTO[1])?
+            .str()?
+            .iter()
+            .collect();
+        assert_eq!(
+            regra,
+            [
+                Some("03"),
+                Some("03"),
+                Some("04"),
+                Some("04"),
+                Some("Total")
+            ]
+        );
+
+        // A soma das contribuições reconcilia com a diferença entre os créditos
+        // sem glosa e os créditos após a auditoria.
+        let total: Vec<Option<f64>> = df_impacto
+            .column(COLUNAS_IMPACTO[4])?
+            .f64()?
+            .iter()
+            .collect();
+        assert_eq!(total[4], Some(800.0));
+
+        Ok(())
+    }
+}
@@ -1,19 +1,32 @@
 mod all_data;
 mod analise_do_periodo_de_apuracao;
 mod args;
+mod atividade_comercial;
 mod columns;
 mod consolidacao_da_natureza;
+mod correspondencia_de_cfop;
+mod creditos_estornados;
 mod descricoes;
+mod dicionario_de_termos;
 mod error;
+mod excecoes_de_glosa;
 mod excel;
+mod excesso_de_credito;
+mod execucao;
 mod filtros;
 mod format;
+mod glosa_rule;
 mod glosar_base_de_calculo;
+mod impacto_das_glosas;
 mod munkres;
 mod polars_assignments;
 mod rateio_dos_creditos;
 mod regime_fiscal;
+mod regras_de_glosa;
+mod rejeitados;
+mod tabela_csv;
 mod traits;
+mod trilha_de_auditoria;
 
 /// A module that exports the `PolarsExcelWriter` struct which provides an Excel
 /// Xlsx serializer that works with Polars dataframes and which can also
@@ -24,28 +37,49 @@ use crate::descricoes::descricao_do_cst;
 
 pub use self::{
     all_data::*,
-    analise_do_periodo_de_apuracao::adicionar_coluna_periodo_de_apuracao_inicial_e_final,
+    analise_do_periodo_de_apuracao::{
+        adicionar_coluna_limite_de_decadencia, adicionar_coluna_periodo_de_apuracao_inicial_e_final,
+    },
     args::*,
+    atividade_comercial::*,
     columns::{
-        MyColumn,
+        MyColumn, PREFIXO_EXCECAO_APLICADA, PREFIXO_LINHA_GLOSADA, PREFIXO_VALOR_GLOSADO,
         Side::{self, Left, Middle, Right},
-        coluna,
+        coluna, is_coluna_de_glosa, obter_coluna,
     },
     consolidacao_da_natureza::obter_consolidacao_nat,
+    correspondencia_de_cfop::*,
+    creditos_estornados::*,
     descricoes::{
         descricao_da_natureza_da_bc_dos_creditos, descricao_da_origem, descricao_do_mes,
         descricao_do_tipo_de_credito, descricao_do_tipo_de_operacao,
     },
+    dicionario_de_termos::*,
     error::{JoinError, JoinResult},
+    excecoes_de_glosa::{
+        COLUNAS_EXCECOES, Excecao, ExcecoesDeGlosa, coluna_excecao_aplicada,
+        obter_excecoes_aplicadas,
+    },
     excel::*,
     excel_writer::PolarsExcelWriter,
+    excesso_de_credito::*,
+    execucao::*,
     filtros::*,
     format::*,
-    glosar_base_de_calculo::glosar_bc,
+    glosa_rule::GlosaRule,
+    glosar_base_de_calculo::{
+        glosar_bc, obter_documentos_em_multiplos_periodos_por_chave,
+        obter_documentos_em_multiplos_periodos_por_cnpj_e_numero,
+    },
+    impacto_das_glosas::*,
     munkres::{munkres_assignments, try_convert},
     polars_assignments::get_dataframe_after_assignments,
     rateio_dos_creditos::RateioDosCreditos,
+    regime_fiscal::{BaseLegal, MatchedBy, RegimesFiscais, adicionar_coluna_de_base_legal},
+    regras_de_glosa::*,
+    rejeitados::*,
     traits::*,
+    trilha_de_auditoria::*,
 };
 
 use polars::prelude::*;
@@ -54,6 +88,7 @@ use std::{
     collections::{HashMap, HashSet},
     env,
     fs::File,
+    io::BufReader,
     path::PathBuf,
 };
 use sysinfo::System;
@@ -470,40 +505,91 @@ fn download_file_from_the_internet(url: &str, output_file: &str) {
 // This allows you to combine expression into powerful aggregations and column selections.
 // All expressions are evaluated in parallel and your queries are optimized just in time.
 
+/// Arquivo CSV lido com todas as colunas do tipo String e convertido aos tipos
+/// definidos em `MyColumn`.
+///
+/// Os planos não são executados na leitura: o arquivo não é carregado em memória.
+pub struct LeituraDoCsv {
+    /// Colunas convertidas (valores rejeitados tornam-se nulos).
+    pub lazyframe: LazyFrame,
+
+    /// Valores informados que não puderam ser convertidos (ver [`COLUNAS_REJEITADOS`]).
+    pub rejeitados: LazyFrame,
+}
+
+/// Conversão de uma coluna lida como texto ao tipo definido em `MyColumn`.
+#[derive(Debug, Clone)]
+pub struct ConversaoDeTipo {
+    pub coluna: String,
+
+    /// Expressão de conversão: valores incompatíveis tornam-se nulos.
+    pub expr: Expr,
+
+    /// Mensagem registrada no relatório de valores rejeitados.
+    pub erro: String,
+}
+
+/// Coluna temporária: número da linha no arquivo CSV (o cabeçalho é a linha 1).
+const LINHA_DO_CSV: &str = "Linha do Arquivo CSV Temp";
+
 pub fn get_lazyframe_from_csv(
     file_path: Option<PathBuf>,
     delimiter: Option<char>,
     side: Side,
-) -> JoinResult<LazyFrame> {
+) -> JoinResult<LeituraDoCsv> {
     validate_entries(file_path.clone(), delimiter, side)?;
 
-    let mut options = StrptimeOptions {
-        format: None,
-        strict: false, // If set then polars will return an error if any date parsing fails
-        exact: true, // If polars may parse matches that not contain the whole string e.g. “foo-2021-01-01-bar” could match “2021-01-01”
-        cache: true, // use a cache of unique, converted dates to apply the datetime conversion.
-    };
+    let arquivo: String = file_path
+        .as_ref()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default();
 
-    match side {
-        Side::Left => options.format = Some("%Y-%-m-%-d".into()),
-        Side::Right => options.format = Some("%-d/%-m/%Y".into()),
-        Side::Middle => {
-            return Err(JoinError::InvalidSide(side.to_string()));
+    // Registros com número de campos diferente do cabeçalho: rejeitados e excluídos.
+    let irregulares: Vec<RegistroIrregular> = match (&file_path, delimiter) {
+        (Some(path), Some(separator)) => {
+            registros_irregulares(BufReader::new(File::open(path)?), separator as u8)?
         }
+        _ => Vec::new(),
+    };
+
+    // Os valores convertidos e os rejeitados são obtidos das mesmas colunas de
+    // texto e das mesmas expressões de conversão, sem materializar o arquivo.
+    let mut texto: LazyFrame = read_csv_as_text(file_path, delimiter)?
+        .with_columns([build_null_expression(false)?])
+        .with_row_index(LINHA_DO_CSV, Some(2));
+
+    if !irregulares.is_empty() {
+        let linhas: Vec<u64> = irregulares.iter().map(|registro| registro.linha).collect();
+        let linhas: Expr = Series::new("linhas".into(), linhas).to_list_expr()?;
+        texto = texto.filter(
+            col(LINHA_DO_CSV)
+                .cast(DataType::UInt64)
+                .is_in(linhas, false)
+                .not(),
+        );
     }
 
-    let replacement_expr: Expr = build_null_expression(true)?;
+    let conversoes: Vec<ConversaoDeTipo> =
+        conversoes_de_tipo(texto.clone().collect_schema()?.as_ref(), side)?;
 
-    // Format date
-    let mut lazyframe: LazyFrame = read_csv_lazy(file_path, delimiter, side)?
-        .with_columns([replacement_expr])
-        .with_column(
-            col("^(Período|Data|Dia).*$") // regex
-                .str()
-                .to_date(options),
-        );
+    let rejeitados: LazyFrame = concat(
+        [
+            rejeitados_dos_registros(&irregulares, &arquivo)?.lazy(),
+            rejeitados_da_conversao(texto.clone(), LINHA_DO_CSV, &conversoes, &arquivo)?,
+        ],
+        UnionArgs::default(),
+    )?;
+
+    let mut lazyframe: LazyFrame = texto
+        .with_columns(
+            conversoes
+                .into_iter()
+                .map(|conversao| conversao.expr)
+                .collect::<Vec<Expr>>(),
+        )
+        .drop_columns(&[LINHA_DO_CSV])?;
 
-    println!("{}\n", lazyframe.clone().collect()?);
+    println!("{}\n", lazyframe.clone().amostra()?);
 
     // Print column names and their respective types
     // Iterates over the `(&name, &dtype)` pairs in this schema
@@ -521,9 +607,33 @@ pub fn get_lazyframe_from_csv(
 
     println!();
 
-    // println!("teste dataframe: {:#?}", lazyframe.clone().collect()?);
+    Ok(LeituraDoCsv {
+        lazyframe,
+        rejeitados,
+    })
+}
+
+/// Regex that selects the date columns of both CSV files.
+pub const DATE_COLUMNS_PATTERN: &str = "^(Período|Data|Dia).*$";
+
+/// Date parsing options of each CSV file.
+///
+/// Left side (EFD): `yyyy-mm-dd`; Right side (NF-e/CT-e): `dd/mm/yyyy`.
+pub fn get_strptime_options(side: Side) -> JoinResult<StrptimeOptions> {
+    let format = match side {
+        Side::Left => "%Y-%-m-%-d",
+        Side::Right => "%-d/%-m/%Y",
+        Side::Middle => {
+            return Err(JoinError::InvalidSide(side.to_string()));
+        }
+    };
 
-    Ok(lazyframe)
+    Ok(StrptimeOptions {
+        format: Some(format.into()),
+        strict: false, // If set then polars will return an error if any date parsing fails
+        exact: true, // If polars may parse matches that not contain the whole string e.g. “foo-2021-01-01-bar” could match “2021-01-01”
+        cache: true, // use a cache of unique, converted dates to apply the datetime conversion.
+    })
 }
 
 /// Define values to be interpreted as null across all columns.
@@ -619,54 +729,59 @@ fn validate_entries(
     Ok(())
 }
 
-/// Helper function to modify the schema obtained from CSV headers.
-/// It applies data types from a predefined map and defaults to String for others.
-/// Receives schema by value, applies modifications, and returns a new schema result.
-fn apply_custom_schema_rules(
-    schema: Schema, // Input schema (passed by value, inferred from CSV headers)
-    cols_dtype_map: &HashMap<&'static str, DataType>, // Map of custom types
-    side: Side,     // Contextual side information
-) -> PolarsResult<Schema> {
-    // Build a new list of fields based on our desired types, preserving original order.
-    let mut modified_fields: Vec<Field> = Vec::with_capacity(schema.len());
-
-    // Iterate through the fields found in the CSV header (order is preserved)
-    for field in schema.iter_fields() {
-        let col_name = field.name().as_str();
-        match cols_dtype_map.get(col_name) {
-            Some(desired_dtype) => {
-                // If the column is in our map, use the specified type.
-                modified_fields.push(Field::new(col_name.into(), desired_dtype.clone()));
-            }
-            None => {
-                // If not in map, keep it as String and warn the user.
-                eprintln!("Insert DataType for column '{col_name}' in Column {side:?}!");
-                modified_fields.push(Field::new(col_name.into(), DataType::String));
-                // Ensure String
-            }
+/// Conversões das colunas lidas como texto aos tipos definidos em `MyColumn`.
+///
+/// As colunas de data ([`DATE_COLUMNS_PATTERN`]) são convertidas conforme
+/// [`get_strptime_options`]. Colunas do tipo String não são convertidas.
+pub fn conversoes_de_tipo(schema: &Schema, side: Side) -> JoinResult<Vec<ConversaoDeTipo>> {
+    let cols_dtype: HashMap<&str, DataType> = MyColumn::get_cols_dtype(side);
+    let date_options: StrptimeOptions = get_strptime_options(side)?;
+    let regex_date_columns = regex::Regex::new(DATE_COLUMNS_PATTERN)
+        .map_err(|erro| JoinError::Other(format!("{DATE_COLUMNS_PATTERN}: {erro}")))?;
+
+    let mut conversoes: Vec<ConversaoDeTipo> = Vec::new();
+
+    for column_name in schema.iter_names().map(|name| name.as_str()) {
+        if column_name == LINHA_DO_CSV {
+            continue;
+        }
+
+        if regex_date_columns.is_match(column_name) {
+            let formato = date_options.format.clone().unwrap_or_default();
+            conversoes.push(ConversaoDeTipo {
+                coluna: column_name.to_string(),
+                expr: col(column_name).str().to_date(date_options.clone()),
+                erro: format!("Data inválida (formato esperado: {formato})"),
+            });
+            continue;
         }
-    }
 
-    // Create and return the new schema.
-    // Using `Schema::from_iter` is clean as we built a new Vec<Field>.
-    let new_schema = Schema::from_iter(modified_fields);
+        match cols_dtype.get(column_name) {
+            Some(DataType::String) => {}
+            Some(dtype) => conversoes.push(ConversaoDeTipo {
+                coluna: column_name.to_string(),
+                expr: col(column_name).cast(dtype.clone()),
+                erro: format!("Valor incompatível com o tipo {dtype}"),
+            }),
+            // If not in map, keep it as String and warn the user.
+            None => eprintln!("Insert DataType for column '{column_name}' in Column {side:?}!"),
+        }
+    }
 
-    Ok(new_schema) // Return the new/modified schema wrapped in Result
+    Ok(conversoes)
 }
 
 /// Function to read a CSV file into a lazy frame using Polars.
-/// Configures reading using schema modification based on a user-provided map.
-fn read_csv_lazy(
+///
+/// All columns are read as String: the types defined in `MyColumn` are applied
+/// by [`conversoes_de_tipo`], which also identifies the rejected values.
+/// Reading errors are not ignored.
+fn read_csv_as_text(
     file_path: Option<PathBuf>, // Optional path to the CSV file
     delimiter: Option<char>,    // Optional delimiter character
-    side: Side,                 // Custom parameter (e.g., determines schema)
 ) -> JoinResult<LazyFrame> {
     match (&file_path, delimiter) {
         (Some(path), Some(separator)) => {
-            // Get the expected column names and their data types BEFORE the closure.
-            // Use Arc to efficiently share the map ownership with the 'move' closure.
-            let cols_dtype: HashMap<&str, DataType> = MyColumn::get_cols_dtype(side);
-
             let pl_ref_path = PlRefPath::try_from_path(path)?;
 
             // Create a LazyCsvReader to process the file lazily.
@@ -677,18 +792,14 @@ fn read_csv_lazy(
                     .with_separator(separator as u8) // Set the column delimiter
                     .with_quote_char(Some(b'"')) // Set the quote character (default)
                     .with_has_header(true) // Indicate the CSV file has a header row
-                    .with_ignore_errors(true) // Continue reading even if parsing errors occur
+                    // Registros com campos a mais ou a menos são reportados por
+                    // `registros_irregulares()` e excluídos em `get_lazyframe_from_csv()`.
+                    .with_truncate_ragged_lines(true)
                     //.with_null_values(Some(NullValues::AllColumns(null_values))) // Apply the predefined null values list
                     .with_null_values(None) // Apply fn build_null_expression()
                     .with_missing_is_null(true) // Treat missing fields as null
-                    // Infer schema length 0 reads only headers. Polars gets column names.
+                    // Infer schema length 0 reads only headers: all columns are String.
                     .with_infer_schema_length(Some(0))
-                    // Modify the schema using the separate helper function.
-                    // The closure's role is now just to bridge from the Polars API signature
-                    // to the helper function signature, passing the captured data.
-                    .with_schema_modify(|schema: Schema| {
-                        apply_custom_schema_rules(schema, &cols_dtype, side)
-                    })? // Add the '?' here to unwrap the result of with_schema_modify
                     .with_rechunk(true) // Optional rechunking step
                     .finish() // Finalize configuration and get the LazyFrame
                     .map_err(|e| JoinError::CSVReadError(e, path.clone()));
@@ -726,7 +837,7 @@ pub fn write_csv(df: &DataFrame, basename: &str, delimiter: char) -> PolarsResul
     let is_itens = basename.to_lowercase().contains("itens");
 
     // Formata o dataframe com a opção condicional de CST
-    let mut df_formated = format_dataframe(df, is_itens)?;
+    let mut df_formated = listas_como_texto(&format_dataframe(df, is_itens)?)?;
     println!("{df_formated}\n");
 
     let mut output_csv: File = File::create(filepath)?;
@@ -740,6 +851,29 @@ pub fn write_csv(df: &DataFrame, basename: &str, delimiter: char) -> PolarsResul
     Ok(())
 }
 
+/// Converte as colunas do tipo lista (ex.: "Situações de Glosa") em texto
+/// com os elementos separados por vírgula, pois os formatos CSV e XLSX não
+/// suportam tipos aninhados. O formato Parquet preserva as listas.
+pub fn listas_como_texto(data_frame: &DataFrame) -> PolarsResult<DataFrame> {
+    let exprs: Vec<Expr> = data_frame
+        .columns()
+        .iter()
+        .filter(|column| matches!(column.dtype(), DataType::List(_)))
+        .map(|column| {
+            col(column.name().as_str())
+                .cast(DataType::List(Box::new(DataType::String)))
+                .list()
+                .join(lit(", "), true)
+        })
+        .collect();
+
+    if exprs.is_empty() {
+        return Ok(data_frame.clone());
+    }
+
+    data_frame.clone().lazy().with_columns(exprs).collect()
+}
+
 /// Formata o DataFrame aplicando descrições categóricas.
 /// O parâmetro `incluir_cst` permite condicionalmente descrever o CST.
 pub fn format_dataframe(data_frame: &DataFrame, incluir_cst: bool) -> PolarsResult<DataFrame> {
@@ -1300,11 +1434,11 @@ mod tests_read_csv {
 
     // O teste de sucesso não deveria ter esse problema, pois ele espera Ok(LazyFrame)
     #[test]
-    fn test_read_csv_lazy_success() -> JoinResult<()> {
+    fn test_read_csv_as_text_success() -> JoinResult<()> {
         let dir = tempdir()?;
         let file_path = create_csv(dir.path(), "data.csv", "col1,col2\n1,a\n2,b")?;
 
-        let lazy_frame = read_csv_lazy(Some(file_path), Some(','), Side::Left)?;
+        let lazy_frame = read_csv_as_text(Some(file_path), Some(','))?;
 
         let df = lazy_frame.collect()?; // Coletar para um DataFrame
 
@@ -1312,12 +1446,13 @@ mod tests_read_csv {
 
         assert_eq!(df.height(), 2);
         assert_eq!(df.get_column_names(), &["col1", "col2"]);
+        assert_eq!(df.column("col1")?.dtype(), &DataType::String);
 
         Ok(())
     }
 
     #[test]
-    fn test_read_csv_lazy_schema_modify() -> JoinResult<()> {
+    fn test_get_lazyframe_from_csv_schema_modify() -> JoinResult<()> {
         configure_the_environment();
         let dir = tempdir()?;
         let csv_content = "Linhas,Registro,Valor Total do Item,unknown_csv_col,extra_col_defined_in_map\n\
@@ -1325,7 +1460,7 @@ mod tests_read_csv {
              <N/D>, world ,2.2,u2,false\n\
              30,test,*DIVERSOS*,u3,"; // Note: empty field for extra_col
         let file_path = create_csv(dir.path(), "test_modify.csv", csv_content)?;
-        let lazy_frame = read_csv_lazy(Some(file_path), Some(','), Side::Left)?;
+        let lazy_frame = get_lazyframe_from_csv(Some(file_path), Some(','), Side::Left)?.lazyframe;
         let df_output = lazy_frame.collect()?;
 
         println!("df_output:\n{df_output}");
@@ -1358,13 +1493,23 @@ mod tests_read_csv {
     }
 
     #[test]
-    fn test_read_csv_lazy_empty_fields() -> JoinResult<()> {
+    fn test_get_lazyframe_from_csv_empty_fields() -> JoinResult<()> {
         configure_the_environment();
         let dir = tempdir()?;
-        let csv_content = "Linhas,Registro,Valor Total do Item\n10,,1.1\n,hello,2.2\n30, world";
+        // Linha 5: registro com campos a menos, rejeitado (não completado com nulos).
+        let csv_content =
+            "Linhas,Registro,Valor Total do Item\n10,,1.1\n,hello,2.2\n30, world,\n40, short";
         let file_path = create_csv(dir.path(), "empty_fields.csv", csv_content)?;
-        let lazy_frame = read_csv_lazy(Some(file_path), Some(','), Side::Left)?;
-        let df_output = lazy_frame.collect()?;
+        let leitura = get_lazyframe_from_csv(Some(file_path), Some(','), Side::Left)?;
+        let df_output = leitura.lazyframe.collect()?;
+
+        let df_rejeitados = leitura.rejeitados.collect()?;
+        println!("df_rejeitados:\n{df_rejeitados}");
+        assert_eq!(df_rejeitados.height(), 1);
+        assert_eq!(
+            df_rejeitados.column(COLUNAS_REJEITADOS[1])?.u64()?.get(0),
+            Some(5)
+        );
 
         println!("df_output:\n{df_output}");
 
@@ -1391,9 +1536,9 @@ mod tests_read_csv {
     }
 
     #[test]
-    fn test_read_csv_lazy_error_on_missing_file_path() {
+    fn test_read_csv_as_text_error_on_missing_file_path() {
         // A função retorna JoinResult<LazyFrame>, então o Ok type é LazyFrame
-        let result = read_csv_lazy(None, Some(','), Side::Left);
+        let result = read_csv_as_text(None, Some(','));
 
         assert!(result.is_err());
 
@@ -1416,12 +1561,12 @@ mod tests_read_csv {
     }
 
     #[test]
-    fn test_read_csv_lazy_error_on_missing_delimiter() {
+    fn test_read_csv_as_text_error_on_missing_delimiter() {
         let dir = tempdir().unwrap();
         let file_path = create_csv(dir.path(), "dummy.csv", "a,b\n1,2").unwrap();
         let file_path_clone = file_path.clone();
 
-        let result = read_csv_lazy(Some(file_path), None, Side::Left);
+        let result = read_csv_as_text(Some(file_path), None);
 
         assert!(result.is_err());
 
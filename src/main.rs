@@ -54,21 +54,71 @@ fn main() -> JoinResult<()> {
 fn run() -> JoinResult<()> {
     clear_terminal_screen();
     configure_the_environment();
-    show_sysinfo();
 
     let args: Arguments = Arguments::build()?;
+    // Variáveis de ambiente do Polars: antes de qualquer thread ser criada.
+    configurar_variaveis_do_polars(&args)?;
+
+    show_sysinfo();
     let timer = ExecutionTime::start();
 
-    let df_itens_de_docs_fiscais: DataFrame = get_dataframe_after_assignments(&args)?;
+    configurar_execucao(&args)?;
+
+    println!("Read LazyFrame from CSV files.");
+    let leitura_a: LeituraDoCsv =
+        get_lazyframe_from_csv(args.file1.clone(), args.delimiter_input_1, Side::Left)?;
+    let leitura_b: LeituraDoCsv =
+        get_lazyframe_from_csv(args.file2.clone(), args.delimiter_input_2, Side::Right)?;
+
+    let df_linhas_rejeitadas: DataFrame =
+        obter_linhas_rejeitadas(&[&leitura_a, &leitura_b], &args)?;
+
+    let df_itens_de_docs_fiscais: DataFrame =
+        get_dataframe_after_assignments(leitura_a.lazyframe, leitura_b.lazyframe)?;
 
     let df_consolidacao_natureza_da_bcalc: DataFrame =
         obter_consolidacao_nat(&df_itens_de_docs_fiscais, false)?;
 
-    let df_itens_de_docs_fiscais_result: DataFrame = glosar_bc(&df_itens_de_docs_fiscais, &args)?;
+    let regras_de_glosa: RegrasDeGlosa = RegrasDeGlosa::carregar(&args)?;
+
+    // Operações retiradas de situações de glosa específicas (`--excecoes`)
+    let excecoes_de_glosa: Option<ExcecoesDeGlosa> =
+        ExcecoesDeGlosa::carregar(&args, &regras_de_glosa)?;
+
+    let df_itens_de_docs_fiscais_result: DataFrame = glosar_bc(
+        &df_itens_de_docs_fiscais,
+        &regras_de_glosa,
+        excecoes_de_glosa.as_ref(),
+        &args,
+    )?;
 
     let df_consolidacao_natureza_da_bcalc_result: DataFrame =
         obter_consolidacao_nat(&df_itens_de_docs_fiscais_result, true)?;
 
+    let df_trilha_de_auditoria: DataFrame =
+        obter_trilha_de_auditoria(&df_itens_de_docs_fiscais_result, &regras_de_glosa)?;
+
+    let df_excecoes_aplicadas: DataFrame =
+        obter_excecoes_aplicadas(&df_itens_de_docs_fiscais_result, &regras_de_glosa)?;
+
+    let df_situacao06a: DataFrame = obter_documentos_em_multiplos_periodos_por_chave(
+        &df_itens_de_docs_fiscais,
+        &regras_de_glosa,
+    )?;
+
+    let df_situacao06b: DataFrame = obter_documentos_em_multiplos_periodos_por_cnpj_e_numero(
+        &df_itens_de_docs_fiscais,
+        &regras_de_glosa,
+    )?;
+
+    let df_impacto_das_glosas: DataFrame = obter_impacto_das_glosas(
+        &df_itens_de_docs_fiscais,
+        &df_itens_de_docs_fiscais_result,
+        &regras_de_glosa,
+        excecoes_de_glosa.as_ref(),
+        &args,
+    )?;
+
     // Add column from one dataframe to another.
     let df_joined: DataFrame =
         integrate_and_sort_column(df_itens_de_docs_fiscais, df_itens_de_docs_fiscais_result)?;
@@ -77,42 +127,51 @@ fn run() -> JoinResult<()> {
 
     let df_itens_de_docs_fiscais_result = conditionally_remove_null_columns(df_filtered, &args)?;
 
-    let dataframes: Vec<DataFrame> = [
-        df_itens_de_docs_fiscais_result,
-        df_consolidacao_natureza_da_bcalc,
-        df_consolidacao_natureza_da_bcalc_result,
+    let sheets: Vec<(DataFrame, SheetContext)> = [
+        (df_itens_de_docs_fiscais_result, SheetContext::Itens),
+        (df_trilha_de_auditoria, SheetContext::Trilha),
+        (df_excecoes_aplicadas, SheetContext::Excecoes),
+        (df_consolidacao_natureza_da_bcalc, SheetContext::EfdOriginal),
+        (
+            df_consolidacao_natureza_da_bcalc_result,
+            SheetContext::EfdAuditoria,
+        ),
+        (df_impacto_das_glosas, SheetContext::Impacto),
+        (df_situacao06a, SheetContext::Situacao06a),
+        (df_situacao06b, SheetContext::Situacao06b),
+        (df_linhas_rejeitadas, SheetContext::Rejeitados),
+        (regras_de_glosa.to_dataframe()?, SheetContext::Regras),
     ]
     .into_iter()
-    .map(|mut df| {
+    .map(|(mut df, context)| {
         // Necessário antes de usar PolarsXlsxWriter::new()
         df.rechunk_mut();
-        df
+        (df, context)
     })
     .collect();
 
-    let basenames: [&str; 3] = [
-        "df_itens_de_docs_fiscais_result",
-        "df_consolidacao_natureza_da_bcalc",
-        "df_consolidacao_natureza_da_bcalc_result",
-    ];
-
-    let iterator = dataframes.iter().zip(basenames.iter());
-
     if args.print_parquet == Some(true) {
-        for (dataframe, basename) in iterator.clone() {
-            write_pqt(dataframe, basename)?;
+        for (dataframe, context) in &sheets {
+            write_pqt(dataframe, context.basename())?;
         }
     }
 
     if args.print_csv == Some(true) {
         let delimiter: char = args.delimiter_output.unwrap_or(';');
-        for (dataframe, basename) in iterator {
-            write_csv(dataframe, basename, delimiter)?;
+        for (dataframe, context) in &sheets {
+            write_csv(dataframe, context.basename(), delimiter)?;
         }
     }
 
     if args.print_xlsx == Some(true) {
-        write_xlsx(&dataframes, args.memory_mode)?;
+        write_xlsx(&sheets, args.memory_mode)?;
+    }
+
+    if let Some((df_rejeitados, _)) = sheets
+        .iter()
+        .find(|(_, context)| *context == SheetContext::Rejeitados)
+    {
+        imprimir_resumo_de_rejeitados(df_rejeitados)?;
     }
 
     let dt_local_now: DateTime<Local> = Local::now();
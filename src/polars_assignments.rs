@@ -4,9 +4,8 @@ use polars::prelude::*;
 use crate::{
     AllCorrelations, DataFrameExtension, EXPLODE_OPTIONS, JoinResult, LazyFrameExtension,
     Side::{Left, Middle, Right},
-    args::Arguments,
-    coluna, formatar_ncm_expr, get_lazyframe_from_csv, get_opt_vectuples, get_option_assignments,
-    get_output_as_uint64, retain_only_digits,
+    coluna, formatar_ncm_expr, get_opt_vectuples, get_option_assignments, get_output_as_uint64,
+    retain_only_digits,
 };
 
 /// Use Polars to get dataframe after Munkres assignments
@@ -14,17 +13,17 @@ use crate::{
 /// A `DataFrame` is built upon a `Vec<Series>` where the `Series` have the same length.
 ///
 /// [polars-core-version/src/frame/mod.rs]
-pub fn get_dataframe_after_assignments(args: &Arguments) -> JoinResult<DataFrame> {
+///
+/// Recebe os arquivos CSV já lidos por [`get_lazyframe_from_csv`](crate::get_lazyframe_from_csv).
+pub fn get_dataframe_after_assignments(
+    lazyframe_a: LazyFrame,
+    lazyframe_b: LazyFrame,
+) -> JoinResult<DataFrame> {
     let count_lines_left = coluna(Left, "count_lines");
     let count_lines_right = coluna(Right, "count_lines");
 
-    println!("Read LazyFrame from CSV files.");
-    let lazyframe_a: LazyFrame =
-        get_lazyframe_from_csv(args.file1.clone(), args.delimiter_input_1, Left)?
-            .with_row_index(count_lines_left, Some(0u32));
-    let lazyframe_b: LazyFrame =
-        get_lazyframe_from_csv(args.file2.clone(), args.delimiter_input_2, Right)?
-            .with_row_index(count_lines_right, Some(0u32));
+    let lazyframe_a: LazyFrame = lazyframe_a.with_row_index(count_lines_left, Some(0u32));
+    let lazyframe_b: LazyFrame = lazyframe_b.with_row_index(count_lines_right, Some(0u32));
 
     println!("Format the columns to perform comparisons and sum values.\n");
     let lazyframe_a: LazyFrame = format_fazyframe_a(lazyframe_a)?;
@@ -87,8 +86,8 @@ fn format_fazyframe_a(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
         .with_column(formatar_ncm_expr(ncm))
         .round_float_columns(2);
 
-    // Lazy operations don’t execute until we call .collect()?.
-    Ok(lz.collect()?.lazy())
+    // Reutilizado no agrupamento e na junção final: executado uma única vez.
+    Ok(lz.cache())
 }
 
 /// Formatar colunas a fim de realizar comparações e somas de valores.
@@ -119,8 +118,8 @@ fn format_fazyframe_b(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
         .with_column(formatar_ncm_expr(ncm))
         .round_float_columns(2);
 
-    // Lazy operations don’t execute until we call .collect()?.
-    Ok(lz.collect()?.lazy())
+    // Reutilizado no agrupamento e na junção final: executado uma única vez.
+    Ok(lz.cache())
 }
 
 /// Análise de Chaves (NFe, CTe) ou da união de (CNPJ + Num Doc Fiscal)
@@ -155,7 +154,7 @@ fn groupby_fazyframe_a(lazyframe: LazyFrame) -> PolarsResult<LazyFrame> {
     println!("Group information according to column '{periodo_de_apuracao}' and '{chave}'");
     println!(
         "groupby_fazyframe_a (informações da EFD):\n{}\n",
-        lf_groupby_chave_efd.clone().collect()?
+        lf_groupby_chave_efd.clone().amostra()?
     );
 
     println!(
@@ -176,7 +175,7 @@ fn groupby_fazyframe_a(lazyframe: LazyFrame) -> PolarsResult<LazyFrame> {
                     .alias("Soma dos Valores dos Itens"),
             ])
             .filter(col(period_count).gt(1))
-            .collect()?
+            .amostra()?
     );
 
     Ok(lf_groupby_chave_efd)
@@ -256,7 +255,7 @@ fn get_lazyframe_from_chave_efd(lazyframe: &LazyFrame) -> PolarsResult<LazyFrame
 
     println!(
         "lazyframe_from_chave_efd:\n{}\n",
-        lf_groupby_chave_efd.clone().collect()?
+        lf_groupby_chave_efd.clone().amostra()?
     );
 
     Ok(lf_groupby_chave_efd)
@@ -362,7 +361,7 @@ fn groupby_fazyframe_b(lazyframe: LazyFrame) -> PolarsResult<LazyFrame> {
     println!("Group information according to column '{chave}'");
     println!(
         "groupby_fazyframe_b (informações da RFB):\n{}\n",
-        lf_groupby_chave_nfe.clone().collect()?
+        lf_groupby_chave_nfe.clone().amostra()?
     );
 
     Ok(lf_groupby_chave_nfe)
@@ -393,7 +392,7 @@ fn groupby_fazyframe_b(lazyframe: LazyFrame) -> PolarsResult<LazyFrame> {
 
     println!(
         "lf_groupby_chave_nfe:\n{}\n",
-        lf_groupby_chave_nfe.clone().collect()?
+        lf_groupby_chave_nfe.clone().amostra()?
     );
 
     let chave_len = 44; // chave de 44 dígitos
@@ -462,7 +461,8 @@ fn join_lazyframes(lazyframe_a: LazyFrame, lazyframe_b: LazyFrame) -> PolarsResu
             "Valores dos Itens da Nota Fiscal NFE",
             "Munkres Assignments",
         )?)
-        .collect()?;
+        // O pareamento (Munkres) exige a materialização das chaves agrupadas.
+        .coletar()?;
 
     println!(
         "dataframe_joinned = lazyframe_a.join(lazyframe_b, [...], JoinType::Inner)\n{dataframe}\n"
@@ -791,7 +791,7 @@ fn check_correlation_between_dataframes(lazyframe: LazyFrame) -> PolarsResult<Da
                 .otherwise(lit(NULL))
                 .alias(verificacao),
         )
-        .collect()?;
+        .coletar()?;
 
     Ok(dataframe)
 }
@@ -800,8 +800,8 @@ fn check_correlation_between_dataframes(lazyframe: LazyFrame) -> PolarsResult<Da
 mod test_assignments {
     use super::*;
     use crate::{
-        CorrelatedLines, ExprExtension, LazyFrameExtension, Side, apply_custom_schema_rules,
-        configure_the_environment,
+        CorrelatedLines, ExprExtension, LazyFrameExtension, configure_the_environment,
+        get_lazyframe_from_csv,
     };
     use std::{collections::HashMap, env, path::PathBuf};
 
@@ -1195,7 +1195,17 @@ mod test_assignments {
             .with_missing_is_null(true)
             .with_infer_schema_length(Some(0)) // Infer schema length 0 reads only headers.
             .with_schema_modify(Box::new(move |schema: Schema| {
-                apply_custom_schema_rules(schema, &cols_dtype, Side::Left)
+                // Colunas ausentes do mapa permanecem do tipo String.
+                Ok(schema
+                    .iter_fields()
+                    .map(|field| {
+                        let dtype = cols_dtype.get(field.name().as_str());
+                        Field::new(
+                            field.name().clone(),
+                            dtype.cloned().unwrap_or(DataType::String),
+                        )
+                    })
+                    .collect::<Schema>())
             }))?
             .finish();
 
@@ -1283,6 +1293,7 @@ mod test_assignments {
 
         let lazyframe_b: LazyFrame =
             get_lazyframe_from_csv(Some(file_path.into()), Some(delimiter), Right)?
+                .lazyframe
                 .with_row_index(coluna(Right, "count_lines"), Some(0u32));
 
         let df_b = lazyframe_b.collect()?;
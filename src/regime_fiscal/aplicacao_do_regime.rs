@@ -2,22 +2,47 @@
 //!
 //! Este módulo gerencia a aplicação de regras de enquadramento legal com base no código NCM
 //! (Nomenclatura Comum do Mercosul) e na descrição dos itens da EFD Contribuições e documentos fiscais (NF-e/CT-e).
+//! Cada item é classificado pelas normas vigentes no seu Período de Apuração.
 //!
-//! A análise divide-se em três grandes pilares amparados pela legislação tributária federal:
+//! A análise divide-se em quatro grandes pilares amparados pela legislação tributária federal:
 //! 1. **Alíquota Zero**: Desonerações na venda no mercado interno (ex: Lei nº 10.925/2004 e Lei nº 10.865/2004).
 //! 2. **Crédito Presumido**: Benefícios de compensação para a cadeia do agronegócio (ex: Decreto nº 8.533/2015).
 //! 3. **Incidência Monofásica**: Concentração do tributo no produtor/importador, com desoneração nas etapas seguintes (ex: Lei nº 10.147/2000).
+//! 4. **Suspensão**: Vendas não tributadas nas cadeias do agronegócio, condicionadas à destinação do bem (ex: Lei nº 10.925/2004, Art. 9º).
+//!
+//! A coluna "Zona Franca" não depende do NCM, mas da localização do participante: identifica
+//! operações destinadas à Zona Franca de Manaus ou às Áreas de Livre Comércio (ou delas
+//! originadas), conforme a UF e o município do CTe ou o CFOP de venda destinada a essas áreas.
+//!
+//! A partir de 01/01/2027, os regimes de CBS e IBS (LC nº 214/2025) são informados em colunas
+//! próprias ("CBS/IBS Alíquota Zero", "CBS/IBS Alíquota Reduzida" e "CBS/IBS Crédito Presumido").
+//! As tabelas de PIS/COFINS terminam em 12/2026 e as de CBS/IBS iniciam em 01/2027: em
+//! conjuntos de dados do ano de transição, cada linha é classificada apenas pelo regime
+//! vigente no seu Período de Apuração.
+//!
+//! Além da coluna de texto, cada regime pode ser informado em coluna do tipo `Struct`
+//! ("Alíquota Zero : Base Legal"), com os campos de [`BaseLegal`] (norma, artigo, inciso,
+//! etc.), para filtrar os itens por dispositivo legal (ver [`adicionar_coluna_de_base_legal`]).
 
+use chrono::NaiveDate;
 use polars::prelude::*;
 
 use crate::{
     JoinResult, LazyFrameExtension,
     Side::{Left, Right},
-    coluna, get_output_same_type, operacoes_de_entrada_ou_saida,
+    coluna, get_output_as_string, operacoes_de_entrada_ou_saida,
     regime_fiscal::{
+        BaseLegal,
         legislacao_aliquota_zero::base_legal_de_aliquota_zero,
+        legislacao_cbs_ibs_aliquota_reduzida::base_legal_de_cbs_ibs_aliquota_reduzida,
+        legislacao_cbs_ibs_aliquota_zero::base_legal_de_cbs_ibs_aliquota_zero,
+        legislacao_cbs_ibs_credito_presumido::base_legal_de_cbs_ibs_credito_presumido,
         legislacao_credito_presumido::base_legal_de_credito_presumido,
         legislacao_incidencia_monofasica::base_legal_de_incidencia_monofasica,
+        legislacao_suspensao::base_legal_de_suspensao,
+        legislacao_zona_franca::{
+            base_legal_de_zona_franca, base_legal_do_cfop_de_zona_franca, uf_da_chave,
+        },
     },
 };
 
@@ -30,6 +55,14 @@ pub enum RegimesFiscais {
     CreditoPresumido,
     /// Regime de Incidência Monofásica (concentração tributária na produção/importação).
     IncidenciaMonofasica,
+    /// Regime de Suspensão (vendas não tributadas conforme a destinação do bem).
+    Suspensao,
+    /// CBS e IBS reduzidos a zero (Anexos I e XV da LC nº 214/2025), a partir de 2027.
+    CbsIbsAliquotaZero,
+    /// CBS e IBS reduzidos em 60% (Anexos VII, VIII e IX da LC nº 214/2025), a partir de 2027.
+    CbsIbsAliquotaReduzida,
+    /// Crédito presumido de CBS e IBS (Art. 168 da LC nº 214/2025), a partir de 2027.
+    CbsIbsCreditoPresumido,
 }
 
 impl RegimesFiscais {
@@ -39,34 +72,64 @@ impl RegimesFiscais {
             Self::AliquotaZero => "Alíquota Zero",
             Self::CreditoPresumido => "Crédito Presumido",
             Self::IncidenciaMonofasica => "Incidência Monofásica",
+            Self::Suspensao => "Suspensão",
+            Self::CbsIbsAliquotaZero => "CBS/IBS Alíquota Zero",
+            Self::CbsIbsAliquotaReduzida => "CBS/IBS Alíquota Reduzida",
+            Self::CbsIbsCreditoPresumido => "CBS/IBS Crédito Presumido",
         }
     }
 
+    /// Retorna o nome da coluna estruturada (`Struct`) de base legal do regime.
+    ///
+    /// Exemplo: "Alíquota Zero : Base Legal".
+    pub fn get_base_legal_column_name(&self) -> String {
+        format!("{} : Base Legal", self.get_column_name())
+    }
+
     /// Associa o regime fiscal à sua respectiva função de validação de base legal.
     ///
-    /// Cada função avalia o enquadramento com base na tupla `(NCM, Descrição)`.
+    /// Cada função avalia o enquadramento com base na tupla `(NCM, Descrição, Data)`.
     pub fn get_base_legal_fn(
         &self,
-    ) -> impl Fn(u64, &str) -> Option<&'static str> + Send + Sync + 'static {
+    ) -> impl Fn(u64, &str, Option<NaiveDate>) -> Option<&'static BaseLegal> + Send + Sync + 'static
+    {
         match self {
             Self::AliquotaZero => base_legal_de_aliquota_zero,
             Self::CreditoPresumido => base_legal_de_credito_presumido,
             Self::IncidenciaMonofasica => base_legal_de_incidencia_monofasica,
+            Self::Suspensao => base_legal_de_suspensao,
+            Self::CbsIbsAliquotaZero => base_legal_de_cbs_ibs_aliquota_zero,
+            Self::CbsIbsAliquotaReduzida => base_legal_de_cbs_ibs_aliquota_reduzida,
+            Self::CbsIbsCreditoPresumido => base_legal_de_cbs_ibs_credito_presumido,
         }
     }
 
     /// Cria uma expressão Polars encapsulada para mapear e aplicar as validações do regime fiscal de forma segura.
     ///
     /// Reduz a duplicação de lógica (DRY) ao criar uma estrutura de dados `Struct` contendo
-    /// a coluna de NCM convertida em String e a descrição física do item.
-    pub fn make_eval_expr(&self, ncm_col: &str, desc_col: &str, alias: &'static str) -> Expr {
+    /// a coluna de NCM convertida em String, a descrição física do item e a data
+    /// (Período de Apuração) que determina as normas vigentes.
+    ///
+    /// O resultado é uma coluna `Struct` com os campos de [`BaseLegal`]
+    /// (ver [`campos_da_base_legal`]).
+    pub fn make_eval_expr(
+        &self,
+        ncm_col: &str,
+        desc_col: &str,
+        data: Expr,
+        alias: &'static str,
+    ) -> Expr {
         let regime = *self;
-        as_struct(vec![col(ncm_col).cast(DataType::String), col(desc_col)])
-            .map(
-                move |col: Column| aplicar_regime_fiscal(&col, regime),
-                get_output_same_type,
-            )
-            .alias(alias)
+        as_struct(vec![
+            col(ncm_col).cast(DataType::String),
+            col(desc_col),
+            data.cast(DataType::Date),
+        ])
+        .map(
+            move |col: Column| aplicar_regime_fiscal(&col, regime),
+            get_output_as_base_legal,
+        )
+        .alias(alias)
     }
 }
 
@@ -94,6 +157,116 @@ pub fn adicionar_coluna_de_incidencia_monofasica(lazyframe: LazyFrame) -> JoinRe
     adicionar_coluna_de_regime_fiscal(lazyframe, RegimesFiscais::IncidenciaMonofasica)
 }
 
+/// Adiciona a coluna analítica de "Suspensão" ao `LazyFrame`.
+///
+/// Identifica bens vendidos com suspensão de PIS/COFINS nas cadeias do agronegócio
+/// (Lei nº 10.925/2004, Art. 9º; Lei nº 12.058/2009, Art. 32; Lei nº 12.350/2010, Art. 54).
+pub fn adicionar_coluna_de_suspensao(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
+    adicionar_coluna_de_regime_fiscal(lazyframe, RegimesFiscais::Suspensao)
+}
+
+/// Adiciona a coluna analítica de "CBS/IBS Alíquota Zero" ao `LazyFrame`.
+///
+/// Identifica os produtos da Cesta Básica Nacional de Alimentos (Anexo I) e os produtos
+/// hortícolas, frutas e ovos (Anexo XV da LC nº 214/2025), a partir de 01/01/2027.
+pub fn adicionar_coluna_de_cbs_ibs_aliquota_zero(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
+    adicionar_coluna_de_regime_fiscal(lazyframe, RegimesFiscais::CbsIbsAliquotaZero)
+}
+
+/// Adiciona a coluna analítica de "CBS/IBS Alíquota Reduzida" ao `LazyFrame`.
+///
+/// Identifica os bens com alíquotas reduzidas em 60% (alimentos, higiene pessoal,
+/// medicamentos e insumos agropecuários), a partir de 01/01/2027.
+pub fn adicionar_coluna_de_cbs_ibs_aliquota_reduzida(
+    lazyframe: LazyFrame,
+) -> JoinResult<LazyFrame> {
+    adicionar_coluna_de_regime_fiscal(lazyframe, RegimesFiscais::CbsIbsAliquotaReduzida)
+}
+
+/// Adiciona a coluna analítica de "CBS/IBS Crédito Presumido" ao `LazyFrame`.
+///
+/// Identifica os bens adquiridos de produtor rural não contribuinte que podem gerar
+/// crédito presumido (Art. 168 da LC nº 214/2025), a partir de 01/01/2027.
+pub fn adicionar_coluna_de_cbs_ibs_credito_presumido(
+    lazyframe: LazyFrame,
+) -> JoinResult<LazyFrame> {
+    adicionar_coluna_de_regime_fiscal(lazyframe, RegimesFiscais::CbsIbsCreditoPresumido)
+}
+
+/// Adiciona a coluna estruturada de base legal do regime fiscal ao `LazyFrame`.
+///
+/// A coluna "{Regime} : Base Legal" é do tipo `Struct`, com os campos de [`BaseLegal`]:
+/// regime, norma, artigo, inciso, anexo, descricao, rule_id, matched_by e enunciado.
+/// Não é incluída nos relatórios CSV e Excel, que não suportam colunas do tipo `Struct`.
+///
+/// Exemplo de filtro: `col("Alíquota Zero : Base Legal").struct_().field_by_name("norma")`.
+pub fn adicionar_coluna_de_base_legal(
+    lazyframe: LazyFrame,
+    regime_fiscal: RegimesFiscais,
+) -> JoinResult<LazyFrame> {
+    adicionar_enquadramento(
+        lazyframe,
+        regime_fiscal,
+        &regime_fiscal.get_base_legal_column_name(),
+        |temp_col, _ncm_col| col(temp_col),
+    )
+}
+
+/// Adiciona a coluna analítica de "Zona Franca" ao `LazyFrame`.
+///
+/// Identifica operações com participante localizado na Zona Franca de Manaus ou em Área de
+/// Livre Comércio (Lei nº 10.996/2004, Art. 2º; Lei nº 11.945/2009), nesta ordem:
+/// 1. Destino: UF e município do término da prestação do CTe, originada fora da área;
+/// 2. CFOP de venda destinada à ZFM ou ALC (5109, 5110, 6109 e 6110), do documento fiscal
+///    ou, na sua falta, da EFD;
+/// 3. Origem: UF e município do início da prestação do CTe ou, na sua falta, município do
+///    remetente e UF da chave da NF-e transportada.
+///
+/// Exemplo: 'Destino Manaus/AM : Zona Franca de Manaus - Lei nº 10.996/2004, Art. 2º (...).'
+pub fn adicionar_coluna_de_zona_franca(lazyframe: LazyFrame) -> JoinResult<LazyFrame> {
+    let schema = lazyframe.clone().collect_schema()?;
+
+    // Colunas ausentes (ex.: conjuntos de dados sem CTe) são tratadas como nulas.
+    let campo = |nome: &str, dtype: DataType| -> Expr {
+        let expr: Expr = if schema.contains(nome) {
+            col(nome)
+        } else {
+            lit(NULL)
+        };
+        expr.cast(dtype).alias(nome)
+    };
+
+    let cfop_b: &str = coluna(Right, "cfop"); // "Código CFOP : NF Item (Todos)"
+    let cfop_a: &str = coluna(Left, "cfop"); // "Código Fiscal de Operações e Prestações (CFOP)"
+
+    let campos: Vec<Expr> = vec![
+        campo(coluna(Right, "termino_estado"), DataType::String),
+        campo(coluna(Right, "termino_municipio"), DataType::String),
+        campo(coluna(Right, "inicio_estado"), DataType::String),
+        campo(coluna(Right, "inicio_municipio"), DataType::String),
+        campo(coluna(Right, "chave_de_acesso"), DataType::String),
+        campo(coluna(Right, "remetente_municipio"), DataType::String),
+        campo(cfop_b, DataType::Int64).fill_null(campo(cfop_a, DataType::Int64)),
+        // Normas vigentes no Período de Apuração
+        campo(coluna(Left, "pa"), DataType::Date),
+    ];
+
+    let zona_franca: Expr = as_struct(campos).map(
+        |col: Column| aplicar_zona_franca(&col),
+        get_output_as_string,
+    );
+
+    let lazyframe = lazyframe.with_column(
+        when(operacoes_de_entrada_ou_saida()?)
+            .then(zona_franca)
+            .otherwise(lit(NULL))
+            .cast(DataType::String)
+            .alias("Zona Franca"),
+    );
+
+    Ok(lazyframe)
+}
+
 /**
 Esta função adiciona uma nova coluna ao LazyFrame com informações sobre a
 incidência das Contribuições de acordo com o regime fiscal específico.
@@ -101,7 +274,8 @@ incidência das Contribuições de acordo com o regime fiscal específico.
 As informações adicionadas na nova coluna sobre a incidência das Contribuições
 são obtidas conforme código NCM e descrição dos itens.
 
-Ou seja, a legislação adicionada é resultado da função: fn(NCM, Descrição dos Itens).
+Ou seja, a legislação adicionada é resultado da função: fn(NCM, Descrição dos Itens, Data),
+em que a data é o Período de Apuração (se ausente, todas as normas são consideradas).
 
  * `lazyframe`: The input LazyFrame.
  * `regime_fiscal`: The fiscal regime to be analyzed (e.g., AliquotaZero).
@@ -110,8 +284,31 @@ fn adicionar_coluna_de_regime_fiscal(
     lazyframe: LazyFrame,
     regime_fiscal: RegimesFiscais,
 ) -> JoinResult<LazyFrame> {
-    let output_col_name = regime_fiscal.get_column_name();
+    // Exemplo: 'NCM 2207.10.90 : Alíquota Zero - Lei xxx.'
+    let texto = |temp_col: &str, ncm_col: &str| -> Expr {
+        concat_str(
+            [lit("NCM"), col(ncm_col), lit(":"), enunciado(temp_col)],
+            " ",
+            true,
+        )
+    };
+
+    adicionar_enquadramento(
+        lazyframe,
+        regime_fiscal,
+        regime_fiscal.get_column_name(),
+        texto,
+    )
+}
 
+/// Enquadra os itens da EFD (A) e dos documentos fiscais (B) no regime fiscal e adiciona
+/// a coluna `output_col_name`, obtida do enquadramento pela função `valor(temp_col, ncm_col)`.
+fn adicionar_enquadramento(
+    lazyframe: LazyFrame,
+    regime_fiscal: RegimesFiscais,
+    output_col_name: &str,
+    valor: impl Fn(&str, &str) -> Expr,
+) -> JoinResult<LazyFrame> {
     let ncm_col_a: &str = coluna(Left, "ncm"); // "Código NCM";
     let desc_col_a: &str = coluna(Left, "item_desc"); // "Descrição do Item";
     let temp_col_a: &str = "Coluna Temporária A";
@@ -120,35 +317,35 @@ fn adicionar_coluna_de_regime_fiscal(
     let desc_col_b: &str = coluna(Right, "descricao_mercadoria"); // "Descrição da Mercadoria/Serviço : NF Item (Todos)";
     let temp_col_b: &str = "Coluna Temporária B";
 
+    // Normas vigentes no Período de Apuração
+    let periodo_de_apuracao: &str = coluna(Left, "pa"); // "Período de Apuração";
+    let data: Expr = if lazyframe
+        .clone()
+        .collect_schema()?
+        .contains(periodo_de_apuracao)
+    {
+        col(periodo_de_apuracao)
+    } else {
+        lit(NULL)
+    };
+
     // Combine null check with the entry/exit operation condition
     // Unifica o filtro de validação para as colunas temporárias geradas
-    let boolean_a: Expr = operacoes_de_entrada_ou_saida()?.and(col(temp_col_a).is_not_null());
-    let boolean_b: Expr = operacoes_de_entrada_ou_saida()?.and(col(temp_col_b).is_not_null());
-
-    // Exemplo: 'NCM 2207.10.90 : Alíquota Zero - Lei xxx.'
-    let exp_a: Expr = concat_str(
-        [lit("NCM"), col(ncm_col_a), lit(":"), col(temp_col_a)],
-        " ",
-        true,
-    );
-    let exp_b: Expr = concat_str(
-        [lit("NCM"), col(ncm_col_b), lit(":"), col(temp_col_b)],
-        " ",
-        true,
-    );
+    let boolean_a: Expr = operacoes_de_entrada_ou_saida()?.and(enunciado(temp_col_a).is_not_null());
+    let boolean_b: Expr = operacoes_de_entrada_ou_saida()?.and(enunciado(temp_col_b).is_not_null());
 
     let lazyframe = lazyframe
         // Adicionar 2 colunas temporárias
         .with_columns([
-            regime_fiscal.make_eval_expr(ncm_col_a, desc_col_a, temp_col_a),
-            regime_fiscal.make_eval_expr(ncm_col_b, desc_col_b, temp_col_b),
+            regime_fiscal.make_eval_expr(ncm_col_a, desc_col_a, data.clone(), temp_col_a),
+            regime_fiscal.make_eval_expr(ncm_col_b, desc_col_b, data, temp_col_b),
         ])
         .with_column(
             // Adicionar 1 coluna que concentra as informações das 2 colunas temporárias
             when(boolean_a)
-                .then(exp_a)
+                .then(valor(temp_col_a, ncm_col_a))
                 .when(boolean_b)
-                .then(exp_b)
+                .then(valor(temp_col_b, ncm_col_b))
                 .otherwise(lit(NULL))
                 .alias(output_col_name),
         )
@@ -158,7 +355,37 @@ fn adicionar_coluna_de_regime_fiscal(
     Ok(lazyframe)
 }
 
-/// Avalia uma coluna estruturada do Polars composta por NCM e descrição física.
+/// Texto da base legal (campo "enunciado") da coluna estruturada de enquadramento.
+fn enunciado(struct_col: &str) -> Expr {
+    col(struct_col).struct_().field_by_name("enunciado")
+}
+
+/// Campos da coluna estruturada de base legal, na ordem de [`BaseLegal`].
+pub fn campos_da_base_legal() -> Vec<Field> {
+    let campo =
+        |nome: &'static str, dtype: DataType| Field::new(PlSmallStr::from_static(nome), dtype);
+
+    vec![
+        campo("regime", DataType::String),
+        campo("norma", DataType::String),
+        campo("artigo", DataType::String),
+        campo("inciso", DataType::String),
+        campo("anexo", DataType::String),
+        campo("descricao", DataType::String),
+        campo("rule_id", DataType::UInt32),
+        campo("matched_by", DataType::String),
+        campo("enunciado", DataType::String),
+    ]
+}
+
+fn get_output_as_base_legal(_: &Schema, field: &Field) -> PolarsResult<Field> {
+    Ok(Field::new(
+        field.name().clone(),
+        DataType::Struct(campos_da_base_legal()),
+    ))
+}
+
+/// Avalia uma coluna estruturada do Polars composta por NCM, descrição física e data.
 ///
 /// Trata os tipos internos da coluna `StructColumn` de forma segura, prevenindo
 /// erros de indexação ou falhas em linhas com esquemas ou valores ausentes.
@@ -182,16 +409,23 @@ fn aplicar_regime_fiscal(col: &Column, regime_fiscal: RegimesFiscais) -> PolarsR
     let ca_str_ncm = ser_codigoncm.str()?;
     let ca_str_dsc = ser_descricao.str()?;
 
+    // Data (optional third field): selects the rules in force on the period.
+    let datas: Vec<Option<NaiveDate>> = match fields.get(2) {
+        Some(ser_data) => ser_data.date()?.as_date_iter().collect(),
+        None => vec![None; ca_str_ncm.len()],
+    };
+
     // Retrieve the specific legal basis function for the given fiscal regime.
     let base_legal = regime_fiscal.get_base_legal_fn(); // Get the specific function here
 
     // Iterate over NCM and description, apply base_legal function
-    let new_col: Column = ca_str_ncm
+    let enquadramentos: Vec<Option<&BaseLegal>> = ca_str_ncm
         .iter()
         .zip(ca_str_dsc.iter())
-        .map(|(opt_ncm, opt_desc)| match (opt_ncm, opt_desc) {
+        .zip(datas)
+        .map(|((opt_ncm, opt_desc), data)| match (opt_ncm, opt_desc) {
             (Some(ncm_str), Some(desc_str)) => match parse_ncm_to_u64(ncm_str) {
-                Some(ncm) => base_legal(ncm, desc_str),
+                Some(ncm) => base_legal(ncm, desc_str, data),
                 None => {
                     eprintln!("Warning: Failed to extract digits from NCM '{}'", ncm_str);
                     None
@@ -199,6 +433,115 @@ fn aplicar_regime_fiscal(col: &Column, regime_fiscal: RegimesFiscais) -> PolarsR
             },
             _ => None, // Handle missing NCM or description by returning None
         })
+        .collect();
+
+    base_legal_to_column(col.name().clone(), &enquadramentos)
+}
+
+/// Converte os enquadramentos em coluna do tipo `Struct` (ver [`campos_da_base_legal`]).
+///
+/// Itens sem enquadramento têm todos os campos nulos.
+fn base_legal_to_column(
+    nome: PlSmallStr,
+    enquadramentos: &[Option<&BaseLegal>],
+) -> PolarsResult<Column> {
+    let texto = |campo: fn(&BaseLegal) -> Option<&str>| -> Column {
+        enquadramentos
+            .iter()
+            .copied()
+            .map(|base_legal| base_legal.and_then(campo))
+            .collect::<StringChunked>()
+            .into_column()
+    };
+
+    let rule_id: Column = enquadramentos
+        .iter()
+        .map(|base_legal| base_legal.map(|base_legal| base_legal.rule_id))
+        .collect::<UInt32Chunked>()
+        .into_column();
+
+    let valores: Vec<Column> = vec![
+        texto(|base_legal| Some(base_legal.regime.get_column_name())),
+        texto(|base_legal| Some(base_legal.norma.as_str())),
+        texto(|base_legal| base_legal.artigo.as_deref()),
+        texto(|base_legal| base_legal.inciso.as_deref()),
+        texto(|base_legal| base_legal.anexo.as_deref()),
+        texto(|base_legal| base_legal.descricao.as_deref()),
+        rule_id,
+        texto(|base_legal| Some(base_legal.matched_by.as_str())),
+        texto(|base_legal| Some(base_legal.enunciado.as_str())),
+    ];
+
+    let colunas: Vec<Column> = campos_da_base_legal()
+        .into_iter()
+        .zip(valores)
+        .map(|(campo, valor)| valor.with_name(campo.name().clone()))
+        .collect();
+
+    let dataframe = DataFrame::new(enquadramentos.len(), colunas)?;
+
+    Ok(dataframe.into_struct(nome).into_column())
+}
+
+/// Avalia a coluna estruturada de localização (CTe), CFOP e data.
+///
+/// Campos: UF e município do término, UF e município do início, chave da NF-e
+/// transportada, município do remetente, CFOP e Período de Apuração.
+fn aplicar_zona_franca(col: &Column) -> PolarsResult<Column> {
+    let struct_chunked: &StructChunked = col.struct_()?;
+    let fields = struct_chunked.fields_as_series();
+
+    if fields.len() < 8 {
+        return Err(PolarsError::ComputeError(
+            "Coluna do tipo Struct necessita de 8 campos (localização, CFOP e data)".into(),
+        ));
+    }
+
+    let termino_uf = fields[0].str()?;
+    let termino_municipio = fields[1].str()?;
+    let inicio_uf = fields[2].str()?;
+    let inicio_municipio = fields[3].str()?;
+    let chave = fields[4].str()?;
+    let remetente_municipio = fields[5].str()?;
+    let cfops = fields[6].i64()?;
+    let datas: Vec<Option<NaiveDate>> = fields[7].date()?.as_date_iter().collect();
+
+    let new_col: Column = datas
+        .into_iter()
+        .enumerate()
+        .map(|(i, data)| {
+            // Exemplo: ("Manaus/AM", "Zona Franca de Manaus - Lei nº 10.996/2004, Art. 2º (...).")
+            let local = |uf: Option<&str>, municipio: Option<&str>| {
+                let (uf, municipio) = (uf?, municipio?);
+                base_legal_de_zona_franca(uf, municipio, data)
+                    .map(|base_legal| (format!("{}/{}", municipio.trim(), uf.trim()), base_legal))
+            };
+
+            let destino = local(termino_uf.get(i), termino_municipio.get(i));
+            let origem = local(inicio_uf.get(i), inicio_municipio.get(i)).or_else(|| {
+                local(
+                    chave.get(i).and_then(uf_da_chave),
+                    remetente_municipio.get(i),
+                )
+            });
+            let cfop = cfops.get(i).and_then(|cfop| {
+                base_legal_do_cfop_de_zona_franca(cfop, data).map(|base_legal| (cfop, base_legal))
+            });
+
+            match (destino, cfop, origem) {
+                // Prestação iniciada fora da área especial de destino
+                (Some((local, base_legal)), _, origem)
+                    if origem
+                        .as_ref()
+                        .is_none_or(|(_, outra)| *outra != base_legal) =>
+                {
+                    Some(format!("Destino {local} : {base_legal}"))
+                }
+                (_, Some((cfop, base_legal)), _) => Some(format!("CFOP {cfop} : {base_legal}")),
+                (_, _, Some((local, base_legal))) => Some(format!("Origem {local} : {base_legal}")),
+                _ => None,
+            }
+        })
         .collect::<StringChunked>()
         .into_column();
 
@@ -398,6 +741,119 @@ mod regime_fiscal_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_adicionar_coluna_de_suspensao() -> JoinResult<()> {
+        let df = create_test_dataframe()?;
+        let result_lf = adicionar_coluna_de_suspensao(df.lazy());
+        let df = result_lf?.collect()?;
+
+        println!("df: {df}");
+
+        let suspensao_col = df.column("Suspensão")?.str()?;
+
+        assert_eq!(
+            suspensao_col.get(5),
+            Some(
+                "NCM 1030000 : Suspensão - Lei nº 12.350/2010, Art. 54, Inciso III (Animais Vivos das Posições 01.03 e 01.05: Suínos e Aves)."
+            )
+        );
+        assert!(suspensao_col.get(1).is_some()); // Soja: cerealista
+        assert_eq!(suspensao_col.get(0), None);
+        assert_eq!(suspensao_col.get(2), None);
+        assert_eq!(suspensao_col.get(3), None);
+        assert_eq!(suspensao_col.get(4), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_adicionar_coluna_de_base_legal() -> JoinResult<()> {
+        let df = create_test_dataframe()?;
+        let regime = RegimesFiscais::AliquotaZero;
+
+        let df = adicionar_coluna_de_base_legal(df.lazy(), regime)?.collect()?;
+        println!("df: {df}");
+
+        // A coluna estruturada não cria a coluna de texto do regime
+        assert!(df.column("Alíquota Zero").is_err());
+
+        let base_legal = df
+            .column(&regime.get_base_legal_column_name())?
+            .struct_()?
+            .clone();
+        let campo = |nome: &str| -> JoinResult<Vec<Option<String>>> {
+            Ok(base_legal
+                .field_by_name(nome)?
+                .cast(&DataType::String)?
+                .str()?
+                .iter()
+                .map(|valor| valor.map(str::to_string))
+                .collect())
+        };
+        let texto = |valor: &str| Some(valor.to_string());
+
+        assert_eq!(
+            campo("regime")?[..2],
+            [texto("Alíquota Zero"), texto("Alíquota Zero")]
+        );
+        assert_eq!(campo("norma")?[0], texto("Lei nº 10.925/2004"));
+        assert_eq!(campo("artigo")?[0], texto("Art. 1º"));
+        assert_eq!(
+            campo("inciso")?[..2],
+            [texto("Inciso I"), texto("Inciso III")]
+        );
+        assert_eq!(
+            campo("descricao")?[0],
+            texto("Adubos ou Fertilizantes do Capítulo 31 e suas Matérias-Primas")
+        );
+        assert_eq!(campo("anexo")?[0], None);
+        assert_eq!(campo("matched_by")?[0], texto("NCM"));
+        assert!(campo("rule_id")?[0].is_some());
+
+        // Itens sem enquadramento
+        for nome in ["norma", "rule_id", "enunciado"] {
+            assert!(campo(nome)?[2..].iter().all(Option::is_none));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_adicionar_coluna_de_zona_franca() -> JoinResult<()> {
+        let df = df! {
+            "Tipo de Operação" => [1, 1, 2, 1, 1, 1],
+            "Código Fiscal de Operações e Prestações (CFOP)" => [Some(1352), None, Some(6110), Some(1102), None, Some(1352)],
+            "Código CFOP : NF Item (Todos)" => [Some(6352), Some(6109), None, Some(6102), None, Some(6352)],
+            "CTe - UF do início da prestação de Conhecimento : ConhecimentoValoresPrestacaoServico-Componentes" => [Some("SP"), None, None, None, Some("AM"), Some("AM")],
+            "CTe - Nome do Município do início da prestação de Conhecimento : ConhecimentoValoresPrestacaoServico-Componentes" => [Some("São Paulo"), None, None, None, Some("MANAUS"), Some("Manaus")],
+            "CTe - UF do término da prestação de Conhecimento : ConhecimentoValoresPrestacaoServico-Componentes" => [Some("AM"), None, None, None, Some("SP"), Some("AM")],
+            "CTe - Nome do Município do término da prestação de Conhecimento : ConhecimentoValoresPrestacaoServico-Componentes" => [Some("Manaus"), None, None, None, Some("Campinas"), Some("Rio Preto da Eva")],
+        }?;
+
+        let df = adicionar_coluna_de_zona_franca(df.lazy())?.collect()?;
+        println!("df: {df}");
+
+        let zona_franca: Vec<Option<&str>> = df.column("Zona Franca")?.str()?.iter().collect();
+        let inicia_com = |linha: usize, prefixo: &str| {
+            zona_franca[linha].is_some_and(|texto| texto.starts_with(prefixo))
+        };
+
+        assert_eq!(
+            zona_franca[0],
+            Some(
+                "Destino Manaus/AM : Zona Franca de Manaus - Lei nº 10.996/2004, Art. 2º (Alíquota Zero nas Vendas Destinadas ao Consumo ou à Industrialização na ZFM)."
+            )
+        );
+        assert!(inicia_com(1, "CFOP 6109 : ")); // CFOP da NF-e
+        assert!(inicia_com(2, "CFOP 6110 : ")); // CFOP da EFD
+        assert_eq!(zona_franca[3], None);
+        assert!(inicia_com(4, "Origem MANAUS/AM : "));
+        // Prestação interna à ZFM: não caracteriza venda destinada à área especial
+        assert!(inicia_com(5, "Origem Manaus/AM : "));
+
+        Ok(())
+    }
+
     #[test]
     fn test_adicionar_coluna_no_empty_frame() -> JoinResult<()> {
         let df_empty = df! {
@@ -480,4 +936,78 @@ mod regime_fiscal_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_normas_vigentes_no_periodo_de_apuracao() -> JoinResult<()> {
+        let data = |ano: i32, mes: u32| NaiveDate::from_ymd_opt(ano, mes, 1);
+
+        // Carne bovina: Alíquota Zero a partir da Medida Provisória nº 609/2013 e até 2026.
+        let df = df! {
+            "Período de Apuração" => [data(2012, 12), data(2013, 3), data(2027, 1), None],
+            "Código NCM" => ["0201.30.00"; 4],
+            "Descrição do Item" => ["Carne bovina"; 4],
+            "Código NCM : NF Item (Todos)" => ["02013000"; 4],
+            "Descrição da Mercadoria/Serviço : NF Item (Todos)" => ["Carne bovina"; 4],
+            "Tipo de Operação" => [1; 4],
+        }?;
+
+        let df = adicionar_coluna_de_aliquota_zero(df.lazy())?.collect()?;
+        println!("df: {df}");
+
+        let aliquota_zero: Vec<bool> = df
+            .column("Alíquota Zero")?
+            .str()?
+            .iter()
+            .map(|base_legal| base_legal.is_some())
+            .collect();
+
+        assert_eq!(aliquota_zero, [false, true, false, true]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ano_de_transicao_para_cbs_ibs() -> JoinResult<()> {
+        let data = |ano: i32, mes: u32| NaiveDate::from_ymd_opt(ano, mes, 1);
+
+        // Arroz: Alíquota Zero de PIS/COFINS até 12/2026 e de CBS/IBS a partir de 01/2027.
+        let df = df! {
+            "Período de Apuração" => [data(2026, 11), data(2026, 12), data(2027, 1), data(2027, 2)],
+            "Código NCM" => ["1006.30.21"; 4],
+            "Descrição do Item" => ["Arroz Branco"; 4],
+            "Código NCM : NF Item (Todos)" => ["10063021"; 4],
+            "Descrição da Mercadoria/Serviço : NF Item (Todos)" => ["Arroz Branco"; 4],
+            "Tipo de Operação" => [1; 4],
+        }?;
+
+        let lazyframe = adicionar_coluna_de_aliquota_zero(df.lazy())?;
+        let lazyframe = adicionar_coluna_de_cbs_ibs_aliquota_zero(lazyframe)?;
+        let lazyframe = adicionar_coluna_de_cbs_ibs_aliquota_reduzida(lazyframe)?;
+        let df = adicionar_coluna_de_cbs_ibs_credito_presumido(lazyframe)?.collect()?;
+        println!("df: {df}");
+
+        let preenchida = |nome: &str| -> JoinResult<Vec<bool>> {
+            Ok(df
+                .column(nome)?
+                .str()?
+                .iter()
+                .map(|base_legal| base_legal.is_some())
+                .collect())
+        };
+
+        assert_eq!(preenchida("Alíquota Zero")?, [true, true, false, false]);
+        assert_eq!(
+            preenchida("CBS/IBS Alíquota Zero")?,
+            [false, false, true, true]
+        );
+        assert_eq!(preenchida("CBS/IBS Alíquota Reduzida")?, [false; 4]);
+
+        // Cereais (Capítulo 10) adquiridos de produtor rural não contribuinte.
+        assert_eq!(
+            preenchida("CBS/IBS Crédito Presumido")?,
+            [false, false, true, true]
+        );
+
+        Ok(())
+    }
 }
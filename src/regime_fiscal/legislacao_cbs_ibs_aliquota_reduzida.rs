@@ -0,0 +1,105 @@
+//! Regras de Alíquota Reduzida de CBS e IBS (Lei Complementar nº 214/2025).
+//!
+//! Ficam reduzidas em 60% as alíquotas da CBS e do IBS, a partir de 01/01/2027, de:
+//! - Anexo VII: alimentos destinados ao consumo humano
+//! - Anexo VIII: produtos de higiene pessoal e limpeza
+//! - Medicamentos registrados na Anvisa
+//! - Anexo IX: insumos agropecuários e aquícolas
+//!
+//! Ao contrário da Alíquota Zero, a redução não afasta o crédito do adquirente: a coluna
+//! é informativa e não caracteriza, por si só, Situação de glosa.
+//!
+//! As faixas de NCM, as descrições e as vigências constam de
+//! `legislacao_cbs_ibs_aliquota_reduzida.toml` (ver [`TabelaDeLegislacao`]).
+
+use chrono::NaiveDate;
+use std::sync::LazyLock;
+
+use crate::regime_fiscal::{BaseLegal, RegimesFiscais, TabelaDeLegislacao};
+
+/// Tabela de Alíquota Reduzida de CBS e IBS incorporada ao executável.
+pub const LEGISLACAO_CBS_IBS_ALIQUOTA_REDUZIDA: &str =
+    include_str!("legislacao_cbs_ibs_aliquota_reduzida.toml");
+
+static TABELA: LazyLock<TabelaDeLegislacao> = LazyLock::new(|| {
+    TabelaDeLegislacao::from_toml(
+        LEGISLACAO_CBS_IBS_ALIQUOTA_REDUZIDA,
+        RegimesFiscais::CbsIbsAliquotaReduzida,
+    )
+    .unwrap_or_else(|erro| panic!("legislacao_cbs_ibs_aliquota_reduzida.toml: {erro}"))
+});
+
+/// Base Legal conforme código NCM, descrição do item e data (`None`: todas as normas).
+pub fn base_legal_de_cbs_ibs_aliquota_reduzida(
+    codigo_ncm: u64,
+    descricao: &str,
+    data: Option<NaiveDate>,
+) -> Option<&'static BaseLegal> {
+    TABELA.base_legal(codigo_ncm, descricao, data)
+}
+
+// ----------------------------------------------------------------------------
+// TESTS
+// ----------------------------------------------------------------------------
+
+/// Run tests with:
+///
+/// `cargo test -- --show-output tests_cbs_ibs_aliquota_reduzida`
+#[cfg(test)]
+mod tests_cbs_ibs_aliquota_reduzida {
+    use super::*;
+
+    #[test]
+    fn test_alimentos_e_higiene() {
+        assert!(base_legal_de_cbs_ibs_aliquota_reduzida(3061700, "Camarão", None).is_some());
+        assert!(base_legal_de_cbs_ibs_aliquota_reduzida(4090000, "Mel", None).is_some());
+        assert!(base_legal_de_cbs_ibs_aliquota_reduzida(33061000, "Creme Dental", None).is_some());
+        assert!(
+            base_legal_de_cbs_ibs_aliquota_reduzida(48181000, "Papel Higiênico", None).is_some()
+        );
+
+        assert_eq!(
+            base_legal_de_cbs_ibs_aliquota_reduzida(3061200, "Lagosta", None),
+            None
+        );
+        assert_eq!(
+            base_legal_de_cbs_ibs_aliquota_reduzida(22029900, "Bebida Energética", None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_insumos_agropecuarios_e_medicamentos() {
+        assert!(base_legal_de_cbs_ibs_aliquota_reduzida(31021010, "Ureia", None).is_some());
+        assert!(
+            base_legal_de_cbs_ibs_aliquota_reduzida(12011000, "Semente de Soja", None).is_some()
+        );
+        assert!(
+            base_legal_de_cbs_ibs_aliquota_reduzida(23099010, "Ração para Bovinos", None).is_some()
+        );
+        assert!(base_legal_de_cbs_ibs_aliquota_reduzida(30049099, "Dipirona", None).is_some());
+
+        // Grãos para consumo e alimentos para cães e gatos
+        assert_eq!(
+            base_legal_de_cbs_ibs_aliquota_reduzida(12019000, "Soja em Grãos", None),
+            None
+        );
+        assert_eq!(
+            base_legal_de_cbs_ibs_aliquota_reduzida(23091000, "Ração para Cães", None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_vigencia_a_partir_de_2027() {
+        let data = |ano: i32, mes: u32| NaiveDate::from_ymd_opt(ano, mes, 1);
+
+        assert_eq!(
+            base_legal_de_cbs_ibs_aliquota_reduzida(31021010, "Ureia", data(2026, 12)),
+            None
+        );
+        assert!(
+            base_legal_de_cbs_ibs_aliquota_reduzida(31021010, "Ureia", data(2027, 1)).is_some()
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Regras de Alíquota Zero de CBS e IBS (Lei Complementar nº 214/2025).
+//!
+//! A partir de 01/01/2027, a CBS (Contribuição sobre Bens e Serviços) e o IBS (Imposto
+//! sobre Bens e Serviços) substituem PIS e COFINS. Ficam reduzidas a zero as alíquotas de:
+//! - Anexo I: produtos da Cesta Básica Nacional de Alimentos (arroz, leite, feijões, café,
+//!   farinhas, açúcar, massas, carnes, peixes, queijos e sal)
+//! - Anexo XV: produtos hortícolas, frutas e ovos
+//!
+//! As faixas de NCM, as descrições e as vigências constam de
+//! `legislacao_cbs_ibs_aliquota_zero.toml` (ver [`TabelaDeLegislacao`]).
+
+use chrono::NaiveDate;
+use std::sync::LazyLock;
+
+use crate::regime_fiscal::{BaseLegal, RegimesFiscais, TabelaDeLegislacao};
+
+/// Tabela de Alíquota Zero de CBS e IBS incorporada ao executável.
+pub const LEGISLACAO_CBS_IBS_ALIQUOTA_ZERO: &str =
+    include_str!("legislacao_cbs_ibs_aliquota_zero.toml");
+
+static TABELA: LazyLock<TabelaDeLegislacao> = LazyLock::new(|| {
+    TabelaDeLegislacao::from_toml(
+        LEGISLACAO_CBS_IBS_ALIQUOTA_ZERO,
+        RegimesFiscais::CbsIbsAliquotaZero,
+    )
+    .unwrap_or_else(|erro| panic!("legislacao_cbs_ibs_aliquota_zero.toml: {erro}"))
+});
+
+/// Base Legal conforme código NCM, descrição do item e data (`None`: todas as normas).
+pub fn base_legal_de_cbs_ibs_aliquota_zero(
+    codigo_ncm: u64,
+    descricao: &str,
+    data: Option<NaiveDate>,
+) -> Option<&'static BaseLegal> {
+    TABELA.base_legal(codigo_ncm, descricao, data)
+}
+
+// ----------------------------------------------------------------------------
+// TESTS
+// ----------------------------------------------------------------------------
+
+/// Run tests with:
+///
+/// `cargo test -- --show-output tests_cbs_ibs_aliquota_zero`
+#[cfg(test)]
+mod tests_cbs_ibs_aliquota_zero {
+    use super::*;
+
+    #[test]
+    fn test_cesta_basica_nacional() {
+        assert!(base_legal_de_cbs_ibs_aliquota_zero(10063021, "Arroz Branco", None).is_some());
+        assert!(base_legal_de_cbs_ibs_aliquota_zero(4012010, "Leite UHT", None).is_some());
+        assert!(base_legal_de_cbs_ibs_aliquota_zero(7133319, "Feijão Preto", None).is_some());
+        assert!(base_legal_de_cbs_ibs_aliquota_zero(2013000, "Carne Bovina", None).is_some());
+        assert!(base_legal_de_cbs_ibs_aliquota_zero(4069030, "Queijo Prato", None).is_some());
+        assert!(base_legal_de_cbs_ibs_aliquota_zero(19059090, "Pão Francês", None).is_some());
+
+        // Exceções da Cesta Básica
+        assert_eq!(
+            base_legal_de_cbs_ibs_aliquota_zero(19059090, "Biscoito Recheado", None),
+            None
+        );
+        assert_eq!(
+            base_legal_de_cbs_ibs_aliquota_zero(3021400, "Salmão do Atlântico", None),
+            None
+        );
+        assert_eq!(
+            base_legal_de_cbs_ibs_aliquota_zero(4069090, "Queijo Gorgonzola", None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_horticolas_frutas_e_ovos() {
+        assert!(base_legal_de_cbs_ibs_aliquota_zero(7020000, "Tomate", None).is_some());
+        assert!(base_legal_de_cbs_ibs_aliquota_zero(8030000, "Banana Prata", None).is_some());
+        assert!(base_legal_de_cbs_ibs_aliquota_zero(4072100, "Ovos Brancos", None).is_some());
+
+        assert_eq!(
+            base_legal_de_cbs_ibs_aliquota_zero(7070000, "Pepino em Conserva", None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_vigencia_a_partir_de_2027() {
+        let data = |ano: i32, mes: u32| NaiveDate::from_ymd_opt(ano, mes, 1);
+
+        assert_eq!(
+            base_legal_de_cbs_ibs_aliquota_zero(10063021, "Arroz", data(2026, 12)),
+            None,
+            "FALHA: Até 2026, o arroz é classificado pela Alíquota Zero de PIS e COFINS."
+        );
+        assert_eq!(
+            base_legal_de_cbs_ibs_aliquota_zero(10063021, "Arroz", data(2027, 1))
+                .map(|base| base.enunciado.as_str()),
+            Some(
+                "CBS/IBS Alíquota Zero - LC nº 214/2025, Art. 125, Anexo I (Cesta Básica Nacional de Alimentos: Arroz)."
+            )
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! Regras de Crédito Presumido de CBS e IBS (Lei Complementar nº 214/2025).
+//!
+//! A partir de 01/01/2027, o crédito da CBS e do IBS corresponde ao valor destacado no
+//! documento fiscal. Nas aquisições de produtor rural ou produtor rural integrado não
+//! contribuinte (Art. 168), sem destaque dos tributos, o adquirente pode apropriar
+//! crédito presumido.
+//!
+//! As faixas de NCM e as vigências constam de `legislacao_cbs_ibs_credito_presumido.toml`
+//! (ver [`TabelaDeLegislacao`]).
+
+use chrono::NaiveDate;
+use std::sync::LazyLock;
+
+use crate::regime_fiscal::{BaseLegal, RegimesFiscais, TabelaDeLegislacao};
+
+/// Tabela de Crédito Presumido de CBS e IBS incorporada ao executável.
+pub const LEGISLACAO_CBS_IBS_CREDITO_PRESUMIDO: &str =
+    include_str!("legislacao_cbs_ibs_credito_presumido.toml");
+
+static TABELA: LazyLock<TabelaDeLegislacao> = LazyLock::new(|| {
+    TabelaDeLegislacao::from_toml(
+        LEGISLACAO_CBS_IBS_CREDITO_PRESUMIDO,
+        RegimesFiscais::CbsIbsCreditoPresumido,
+    )
+    .unwrap_or_else(|erro| panic!("legislacao_cbs_ibs_credito_presumido.toml: {erro}"))
+});
+
+/// Base Legal conforme código NCM, descrição do item e data (`None`: todas as normas).
+pub fn base_legal_de_cbs_ibs_credito_presumido(
+    codigo_ncm: u64,
+    descricao: &str,
+    data: Option<NaiveDate>,
+) -> Option<&'static BaseLegal> {
+    TABELA.base_legal(codigo_ncm, descricao, data)
+}
+
+// ----------------------------------------------------------------------------
+// TESTS
+// ----------------------------------------------------------------------------
+
+/// Run tests with:
+///
+/// `cargo test -- --show-output tests_cbs_ibs_credito_presumido`
+#[cfg(test)]
+mod tests_cbs_ibs_credito_presumido {
+    use super::*;
+
+    #[test]
+    fn test_produtor_rural_nao_contribuinte() {
+        let data = |ano: i32, mes: u32| NaiveDate::from_ymd_opt(ano, mes, 1);
+
+        assert!(base_legal_de_cbs_ibs_credito_presumido(1022110, "", data(2027, 1)).is_some()); // Bovinos
+        assert!(base_legal_de_cbs_ibs_credito_presumido(4011010, "", data(2027, 1)).is_some()); // Leite
+        assert!(base_legal_de_cbs_ibs_credito_presumido(12019000, "", data(2027, 1)).is_some()); // Soja
+        assert!(base_legal_de_cbs_ibs_credito_presumido(44039900, "", data(2027, 1)).is_some()); // Madeira
+
+        // Produtos industrializados e períodos de PIS e COFINS
+        assert_eq!(
+            base_legal_de_cbs_ibs_credito_presumido(15071000, "", data(2027, 1)),
+            None
+        );
+        assert_eq!(
+            base_legal_de_cbs_ibs_credito_presumido(1022110, "", data(2026, 12)),
+            None
+        );
+    }
+}
@@ -4,70 +4,33 @@
 //! - Decreto nº 8.533/2015 (Programa Mais Leite Saudável)
 //! - Lei nº 12.058/2009 (Cadeia de carne bovina, ovina e caprina)
 //! - Lei nº 12.350/2010 (Cadeia de carne suína e avícola)
+//!
+//! As faixas de NCM, as descrições e as vigências constam de
+//! `legislacao_credito_presumido.toml` (ver [`TabelaDeLegislacao`]).
 
-use regex::Regex;
+use chrono::NaiveDate;
 use std::sync::LazyLock;
 
-/// Base Legal conforme código NCM e descrição do item.
-pub fn base_legal_de_credito_presumido(codigo_ncm: u64, descricao: &str) -> Option<&'static str> {
-    let especificos: &[u64] = &[
-        3029000, // Exceção padrão para descartar conflitos de peixes
-    ];
-
-    if especificos.contains(&codigo_ncm) {
-        return None;
-    }
-
-    match codigo_ncm {
-        // Capítulo 4: Leite e derivados líquidos/pó (04.01 a 04.04)
-        // Quando convertidos para u64, os NCMs iniciados em '0' perdem o zero à esquerda (7 dígitos)
-        ncm @ 4010000..=4049999 => condicoes_ncm_04(descricao, ncm),
-
-        // Posição 01.02 (Bovinos) e 01.04 (Ovinos/Caprinos)
-        1020000..=1029999 | 1040000..=1049999 => lei_12058_art33(),
+use crate::regime_fiscal::{BaseLegal, RegimesFiscais, TabelaDeLegislacao};
 
-        // Posição 01.03 (Suínos) e 01.05 (Aves)
-        1030000..=1039999 | 1050000..=1059999 => lei_12350_art55(),
+/// Tabela de Crédito Presumido incorporada ao executável.
+pub const LEGISLACAO_CREDITO_PRESUMIDO: &str = include_str!("legislacao_credito_presumido.toml");
 
-        _ => None,
-    }
-}
-
-fn condicoes_ncm_04(descricao: &str, _ncm: u64) -> Option<&'static str> {
-    static LEITE_IN_NATURA: LazyLock<Regex> =
-        LazyLock::new(|| Regex::new(r"(?i)Leite (In Natura|Cru)").unwrap());
-
-    if LEITE_IN_NATURA.is_match(descricao) {
-        // DECRETO Nº 8.533, DE 30 DE SETEMBRO DE 2015
-        // Art. 4º A pessoa jurídica regularmente habilitada, provisória ou definitivamente, ao Programa Mais Leite Saudável poderá descontar créditos presumidos da Contribuição para o PIS/Pasep e da Cofins, (...) calculados sobre o valor do leite in natura adquirido de produtor rural ou recebido de cooperado cooperativa...
-        Some(
-            "Crédito Presumido - Decreto nº 8.533/2015, Art. 4º, Inciso I (Leite In Natura Utilizado como Insumo - Programa Mais Leite Saudável).",
-        )
-    } else {
-        None
-    }
-}
-
-fn lei_12058_art33() -> Option<&'static str> {
-    // LEI Nº 12.058, DE 13 DE OUTUBRO DE 2009.
-    // Art. 33. As pessoas jurídicas sujeitas ao regime de apuração não cumulativa da Contribuição para o PIS/Pasep e da Cofins, inclusive cooperativas,
-    // que produzam mercadorias classificadas nos códigos 02.01, 02.02, 02.04, 0206.10.00, 0206.20, 0206.21, 0206.29, 0206.80.00, 0210.20.00,
-    // 0506.90.00, 0510.00.10 e 1502.00.1 da NCM, destinadas à exportação, poderão descontar da Contribuição para o PIS/Pasep e da Cofins devidas
-    // em cada período de apuração crédito presumido, calculado sobre o valor dos bens classificados nas posições 01.02 e 01.04 da NCM...
-    Some(
-        "Crédito Presumido - Lei nº 12.058/2009, Art. 33 (Animais Vivos da Posição 01.02 e 01.04: Bovinos, Ovinos ou Caprinos).",
-    )
-}
-
-fn lei_12350_art55() -> Option<&'static str> {
-    // LEI Nº 12.350, DE 20 DE DEZEMBRO DE 2010.
-    // Art. 55. As pessoas jurídicas sujeitas ao regime de apuração não cumulativa da Contribuição para o PIS/Pasep e da Cofins, inclusive cooperativas,
-    // que produzam mercadorias classificadas nos códigos 02.03, 0206.30.00, 0206.4, 02.07 e 0210.1 da NCM, destinadas a exportação, poderão descontar da
-    // Contribuição para o PIS/Pasep e da Cofins devidas em cada período de apuração crédito presumido, calculado sobre:
-    // III – o valor dos bens classificados nas posições 01.03 e 01.05 da NCM, adquiridos de pessoa física ou recebidos de cooperado pessoa física.
-    Some(
-        "Crédito Presumido - Lei nº 12.350/2010, Art. 55 (Animais Vivos da Posição 01.03 e 01.05: Suínos ou Aves).",
+static TABELA: LazyLock<TabelaDeLegislacao> = LazyLock::new(|| {
+    TabelaDeLegislacao::from_toml(
+        LEGISLACAO_CREDITO_PRESUMIDO,
+        RegimesFiscais::CreditoPresumido,
     )
+    .unwrap_or_else(|erro| panic!("legislacao_credito_presumido.toml: {erro}"))
+});
+
+/// Base Legal conforme código NCM, descrição do item e data (`None`: todas as normas).
+pub fn base_legal_de_credito_presumido(
+    codigo_ncm: u64,
+    descricao: &str,
+    data: Option<NaiveDate>,
+) -> Option<&'static BaseLegal> {
+    TABELA.base_legal(codigo_ncm, descricao, data)
 }
 
 // ----------------------------------------------------------------------------
@@ -84,26 +47,35 @@ mod tests_credito_presumido {
     #[test]
     fn test_leite_mais_leite_saudavel() {
         // NCMs iniciados em 0 perdem o zero à esquerda e resultam em 7 dígitos
-        assert!(base_legal_de_credito_presumido(4011010, "Leite In Natura").is_some());
-        assert!(base_legal_de_credito_presumido(4011010, "Leite Cru").is_some());
-        assert!(base_legal_de_credito_presumido(4011010, "Leite UHT").is_none());
+        assert!(base_legal_de_credito_presumido(4011010, "Leite In Natura", None).is_some());
+        assert!(base_legal_de_credito_presumido(4011010, "Leite Cru", None).is_some());
+        assert!(base_legal_de_credito_presumido(4011010, "Leite UHT", None).is_none());
     }
 
     #[test]
     fn test_animais_vivos_bovinos_e_ovinos() {
-        assert!(base_legal_de_credito_presumido(1022110, "").is_some()); // Bovinos (7 dígitos)
-        assert!(base_legal_de_credito_presumido(1041011, "").is_some()); // Ovinos (7 dígitos)
+        assert!(base_legal_de_credito_presumido(1022110, "", None).is_some()); // Bovinos (7 dígitos)
+        assert!(base_legal_de_credito_presumido(1041011, "", None).is_some()); // Ovinos (7 dígitos)
     }
 
     #[test]
     fn test_animais_vivos_suinos_e_aves() {
-        assert!(base_legal_de_credito_presumido(1031000, "").is_some()); // Suínos (7 dígitos)
-        assert!(base_legal_de_credito_presumido(1051110, "").is_some()); // Aves (7 dígitos)
+        assert!(base_legal_de_credito_presumido(1031000, "", None).is_some()); // Suínos (7 dígitos)
+        assert!(base_legal_de_credito_presumido(1051110, "", None).is_some()); // Aves (7 dígitos)
     }
 
     #[test]
     fn test_nao_credito_presumido() {
-        assert!(base_legal_de_credito_presumido(3029000, "").is_none());
-        assert!(base_legal_de_credito_presumido(31021010, "").is_none());
+        assert!(base_legal_de_credito_presumido(3029000, "", None).is_none());
+        assert!(base_legal_de_credito_presumido(31021010, "", None).is_none());
+    }
+
+    #[test]
+    fn test_vigencia_mais_leite_saudavel() {
+        let data = |ano: i32, mes: u32| NaiveDate::from_ymd_opt(ano, mes, 1);
+
+        // Programa Mais Leite Saudável: Decreto nº 8.533, de 30 de setembro de 2015.
+        assert!(base_legal_de_credito_presumido(4011010, "Leite Cru", data(2015, 8)).is_none());
+        assert!(base_legal_de_credito_presumido(4011010, "Leite Cru", data(2015, 9)).is_some());
     }
 }
@@ -0,0 +1,104 @@
+//! Regras de Suspensão de PIS e COFINS nas cadeias do agronegócio.
+//!
+//! A incidência das contribuições fica suspensa nas vendas de:
+//! - produtos in natura de origem vegetal por cerealista e leite in natura
+//!   (Lei nº 10.925/2004, Art. 9º)
+//! - bovinos vivos para a produção de carnes (Lei nº 12.058/2009, Art. 32)
+//! - insumos, rações e animais vivos para a produção de carnes suínas e de aves
+//!   (Lei nº 12.350/2010, Art. 54)
+//!
+//! Ao contrário da Alíquota Zero, a suspensão depende da destinação do bem: é vedada
+//! quando a aquisição for destinada à revenda (IN RFB nº 2.121/2022, Art. 569).
+//!
+//! As faixas de NCM, as descrições e as vigências constam de `legislacao_suspensao.toml`
+//! (ver [`TabelaDeLegislacao`]).
+
+use chrono::NaiveDate;
+use std::sync::LazyLock;
+
+use crate::regime_fiscal::{BaseLegal, RegimesFiscais, TabelaDeLegislacao};
+
+/// Tabela de Suspensão incorporada ao executável.
+pub const LEGISLACAO_SUSPENSAO: &str = include_str!("legislacao_suspensao.toml");
+
+/// NCM 2309.90.xx ou 230990xx (regex): preparações para alimentação animal cuja
+/// suspensão é vedada na revenda (IN RFB nº 2.121/2022, Art. 569, Inciso II).
+pub const NCM_COM_SUSPENSAO_VEDADA_NA_REVENDA: &str = r"^\D*2309\.?90";
+
+static TABELA: LazyLock<TabelaDeLegislacao> = LazyLock::new(|| {
+    TabelaDeLegislacao::from_toml(LEGISLACAO_SUSPENSAO, RegimesFiscais::Suspensao)
+        .unwrap_or_else(|erro| panic!("legislacao_suspensao.toml: {erro}"))
+});
+
+/// Base Legal conforme código NCM, descrição do item e data (`None`: todas as normas).
+pub fn base_legal_de_suspensao(
+    codigo_ncm: u64,
+    descricao: &str,
+    data: Option<NaiveDate>,
+) -> Option<&'static BaseLegal> {
+    TABELA.base_legal(codigo_ncm, descricao, data)
+}
+
+// ----------------------------------------------------------------------------
+// TESTS
+// ----------------------------------------------------------------------------
+
+/// Run tests with:
+///
+/// `cargo test -- --show-output tests_suspensao`
+#[cfg(test)]
+mod tests_suspensao {
+    use super::*;
+
+    #[test]
+    fn test_cerealista_e_leite_in_natura() {
+        assert!(base_legal_de_suspensao(9011110, "Café Cru em Grão", None).is_some());
+        assert!(base_legal_de_suspensao(10059010, "Milho em Grão", None).is_some());
+        assert!(base_legal_de_suspensao(12019000, "Soja em Grãos", None).is_some());
+        assert!(base_legal_de_suspensao(4012010, "Leite Cru Refrigerado", None).is_some());
+
+        // Arroz descascado ou beneficiado (1006.20 e 1006.30) e leite industrializado
+        assert_eq!(
+            base_legal_de_suspensao(10063021, "Arroz Branco", None),
+            None
+        );
+        assert_eq!(base_legal_de_suspensao(4012010, "Leite UHT", None), None);
+    }
+
+    #[test]
+    fn test_cadeias_de_carnes() {
+        assert!(base_legal_de_suspensao(1022110, "Boi Vivo", None).is_some());
+        assert!(base_legal_de_suspensao(1039100, "Suíno Vivo", None).is_some());
+        assert!(base_legal_de_suspensao(23040010, "Farelo de Soja", None).is_some());
+        assert_eq!(
+            base_legal_de_suspensao(23099010, "Ração para Aves", None)
+                .map(|base| base.enunciado.as_str()),
+            Some(
+                "Suspensão - Lei nº 12.350/2010, Art. 54, Inciso II (Preparações para Alimentação Animal do Código 2309.90)."
+            )
+        );
+
+        // Alimentos para cães e gatos
+        assert_eq!(
+            base_legal_de_suspensao(23091000, "Ração para Cães", None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_vigencia_das_normas() {
+        let data = |ano: i32, mes: u32| NaiveDate::from_ymd_opt(ano, mes, 1);
+
+        assert_eq!(
+            base_legal_de_suspensao(23099010, "Ração", data(2010, 11)),
+            None
+        );
+        assert!(base_legal_de_suspensao(23099010, "Ração", data(2011, 1)).is_some());
+        assert!(base_legal_de_suspensao(23099010, "Ração", data(2026, 12)).is_some());
+        assert_eq!(
+            base_legal_de_suspensao(23099010, "Ração", data(2027, 1)),
+            None,
+            "FALHA: A partir de 2027, PIS e COFINS são substituídos pela CBS e pelo IBS."
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! Regras da Zona Franca de Manaus (ZFM) e das Áreas de Livre Comércio (ALC).
+//!
+//! Ao contrário dos demais regimes, o enquadramento não depende do NCM, mas da
+//! localização do participante: as vendas destinadas ao consumo ou à industrialização
+//! na ZFM (Lei nº 10.996/2004, Art. 2º) ou nas ALC (Lei nº 11.945/2009), por pessoa
+//! jurídica estabelecida fora dessas áreas, têm alíquota zero de PIS e COFINS.
+//!
+//! A localização é obtida da UF e do município do participante. Nas NF-e, o município
+//! não é informado: a venda destinada à ZFM ou ALC é identificada pelo CFOP
+//! (5109, 5110, 6109 e 6110).
+//!
+//! As áreas, os municípios, os CFOPs e as vigências constam de
+//! `legislacao_zona_franca.toml`.
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::sync::LazyLock;
+
+use crate::{JoinError, JoinResult, regime_fiscal::tabela_de_legislacao::vigente, remover_acentos};
+
+/// Tabela da ZFM e das ALC incorporada ao executável.
+pub const LEGISLACAO_ZONA_FRANCA: &str = include_str!("legislacao_zona_franca.toml");
+
+static TABELA: LazyLock<TabelaDeAreasEspeciais> = LazyLock::new(|| {
+    TabelaDeAreasEspeciais::from_toml(LEGISLACAO_ZONA_FRANCA)
+        .unwrap_or_else(|erro| panic!("legislacao_zona_franca.toml: {erro}"))
+});
+
+/// Áreas especiais (ZFM e ALC), lidas de um arquivo TOML.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TabelaDeAreasEspeciais {
+    /// Áreas avaliadas na ordem do arquivo.
+    #[serde(default, rename = "area")]
+    pub areas: Vec<AreaEspecial>,
+
+    /// CFOPs de venda destinada à ZFM ou ALC.
+    pub cfop: CfopDeAreasEspeciais,
+}
+
+/// Municípios de uma Unidade da Federação abrangidos pela área especial.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AreaEspecial {
+    /// Texto informado na coluna "Zona Franca".
+    pub base_legal: String,
+
+    /// Transcrição do dispositivo legal (apenas documental).
+    #[allow(dead_code)]
+    #[serde(default)]
+    pub texto: String,
+
+    /// Sigla da Unidade da Federação.
+    pub uf: String,
+
+    pub municipios: Vec<String>,
+
+    pub inicio: Option<NaiveDate>,
+    pub fim: Option<NaiveDate>,
+}
+
+/// CFOPs que identificam, no documento fiscal, a venda destinada à ZFM ou ALC.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CfopDeAreasEspeciais {
+    pub base_legal: String,
+    pub codigos: Vec<i64>,
+    pub inicio: Option<NaiveDate>,
+    pub fim: Option<NaiveDate>,
+}
+
+impl TabelaDeAreasEspeciais {
+    /// Lê e valida a tabela a partir de um texto no formato TOML.
+    pub fn from_toml(texto: &str) -> JoinResult<Self> {
+        let tabela: TabelaDeAreasEspeciais = toml::from_str(texto)?;
+
+        for area in &tabela.areas {
+            if area.uf.trim().len() != 2 || area.municipios.is_empty() {
+                return Err(JoinError::Other(format!(
+                    "área '{}': informe a sigla da UF e os municípios",
+                    area.base_legal
+                )));
+            }
+        }
+
+        Ok(tabela)
+    }
+
+    /// Base legal da primeira área vigente em `data` que abrange a UF e o município.
+    pub fn base_legal(&self, uf: &str, municipio: &str, data: Option<NaiveDate>) -> Option<&str> {
+        let municipio: String = remover_acentos(municipio.trim());
+
+        self.areas
+            .iter()
+            .find(|area| {
+                vigente(area.inicio, area.fim, data)
+                    && area.uf.eq_ignore_ascii_case(uf.trim())
+                    && area
+                        .municipios
+                        .iter()
+                        .any(|nome| remover_acentos(nome) == municipio)
+            })
+            .map(|area| area.base_legal.as_str())
+    }
+
+    /// Base legal do CFOP de venda destinada à ZFM ou ALC, se vigente em `data`.
+    pub fn base_legal_do_cfop(&self, cfop: i64, data: Option<NaiveDate>) -> Option<&str> {
+        let cfop_especial = &self.cfop;
+
+        (vigente(cfop_especial.inicio, cfop_especial.fim, data)
+            && cfop_especial.codigos.contains(&cfop))
+        .then_some(cfop_especial.base_legal.as_str())
+    }
+}
+
+/// Base Legal conforme UF, município e data (`None`: todas as normas).
+pub fn base_legal_de_zona_franca(
+    uf: &str,
+    municipio: &str,
+    data: Option<NaiveDate>,
+) -> Option<&'static str> {
+    TABELA.base_legal(uf, municipio, data)
+}
+
+/// Base Legal conforme CFOP de venda destinada à ZFM ou ALC e data (`None`: todas as normas).
+pub fn base_legal_do_cfop_de_zona_franca(
+    cfop: i64,
+    data: Option<NaiveDate>,
+) -> Option<&'static str> {
+    TABELA.base_legal_do_cfop(cfop, data)
+}
+
+/// Sigla da UF do emitente, informada nos dois primeiros dígitos da chave de acesso
+/// (código IBGE da Unidade da Federação).
+pub fn uf_da_chave(chave: &str) -> Option<&'static str> {
+    let codigo: String = chave
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .take(2)
+        .collect();
+
+    let uf = match codigo.as_str() {
+        "11" => "RO",
+        "12" => "AC",
+        "13" => "AM",
+        "14" => "RR",
+        "15" => "PA",
+        "16" => "AP",
+        "17" => "TO",
+        "21" => "MA",
+        "22" => "PI",
+        "23" => "CE",
+        "24" => "RN",
+        "25" => "PB",
+        "26" => "PE",
+        "27" => "AL",
+        "28" => "SE",
+        "29" => "BA",
+        "31" => "MG",
+        "32" => "ES",
+        "33" => "RJ",
+        "35" => "SP",
+        "41" => "PR",
+        "42" => "SC",
+        "43" => "RS",
+        "50" => "MS",
+        "51" => "MT",
+        "52" => "GO",
+        "53" => "DF",
+        _ => return None,
+    };
+
+    Some(uf)
+}
+
+// ----------------------------------------------------------------------------
+// TESTS
+// ----------------------------------------------------------------------------
+
+/// Run tests with:
+///
+/// `cargo test -- --show-output tests_zona_franca`
+#[cfg(test)]
+mod tests_zona_franca {
+    use super::*;
+
+    #[test]
+    fn test_municipios_da_zfm_e_das_alc() {
+        assert_eq!(
+            base_legal_de_zona_franca("AM", "MANAUS", None),
+            Some(
+                "Zona Franca de Manaus - Lei nº 10.996/2004, Art. 2º (Alíquota Zero nas Vendas Destinadas ao Consumo ou à Industrialização na ZFM)."
+            )
+        );
+        assert!(base_legal_de_zona_franca("am", "Rio Preto da Eva", None).is_some());
+        assert!(base_legal_de_zona_franca("RO", "GUAJARA-MIRIM", None).is_some());
+        assert!(base_legal_de_zona_franca("AP", "Macapá", None).is_some());
+        assert!(base_legal_de_zona_franca("AC", "Epitaciolândia", None).is_some());
+
+        // Município fora das áreas especiais ou UF divergente
+        assert_eq!(base_legal_de_zona_franca("AM", "Parintins", None), None);
+        assert_eq!(base_legal_de_zona_franca("PA", "Santana", None), None);
+    }
+
+    #[test]
+    fn test_cfop_de_venda_para_zfm_ou_alc() {
+        assert!(base_legal_do_cfop_de_zona_franca(6109, None).is_some());
+        assert!(base_legal_do_cfop_de_zona_franca(5110, None).is_some());
+        assert_eq!(base_legal_do_cfop_de_zona_franca(6102, None), None);
+    }
+
+    #[test]
+    fn test_vigencia_das_normas() {
+        let data = |ano: i32, mes: u32| NaiveDate::from_ymd_opt(ano, mes, 1);
+
+        assert!(base_legal_de_zona_franca("AM", "Manaus", data(2005, 1)).is_some());
+        assert_eq!(
+            base_legal_de_zona_franca("AP", "Macapá", data(2008, 12)),
+            None
+        );
+        assert!(base_legal_de_zona_franca("AP", "Macapá", data(2009, 6)).is_some());
+        assert_eq!(
+            base_legal_de_zona_franca("AM", "Manaus", data(2027, 1)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_uf_da_chave() {
+        assert_eq!(
+            uf_da_chave("13230512345678000190550010000012341000012345"),
+            Some("AM")
+        );
+        assert_eq!(uf_da_chave("35-2305"), Some("SP"));
+        assert_eq!(uf_da_chave("99"), None);
+        assert_eq!(uf_da_chave(""), None);
+    }
+}
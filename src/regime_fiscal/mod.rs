@@ -4,10 +4,33 @@
 //! - Alíquota Zero (com base na Lei nº 10.925/2004, Lei nº 10.865/2004, etc.)
 //! - Crédito Presumido (cadeias do agronegócio e pecuária)
 //! - Incidência Monofásica (combustíveis, cosméticos, fármacos, autopeças e pneus)
+//! - Suspensão (cadeias do agronegócio: cerealista, leite in natura, carnes e rações)
+//! - Zona Franca de Manaus e Áreas de Livre Comércio (localização do participante)
+//!
+//! A partir de 01/01/2027, PIS e COFINS são substituídos pela CBS e pelo IBS
+//! (LC nº 214/2025), com os regimes de:
+//! - Alíquota Zero (Cesta Básica Nacional de Alimentos, produtos hortícolas, frutas e ovos)
+//! - Alíquota Reduzida (alimentos, higiene pessoal, medicamentos e insumos agropecuários)
+//! - Crédito Presumido (aquisições de produtor rural não contribuinte)
+//!
+//! O regime aplicável a cada item é escolhido pelo seu Período de Apuração, conforme a
+//! vigência das normas: auditorias do ano de transição classificam os períodos até
+//! 12/2026 pelas regras de PIS e COFINS e os seguintes pelas regras de CBS e IBS.
+//!
+//! As normas de cada regime, com suas vigências, são lidas de tabelas TOML
+//! (ver [`TabelaDeLegislacao`]). O enquadramento de cada item é retornado como
+//! [`BaseLegal`] (norma, artigo, inciso, anexo e descrição).
 
 pub mod aplicacao_do_regime;
 pub mod legislacao_aliquota_zero;
+pub mod legislacao_cbs_ibs_aliquota_reduzida;
+pub mod legislacao_cbs_ibs_aliquota_zero;
+pub mod legislacao_cbs_ibs_credito_presumido;
 pub mod legislacao_credito_presumido;
 pub mod legislacao_incidencia_monofasica;
+pub mod legislacao_suspensao;
+pub mod legislacao_zona_franca;
+pub mod tabela_de_legislacao;
 
 pub use aplicacao_do_regime::*;
+pub use tabela_de_legislacao::*;
@@ -0,0 +1,526 @@
+//! # Tabelas de Legislação por NCM
+//!
+//! O enquadramento de cada regime fiscal — fn(NCM, Descrição) -> Base Legal — é lido
+//! de uma tabela TOML incorporada ao executável, na qual cada norma informa sua vigência.
+//! Cada item é classificado pelas normas vigentes no seu Período de Apuração:
+//! auditorias de períodos antigos utilizam as regras da época.
+//!
+//! Formato TOML:
+//!
+//! ```toml
+//! [[exclusao]]
+//! motivo = "Lei nº 10.925/2004, Art. 1º, Inciso XX, alínea 'a'"
+//! ncm = ["0302.90.00"]
+//!
+//! [[norma]]
+//! base_legal = "Alíquota Zero - Lei nº 10.925/2004, Art. 1º, Inciso I (...)."
+//! norma = "Lei nº 10.925/2004"
+//! artigo = "Art. 1º"                  # opcional
+//! inciso = "Inciso I"                 # opcional
+//! anexo = "Anexo I"                   # opcional
+//! produtos = "Adubos ou Fertilizantes do Capítulo 31 e suas Matérias-Primas"  # opcional
+//! texto = "Inciso I - adubos ou fertilizantes classificados no Capítulo 31 ..."
+//! ncm = ["3100.00.00 a 3199.99.99", "2707.50.00"]
+//! descricao = '(?i)Fertilizante'      # opcional: a descrição deve conter o padrão
+//! excluir_descricao = '(?i)Vacina'    # opcional: a descrição não pode conter o padrão
+//! inicio = "2004-07-23"               # opcional: início da vigência (AAAA-MM-DD)
+//! fim = "2026-12-31"                  # opcional: fim da vigência
+//! ```
+//!
+//! As exclusões vigentes prevalecem. Em seguida, vale a primeira norma vigente cujo
+//! NCM e descrição sejam compatíveis com o item: a ordem das normas no arquivo importa.
+//!
+//! A vigência é comparada por mês, pois o Período de Apuração é mensal: a norma vigente
+//! em algum dia do período é considerada.
+//!
+//! Os NCMs são comparados como números, tal como [`parse_ncm_to_u64`]:
+//! "0201.00.00" corresponde a `2010000` (o zero à esquerda é descartado).
+//!
+//! O enquadramento é retornado como [`BaseLegal`], com a norma, o artigo, o inciso, o
+//! anexo e os produtos declarados em cada `[[norma]]`, de modo que relatórios e regras
+//! possam filtrar os itens por dispositivo legal sem interpretar o texto da coluna.
+//! A chave `descricao` da tabela é a expressão regular aplicada à descrição do item;
+//! a descrição dos produtos abrangidos pelo dispositivo é informada em `produtos`.
+
+use chrono::{Datelike, NaiveDate};
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::{
+    JoinError, JoinResult,
+    regime_fiscal::{RegimesFiscais, parse_ncm_to_u64},
+};
+
+/// Normas de um regime fiscal, lidas de um arquivo TOML.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TabelaDeLegislacao {
+    /// NCMs expressamente excluídos do regime.
+    #[serde(default, rename = "exclusao")]
+    pub exclusoes: Vec<Exclusao>,
+
+    /// Normas avaliadas na ordem do arquivo.
+    #[serde(default, rename = "norma")]
+    pub normas: Vec<Norma>,
+}
+
+/// NCMs que, embora inseridos em faixas do regime, são excluídos pela legislação.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Exclusao {
+    /// Fundamento da exclusão (apenas documental).
+    #[allow(dead_code)]
+    #[serde(default)]
+    pub motivo: String,
+
+    pub ncm: Vec<FaixaDeNcm>,
+
+    pub inicio: Option<NaiveDate>,
+    pub fim: Option<NaiveDate>,
+}
+
+/// Enquadramento legal de um conjunto de NCMs, restrito ou não pela descrição do item.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Norma {
+    /// Texto informado na coluna do regime fiscal.
+    pub base_legal: String,
+
+    /// Ato normativo. Exemplo: "Lei nº 10.925/2004".
+    pub norma: String,
+
+    /// Exemplo: "Art. 1º" ou "Art. 8º, § 12".
+    pub artigo: Option<String>,
+
+    /// Exemplo: "Inciso XIX, alínea 'a'".
+    pub inciso: Option<String>,
+
+    /// Exemplo: "Anexo I".
+    pub anexo: Option<String>,
+
+    /// Produtos abrangidos pelo dispositivo. Exemplo: "Trigo da Posição 10.01".
+    pub produtos: Option<String>,
+
+    /// Transcrição do dispositivo legal (apenas documental).
+    #[allow(dead_code)]
+    #[serde(default)]
+    pub texto: String,
+
+    /// NCMs abrangidos (vazio: qualquer NCM, desde que a descrição seja compatível).
+    #[serde(default)]
+    pub ncm: Vec<FaixaDeNcm>,
+
+    /// Expressão regular que a descrição do item deve conter.
+    pub descricao: Option<String>,
+
+    /// Expressão regular que afasta o enquadramento.
+    pub excluir_descricao: Option<String>,
+
+    pub inicio: Option<NaiveDate>,
+    pub fim: Option<NaiveDate>,
+
+    #[serde(skip)]
+    regex_descricao: Option<Regex>,
+
+    #[serde(skip)]
+    regex_excluir_descricao: Option<Regex>,
+
+    #[serde(skip)]
+    enquadramento: Option<BaseLegal>,
+}
+
+/// Enquadramento legal estruturado de um item, obtido da norma compatível.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseLegal {
+    pub regime: RegimesFiscais,
+
+    /// Ato normativo. Exemplo: "Lei nº 10.925/2004".
+    pub norma: String,
+
+    /// Exemplo: "Art. 1º" ou "Art. 8º, § 12".
+    pub artigo: Option<String>,
+
+    /// Exemplo: "Inciso XIX, alínea 'a'".
+    pub inciso: Option<String>,
+
+    /// Exemplo: "Anexo I".
+    pub anexo: Option<String>,
+
+    /// Produtos abrangidos (`produtos` da norma). Exemplo: "Trigo da Posição 10.01".
+    pub descricao: Option<String>,
+
+    /// Posição da norma na tabela do regime (1, 2, ...).
+    pub rule_id: u32,
+
+    /// Critério utilizado no enquadramento.
+    pub matched_by: MatchedBy,
+
+    /// Texto informado na coluna do regime fiscal (`base_legal` da norma).
+    pub enunciado: String,
+}
+
+/// Critério de enquadramento da norma: NCM, descrição do item ou ambos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchedBy {
+    Ncm,
+    Descricao,
+    Both,
+}
+
+impl MatchedBy {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ncm => "NCM",
+            Self::Descricao => "Descrição",
+            Self::Both => "NCM e Descrição",
+        }
+    }
+}
+
+/// Faixa de NCMs: "3100.00.00 a 3199.99.99" ou um único código "2707.50.00".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct FaixaDeNcm {
+    pub inicio: u64,
+    pub fim: u64,
+}
+
+impl TryFrom<String> for FaixaDeNcm {
+    type Error = String;
+
+    fn try_from(texto: String) -> Result<Self, Self::Error> {
+        let (inicio, fim) = texto
+            .split_once(" a ")
+            .unwrap_or((texto.as_str(), texto.as_str()));
+        let ncm = |codigo: &str| -> Result<u64, String> {
+            parse_ncm_to_u64(codigo).ok_or_else(|| format!("NCM inválido: '{texto}'"))
+        };
+
+        let faixa = FaixaDeNcm {
+            inicio: ncm(inicio)?,
+            fim: ncm(fim)?,
+        };
+
+        if faixa.inicio > faixa.fim {
+            return Err(format!("faixa de NCM invertida: '{texto}'"));
+        }
+
+        Ok(faixa)
+    }
+}
+
+impl FaixaDeNcm {
+    pub fn contains(&self, ncm: u64) -> bool {
+        (self.inicio..=self.fim).contains(&ncm)
+    }
+}
+
+impl TabelaDeLegislacao {
+    /// Lê e valida a tabela do regime fiscal a partir de um texto no formato TOML.
+    pub fn from_toml(texto: &str, regime: RegimesFiscais) -> JoinResult<Self> {
+        let mut tabela: TabelaDeLegislacao = toml::from_str(texto)?;
+
+        for (indice, norma) in tabela.normas.iter_mut().enumerate() {
+            norma
+                .compilar(regime, indice as u32 + 1)
+                .map_err(|motivo| {
+                    JoinError::Other(format!(
+                        "norma {} ('{}'): {motivo}",
+                        indice + 1,
+                        norma.base_legal
+                    ))
+                })?;
+        }
+
+        Ok(tabela)
+    }
+
+    /// Base legal da primeira norma vigente em `data` compatível com o NCM e a descrição.
+    ///
+    /// Sem data (`None`), a vigência não é considerada.
+    pub fn base_legal(
+        &self,
+        ncm: u64,
+        descricao: &str,
+        data: Option<NaiveDate>,
+    ) -> Option<&BaseLegal> {
+        let excluido: bool = self.exclusoes.iter().any(|exclusao| {
+            vigente(exclusao.inicio, exclusao.fim, data)
+                && exclusao.ncm.iter().any(|faixa| faixa.contains(ncm))
+        });
+
+        if excluido {
+            return None;
+        }
+
+        self.normas
+            .iter()
+            .find(|norma| norma.aplica_se(ncm, descricao, data))
+            .and_then(|norma| norma.enquadramento.as_ref())
+    }
+}
+
+impl Norma {
+    fn compilar(&mut self, regime: RegimesFiscais, rule_id: u32) -> Result<(), String> {
+        if self.ncm.is_empty() && self.descricao.is_none() {
+            return Err("informe os NCMs ou a descrição".to_string());
+        }
+
+        if self.norma.trim().is_empty() {
+            return Err("informe a norma".to_string());
+        }
+
+        if let (Some(inicio), Some(fim)) = (self.inicio, self.fim)
+            && inicio > fim
+        {
+            return Err(format!("vigência invertida: {inicio} a {fim}"));
+        }
+
+        let compilar = |padrao: &Option<String>| -> Result<Option<Regex>, String> {
+            padrao
+                .as_deref()
+                .map(|padrao| Regex::new(padrao).map_err(|e| format!("regex '{padrao}': {e}")))
+                .transpose()
+        };
+
+        self.regex_descricao = compilar(&self.descricao)?;
+        self.regex_excluir_descricao = compilar(&self.excluir_descricao)?;
+
+        let matched_by = match (self.ncm.is_empty(), self.descricao.is_none()) {
+            (false, true) => MatchedBy::Ncm,
+            (true, false) => MatchedBy::Descricao,
+            _ => MatchedBy::Both,
+        };
+        self.enquadramento = Some(BaseLegal {
+            regime,
+            norma: self.norma.clone(),
+            artigo: self.artigo.clone(),
+            inciso: self.inciso.clone(),
+            anexo: self.anexo.clone(),
+            descricao: self.produtos.clone(),
+            rule_id,
+            matched_by,
+            enunciado: self.base_legal.clone(),
+        });
+
+        Ok(())
+    }
+
+    fn aplica_se(&self, ncm: u64, descricao: &str, data: Option<NaiveDate>) -> bool {
+        vigente(self.inicio, self.fim, data)
+            && (self.ncm.is_empty() || self.ncm.iter().any(|faixa| faixa.contains(ncm)))
+            && self
+                .regex_descricao
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(descricao))
+            && !self
+                .regex_excluir_descricao
+                .as_ref()
+                .is_some_and(|regex| regex.is_match(descricao))
+    }
+}
+
+/// O mês da data está dentro da vigência (limites inclusivos e opcionais)?
+pub(crate) fn vigente(
+    inicio: Option<NaiveDate>,
+    fim: Option<NaiveDate>,
+    data: Option<NaiveDate>,
+) -> bool {
+    let mes = |data: NaiveDate| (data.year(), data.month());
+
+    data.is_none_or(|data| {
+        inicio.is_none_or(|inicio| mes(inicio) <= mes(data))
+            && fim.is_none_or(|fim| mes(data) <= mes(fim))
+    })
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_tabela_de_legislacao
+#[cfg(test)]
+mod tests_tabela_de_legislacao {
+    use super::*;
+
+    fn data(texto: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(texto, "%Y-%m-%d").ok()
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_faixa_de_ncm`
+    fn test_faixa_de_ncm() {
+        let faixa = FaixaDeNcm::try_from("0201.00.00 a 0202.99.99".to_string());
+        assert_eq!(
+            faixa,
+            Ok(FaixaDeNcm {
+                inicio: 2010000,
+                fim: 2029999
+            })
+        );
+
+        let codigo = FaixaDeNcm::try_from("2707.50.00".to_string());
+        assert_eq!(
+            codigo,
+            Ok(FaixaDeNcm {
+                inicio: 27075000,
+                fim: 27075000
+            })
+        );
+
+        assert!(FaixaDeNcm::try_from("3199.99.99 a 3100.00.00".to_string()).is_err());
+        assert!(FaixaDeNcm::try_from("NCM".to_string()).is_err());
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_vigencia_das_normas`
+    fn test_vigencia_das_normas() -> JoinResult<()> {
+        let texto = r#"
+            [[exclusao]]
+            ncm = ["0302.90.00"]
+
+            [[norma]]
+            base_legal = "Redação nova"
+            norma = "Lei nº 10.925/2004"
+            ncm = ["0302.00.00 a 0302.99.99"]
+            inicio = "2013-03-08"
+
+            [[norma]]
+            base_legal = "Redação antiga"
+            norma = "Lei nº 10.925/2004"
+            ncm = ["0302.00.00 a 0302.99.99"]
+            descricao = '(?i)Peixe'
+            excluir_descricao = '(?i)Ornamental'
+            fim = "2013-02-28"
+        "#;
+        let tabela = TabelaDeLegislacao::from_toml(texto, RegimesFiscais::AliquotaZero)?;
+        let enunciado = |ncm: u64, descricao: &str, data: Option<NaiveDate>| {
+            tabela
+                .base_legal(ncm, descricao, data)
+                .map(|base_legal| base_legal.enunciado.as_str())
+        };
+
+        // Vigência comparada por mês: a norma de 08/03/2013 vale para o período 03/2013.
+        assert_eq!(
+            enunciado(3021100, "", data("2013-03-01")),
+            Some("Redação nova")
+        );
+        assert_eq!(
+            enunciado(3021100, "Peixe fresco", data("2013-02-01")),
+            Some("Redação antiga")
+        );
+        assert_eq!(enunciado(3021100, "", data("2013-02-01")), None);
+        assert_eq!(
+            enunciado(3021100, "Peixe ornamental", data("2012-01-01")),
+            None
+        );
+        assert_eq!(enunciado(3029000, "Peixe", None), None);
+
+        // Sem data, vale a primeira norma compatível.
+        assert_eq!(enunciado(3021100, "Peixe", None), Some("Redação nova"));
+
+        let sem_ncm = r#"
+            [[norma]]
+            base_legal = "Qualquer item"
+            norma = "Lei nº 10.925/2004"
+        "#;
+        assert!(TabelaDeLegislacao::from_toml(sem_ncm, RegimesFiscais::AliquotaZero).is_err());
+
+        let regex_invalida = r#"
+            [[norma]]
+            base_legal = "Regex inválida"
+            norma = "Lei nº 10.925/2004"
+            descricao = '(?i)Leite ('
+        "#;
+        assert!(
+            TabelaDeLegislacao::from_toml(regex_invalida, RegimesFiscais::AliquotaZero).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_base_legal_estruturada`
+    fn test_base_legal_estruturada() -> JoinResult<()> {
+        let texto = r#"
+            [[norma]]
+            base_legal = "Alíquota Zero - Lei nº 10.925/2004, Art. 1º, Inciso XIX, alínea 'a' (Peixes (exceto ornamentais) da Posição 03.02)."
+            norma = "Lei nº 10.925/2004"
+            artigo = "Art. 1º"
+            inciso = "Inciso XIX, alínea 'a'"
+            produtos = "Peixes (exceto ornamentais) da Posição 03.02"
+            ncm = ["0302.00.00 a 0302.99.99"]
+            excluir_descricao = '(?i)Ornamental'
+
+            [[norma]]
+            base_legal = "Alíquota Zero - Lei nº 10.865/2004, Art. 8º, § 12 (Livros)."
+            norma = "Lei nº 10.865/2004"
+            artigo = "Art. 8º, § 12"
+            produtos = "Livros"
+            ncm = ["4901.00.00 a 4901.99.99"]
+            descricao = '(?i)Livro'
+
+            [[norma]]
+            base_legal = "Incidência Monofásica - Medida Provisória nº 2.158-35/2001, Art. 43."
+            norma = "Medida Provisória nº 2.158-35/2001"
+            artigo = "Art. 43"
+            descricao = '(?i)Querosene'
+
+            [[norma]]
+            base_legal = "CBS/IBS Alíquota Zero - LC nº 214/2025, Anexo XV (Frutas Frescas ou Refrigeradas)."
+            norma = "LC nº 214/2025"
+            anexo = "Anexo XV"
+            produtos = "Frutas Frescas ou Refrigeradas"
+            ncm = ["0803.00.00 a 0810.99.99"]
+        "#;
+        let tabela = TabelaDeLegislacao::from_toml(texto, RegimesFiscais::AliquotaZero)?;
+
+        assert_eq!(
+            tabela.base_legal(3021100, "Salmão", None),
+            Some(&BaseLegal {
+                regime: RegimesFiscais::AliquotaZero,
+                norma: "Lei nº 10.925/2004".to_string(),
+                artigo: Some("Art. 1º".to_string()),
+                inciso: Some("Inciso XIX, alínea 'a'".to_string()),
+                anexo: None,
+                descricao: Some("Peixes (exceto ornamentais) da Posição 03.02".to_string()),
+                rule_id: 1,
+                matched_by: MatchedBy::Ncm,
+                enunciado: "Alíquota Zero - Lei nº 10.925/2004, Art. 1º, Inciso XIX, alínea 'a' (Peixes (exceto ornamentais) da Posição 03.02).".to_string(),
+            })
+        );
+
+        let livro = tabela.base_legal(49019900, "Livro Didático", None);
+        assert_eq!(
+            livro.map(|base| (base.artigo.as_deref(), base.inciso.as_deref())),
+            Some((Some("Art. 8º, § 12"), None))
+        );
+        assert_eq!(livro.map(|base| base.matched_by), Some(MatchedBy::Both));
+
+        let querosene = tabela.base_legal(0, "Querosene de Aviação", None);
+        assert_eq!(
+            querosene.map(|base| (base.norma.as_str(), base.descricao.as_deref())),
+            Some(("Medida Provisória nº 2.158-35/2001", None))
+        );
+        assert_eq!(querosene.map(|base| base.rule_id), Some(3));
+        assert_eq!(
+            querosene.map(|base| base.matched_by),
+            Some(MatchedBy::Descricao)
+        );
+
+        // O anexo não é confundido com o artigo.
+        let fruta = tabela.base_legal(8030000, "Banana", None);
+        assert_eq!(
+            fruta.map(|base| (base.artigo.as_deref(), base.anexo.as_deref())),
+            Some((None, Some("Anexo XV")))
+        );
+
+        let sem_norma = r#"
+            [[norma]]
+            base_legal = "Sem norma"
+            norma = " "
+            ncm = ["0302.00.00"]
+        "#;
+        assert!(TabelaDeLegislacao::from_toml(sem_norma, RegimesFiscais::AliquotaZero).is_err());
+
+        Ok(())
+    }
+}
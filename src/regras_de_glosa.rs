@@ -0,0 +1,1315 @@
+//! # Regras de Glosa Declarativas
+//!
+//! As situações de glosa são declaradas em um arquivo TOML (`--regras`).
+//! Na ausência deste arquivo, utiliza-se `regras_de_glosa.toml`, incorporado
+//! ao executável, que contém as situações originais do programa.
+//!
+//! Cada regra contém:
+//!
+//! - `id`, `descricao` e `base_legal`;
+//! - `filtro`: predicado composto por faixas de CST, listas de CFOP, expressões
+//!   regulares, verificações de nulos e filtros de `filtros.rs` (por nome);
+//! - `mensagem`: segmentos de texto ou colunas adicionados à coluna `glosar`;
+//! - `ajuste`: novo valor da Base de Cálculo: zero (padrão), um valor fixo ou
+//!   expressão, um percentual glosado ou a dedução de uma coluna.
+//!
+//! O valor glosado por cada regra é registrado na coluna
+//! `Valor Glosado: Situação <id>` (ver [`coluna_valor_glosado`]) e o total
+//! na coluna `Valor Glosado da Base de Cálculo`.
+//!
+//! Regras que dependem de agrupamentos (como a Situação 06) utilizam um
+//! `preparo` nomeado, implementado em `glosar_base_de_calculo.rs`.
+//!
+//! As regras aplicadas podem ser escolhidas com `--situacoes` e
+//! `--excluir-situacoes` (ver [`SelecaoDeSituacoes`]); a relação das regras e
+//! do critério de seleção de cada uma é gravada em `df_regras_de_glosa`.
+//!
+//! Todas as regras implementam [`GlosaRule`]: regras próprias de bibliotecas
+//! clientes são acrescentadas com [`RegrasDeGlosa::registrar`].
+//!
+//! Referências a colunas podem ser nomes literais ("CNPJ Base do Remetente")
+//! ou apelidos de `MyColumn` no formato `lado.apelido` ("right.cfop").
+
+use chrono::NaiveDate;
+use polars::prelude::*;
+use regex::Regex;
+use serde::Deserialize;
+use std::{
+    cmp::Reverse,
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    Arguments, COLUNA_TERMO_ENCONTRADO, DicionarioDeTermos, ExprExtension, GlosaRule, JoinError,
+    JoinResult, PREFIXO_VALOR_GLOSADO,
+    Side::{self, Left, Middle, Right},
+    ToLiteralListExpr, Vocabulario, codigo_nat_01, coluna, cst_50_a_56, cst_50_a_66, equal,
+    glosa_rule::validar_regra,
+    glosar_base_de_calculo::{
+        CFOP_DE_ARMAZENAGEM, CFOP_DE_INDUSTRIALIZACAO, obter_preparo,
+        optante_do_simples_nacional_ou_mei,
+    },
+    obter_coluna, operacoes_de_credito, operacoes_de_entrada, operacoes_de_saida, unequal,
+};
+
+/// Regras padrão: as situações originais de glosa.
+pub const REGRAS_PADRAO: &str = include_str!("regras_de_glosa.toml");
+
+/// Colunas da relação de regras de glosa (`df_regras_de_glosa`).
+pub const COLUNAS_REGRAS: [&str; 8] = [
+    "Regra",
+    "Situação",
+    "Descrição",
+    "Base Legal",
+    "Aplicada",
+    "Ordem de Aplicação",
+    "Critério de Seleção",
+    "Arquivo de Regras",
+];
+
+/// Registro das regras de glosa: as lidas do arquivo TOML, seguidas das
+/// registradas pela biblioteca cliente.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegrasDeGlosa {
+    #[serde(rename = "regra", default)]
+    pub regras: Vec<RegraDeGlosa>,
+
+    /// Regras implementadas fora do arquivo de regras (ver [`GlosaRule`]).
+    #[serde(skip)]
+    pub personalizadas: Vec<Arc<dyn GlosaRule>>,
+
+    /// Arquivo de origem das regras (`None`: regras padrão).
+    #[serde(skip)]
+    pub origem: Option<PathBuf>,
+
+    /// Situações escolhidas com `--situacoes` e `--excluir-situacoes`.
+    #[serde(skip)]
+    pub selecao: SelecaoDeSituacoes,
+}
+
+/// Seleção das situações de glosa a serem aplicadas.
+///
+/// Os identificadores são comparados com o `id` e com a `situacao` de cada
+/// regra, após normalização ("1" e "01" são equivalentes; "6" seleciona
+/// "06a" e "06b"). A exclusão prevalece sobre a inclusão.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelecaoDeSituacoes {
+    /// Se informado, apenas estas situações são aplicadas (`--situacoes`).
+    pub incluir: Option<Vec<String>>,
+    /// Situações nunca aplicadas (`--excluir-situacoes`).
+    pub excluir: Vec<String>,
+    /// Situações habilitadas pelos arquivos de entrada ou pela configuração
+    /// (ex.: `--creditos-estornados`), ainda que inativas no arquivo de regras.
+    pub automaticas: Vec<String>,
+}
+
+/// Regra de glosa declarada no arquivo de regras.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RegraDeGlosa {
+    /// Identificador único da regra (ex.: "01", "06a").
+    pub id: String,
+
+    /// Número da situação exibido na mensagem. Por padrão, igual ao `id`.
+    pub situacao: Option<String>,
+
+    pub descricao: String,
+
+    pub base_legal: Option<String>,
+
+    #[serde(default = "ativa_por_padrao")]
+    pub ativa: bool,
+
+    /// Precedência da regra: regras de maior precedência são aplicadas primeiro
+    /// (empates mantêm a ordem do arquivo). Por padrão, zero.
+    ///
+    /// Uma linha atingida por várias regras tem o valor glosado atribuído às
+    /// primeiras regras aplicadas; as seguintes glosam apenas o saldo remanescente.
+    #[serde(default)]
+    pub precedencia: i32,
+
+    /// Procedimento nomeado executado antes da aplicação do filtro.
+    pub preparo: Option<String>,
+
+    /// Vocabulário do dicionário de termos (ver `dicionario_de_termos.rs`).
+    /// O termo encontrado é informado na coluna temporária "Termo Encontrado Temp".
+    pub vocabulario: Option<String>,
+
+    /// Termos do `vocabulario`, obtidos do dicionário de termos.
+    #[serde(skip)]
+    pub termos: Option<Vocabulario>,
+
+    /// Colunas adicionadas antes do filtro e removidas após a aplicação da regra.
+    #[serde(default, rename = "coluna_temporaria")]
+    pub colunas_temporarias: Vec<ColunaTemporaria>,
+
+    /// Colunas criadas pelo `preparo` a serem removidas após a aplicação da regra.
+    #[serde(default)]
+    pub remover_colunas: Vec<String>,
+
+    #[serde(default)]
+    pub mensagem: Vec<Segmento>,
+
+    #[serde(default)]
+    pub ajuste: Ajuste,
+
+    pub filtro: Predicado,
+}
+
+const fn ativa_por_padrao() -> bool {
+    true
+}
+
+/// Coluna temporária: booleana (apenas `quando`) ou com o `valor` informado
+/// nas linhas em que `quando` é verdadeiro (nulo nas demais).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ColunaTemporaria {
+    pub nome: String,
+    pub quando: Predicado,
+    pub valor: Option<Operando>,
+}
+
+/// Predicados combináveis que compõem o filtro de uma regra.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Predicado {
+    /// Todos os predicados devem ser verdadeiros.
+    Todos(Vec<Predicado>),
+    /// Ao menos um dos predicados deve ser verdadeiro.
+    Algum(Vec<Predicado>),
+    Nao(Box<Predicado>),
+    /// Filtro de `filtros.rs` identificado pelo nome (ex.: "operacoes_de_credito").
+    Filtro(String),
+    Nulo(String),
+    NaoNulo(String),
+    /// Coluna booleana (nulos são considerados falsos).
+    Verdadeiro(String),
+    Regex {
+        coluna: String,
+        padrao: String,
+    },
+    EmLista {
+        coluna: String,
+        #[serde(default)]
+        valores: Vec<ValorLiteral>,
+        /// Listas definidas no código (ex.: "CFOP_DE_ARMAZENAGEM").
+        #[serde(default)]
+        constantes: Vec<String>,
+    },
+    /// Intervalo fechado de valores (ex.: CST entre 50 e 56).
+    Faixa {
+        coluna: String,
+        inicio: i64,
+        fim: i64,
+    },
+    /// Datas (ex.: Período de Apuração) no intervalo fechado `[inicio, fim]`,
+    /// informado no formato "AAAA-MM-DD". Limites ausentes não restringem.
+    Periodo {
+        coluna: String,
+        #[serde(default)]
+        inicio: Option<String>,
+        #[serde(default)]
+        fim: Option<String>,
+    },
+    /// Colunas não nulas e iguais.
+    Igual([String; 2]),
+    /// Colunas não nulas e distintas.
+    Diferente([String; 2]),
+    Comparar {
+        esquerda: Operando,
+        operador: Operador,
+        direita: Operando,
+    },
+}
+
+/// Valores literais de `em_lista`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum ValorLiteral {
+    Inteiro(i64),
+    Texto(String),
+}
+
+/// Operandos numéricos: constantes, colunas e expressões simples.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Operando {
+    Numero(f64),
+    Coluna(String),
+    /// Primeiro operando menos os demais.
+    Diferenca {
+        diferenca: Vec<Operando>,
+    },
+    Arredondado {
+        arredondar: Box<Operando>,
+        casas: u32,
+    },
+    /// Menor valor entre os operandos (nulos são ignorados).
+    Minimo {
+        minimo: Vec<Operando>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Operador {
+    #[serde(rename = "==")]
+    Igual,
+    #[serde(rename = "!=")]
+    Diferente,
+    #[serde(rename = "<")]
+    Menor,
+    #[serde(rename = "<=")]
+    MenorOuIgual,
+    #[serde(rename = ">")]
+    Maior,
+    #[serde(rename = ">=")]
+    MaiorOuIgual,
+}
+
+/// Segmentos da mensagem de glosa, unidos por espaço.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Segmento {
+    Texto(String),
+    Coluna(String),
+    /// Valor numérico arredondado para 2 casas decimais.
+    Valor(Operando),
+    Condicional {
+        quando: Predicado,
+        segmentos: Vec<Segmento>,
+    },
+}
+
+/// Novo valor da Base de Cálculo nas linhas glosadas.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ajuste {
+    /// Glosa integral: a Base de Cálculo passa a ser zero.
+    #[default]
+    Zerar,
+    /// Novo valor da Base de Cálculo: valor fixo ou expressão.
+    ///
+    /// Exemplo: `ajuste = { valor = 100.0 }`
+    Valor(Operando),
+    /// Percentual glosado da Base de Cálculo (ex.: item de uso misto).
+    ///
+    /// Exemplo: `ajuste = { percentual = 40 }` (novo valor = 60% da Base de Cálculo)
+    Percentual(f64),
+    /// Valor deduzido da Base de Cálculo, limitado ao valor da própria Base de Cálculo.
+    ///
+    /// Exemplo: `ajuste = { deduzir = "Valor Total de Documentos Vinculados" }`
+    Deduzir(Operando),
+    /// Apenas sinaliza a linha (mensagem e valor glosado zero):
+    /// a Base de Cálculo é mantida.
+    Sinalizar,
+}
+
+impl RegrasDeGlosa {
+    /// Regras padrão incorporadas ao executável.
+    pub fn padrao() -> JoinResult<Self> {
+        Self::from_toml(REGRAS_PADRAO)
+    }
+
+    /// Lê e valida as regras a partir de um texto no formato TOML.
+    pub fn from_toml(texto: &str) -> JoinResult<Self> {
+        let mut regras: RegrasDeGlosa = toml::from_str(texto)?;
+        regras.usar_dicionario(&DicionarioDeTermos::padrao()?)?;
+        regras.validar()?;
+        Ok(regras)
+    }
+
+    /// Obtém do dicionário os termos das regras que informam um `vocabulario`.
+    pub fn usar_dicionario(&mut self, dicionario: &DicionarioDeTermos) -> JoinResult<()> {
+        for regra in &mut self.regras {
+            if let Some(nome) = &regra.vocabulario {
+                let vocabulario: &Vocabulario = dicionario
+                    .vocabulario(nome)
+                    .ok_or_else(|| regra.erro(format!("vocabulário desconhecido '{nome}'")))?;
+                regra.termos = Some(vocabulario.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lê as regras do arquivo informado.
+    pub fn from_path(path: &Path) -> JoinResult<Self> {
+        let texto: String = fs::read_to_string(path)?;
+        let mut regras: RegrasDeGlosa = Self::from_toml(&texto)?;
+        regras.origem = Some(path.to_path_buf());
+        Ok(regras)
+    }
+
+    /// Regras do arquivo `--regras` ou, na ausência deste, as regras padrão,
+    /// com a seleção de `--situacoes` e `--excluir-situacoes`.
+    pub fn carregar(args: &Arguments) -> JoinResult<Self> {
+        Self::carregar_com(args, Vec::new())
+    }
+
+    /// Como [`RegrasDeGlosa::carregar`], acrescentando as regras personalizadas
+    /// antes da seleção, de modo que estas também possam ser escolhidas com
+    /// `--situacoes` e `--excluir-situacoes`.
+    pub fn carregar_com(
+        args: &Arguments,
+        personalizadas: Vec<Arc<dyn GlosaRule>>,
+    ) -> JoinResult<Self> {
+        let mut regras: RegrasDeGlosa = match &args.regras {
+            Some(path) => {
+                println!("Regras de glosa: '{}'\n", path.display());
+                Self::from_path(path)?
+            }
+            None => Self::padrao()?,
+        };
+
+        if args.dicionario.is_some() {
+            regras.usar_dicionario(&DicionarioDeTermos::carregar(args)?)?;
+        }
+
+        for regra in personalizadas {
+            regras.registrar_regra(regra)?;
+        }
+
+        let mut selecao =
+            SelecaoDeSituacoes::new(args.situacoes.as_deref(), args.excluir_situacoes.as_deref());
+
+        // Regras que dependem de arquivos de entrada ou da configuração são habilitadas
+        // quando estes são informados.
+        if args.creditos_estornados.is_some() {
+            selecao
+                .automaticas
+                .extend(regras.com_preparo("creditos_estornados"));
+        }
+
+        if args.atividade_comercial == Some(true) {
+            selecao
+                .automaticas
+                .extend(regras.com_preparo("despesas_de_revenda"));
+        }
+
+        regras.com_selecao(selecao)
+    }
+
+    /// Define as situações a serem aplicadas.
+    ///
+    /// Retorna erro se algum identificador não corresponder a nenhuma regra.
+    pub fn com_selecao(mut self, selecao: SelecaoDeSituacoes) -> JoinResult<Self> {
+        let informados = selecao.incluir.iter().flatten().chain(&selecao.excluir);
+
+        for id in informados {
+            if !self.todas().any(|regra| corresponde(regra, id)) {
+                return Err(JoinError::InvalidGlosaRule {
+                    id: id.clone(),
+                    motivo: "situação não encontrada nas regras de glosa".to_string(),
+                });
+            }
+        }
+
+        self.selecao = selecao;
+        Ok(self)
+    }
+
+    /// Registra uma regra implementada fora do arquivo de regras.
+    ///
+    /// Retorna erro se o identificador já existir ou se as expressões da regra
+    /// não puderem ser compiladas.
+    pub fn registrar(mut self, regra: impl GlosaRule + 'static) -> JoinResult<Self> {
+        self.registrar_regra(Arc::new(regra))?;
+        Ok(self)
+    }
+
+    fn registrar_regra(&mut self, regra: Arc<dyn GlosaRule>) -> JoinResult<()> {
+        if self
+            .todas()
+            .any(|existente| existente.id().eq_ignore_ascii_case(regra.id()))
+        {
+            return Err(JoinError::InvalidGlosaRule {
+                id: regra.id().to_string(),
+                motivo: "identificador duplicado".to_string(),
+            });
+        }
+
+        validar_regra(regra.as_ref())?;
+
+        self.personalizadas.push(regra);
+        Ok(())
+    }
+
+    /// Todas as regras do registro: as do arquivo de regras, seguidas das
+    /// registradas com [`RegrasDeGlosa::registrar`].
+    pub fn todas(&self) -> impl Iterator<Item = &dyn GlosaRule> {
+        self.regras
+            .iter()
+            .map(|regra| regra as &dyn GlosaRule)
+            .chain(self.personalizadas.iter().map(|regra| regra.as_ref()))
+    }
+
+    /// Identificadores das regras que utilizam o preparo informado.
+    fn com_preparo(&self, nome: &str) -> Vec<String> {
+        self.regras
+            .iter()
+            .filter(|regra| regra.preparo.as_deref() == Some(nome))
+            .map(|regra| regra.id.clone())
+            .collect()
+    }
+
+    /// Regras aplicadas, na ordem de aplicação: maior precedência primeiro
+    /// e, em caso de empate, a ordem do arquivo.
+    pub fn ativas(&self) -> impl Iterator<Item = &dyn GlosaRule> {
+        let mut ativas: Vec<&dyn GlosaRule> = self
+            .todas()
+            .filter(|regra| self.criterio(*regra).0)
+            .collect();
+
+        // Ordenação estável: preserva a ordem do arquivo entre regras de mesma precedência.
+        ativas.sort_by_key(|regra| Reverse(regra.precedence()));
+
+        ativas.into_iter()
+    }
+
+    /// Indica se a regra será aplicada e o critério que determinou a escolha.
+    pub fn criterio(&self, regra: &dyn GlosaRule) -> (bool, &'static str) {
+        let selecionada = |ids: &[String]| ids.iter().any(|id| corresponde(regra, id));
+
+        if selecionada(&self.selecao.excluir) {
+            return (false, "excluída por --excluir-situacoes");
+        }
+
+        match &self.selecao.incluir {
+            Some(ids) if selecionada(ids) => (true, "selecionada por --situacoes"),
+            Some(_) => (false, "não selecionada em --situacoes"),
+            None if regra.enabled() => (true, "ativa no arquivo de regras"),
+            None if self.selecao.automaticas.iter().any(|id| id == regra.id()) => (
+                true,
+                "habilitada pelo arquivo de entrada ou pela configuração",
+            ),
+            None => (false, "inativa no arquivo de regras"),
+        }
+    }
+
+    /// Relação de todas as regras, indicando as aplicadas e o critério de seleção.
+    ///
+    /// Acompanha os arquivos finais para que o relatório informe quais regras
+    /// foram aplicadas.
+    pub fn to_dataframe(&self) -> JoinResult<DataFrame> {
+        let origem: String = match &self.origem {
+            Some(path) => path.display().to_string(),
+            None => "regras padrão".to_string(),
+        };
+
+        let mut ids: Vec<&str> = Vec::new();
+        let mut situacoes: Vec<&str> = Vec::new();
+        let mut descricoes: Vec<&str> = Vec::new();
+        let mut bases_legais: Vec<Option<&str>> = Vec::new();
+        let mut aplicadas: Vec<&str> = Vec::new();
+        let mut ordens: Vec<Option<u32>> = Vec::new();
+        let mut criterios: Vec<&str> = Vec::new();
+
+        let ordem_de_aplicacao: Vec<&str> = self.ativas().map(|regra| regra.id()).collect();
+
+        for regra in self.todas() {
+            let (aplicada, criterio) = self.criterio(regra);
+            let ordem: Option<u32> = ordem_de_aplicacao
+                .iter()
+                .position(|id| *id == regra.id())
+                .map(|posicao| posicao as u32 + 1);
+
+            ids.push(regra.id());
+            situacoes.push(regra.situation());
+            descricoes.push(regra.description());
+            bases_legais.push(regra.legal_basis());
+            aplicadas.push(if aplicada { "Sim" } else { "Não" });
+            ordens.push(ordem);
+            criterios.push(criterio);
+        }
+
+        let origens: Vec<&str> = std::iter::repeat_n(origem.as_str(), self.regras.len())
+            .chain(std::iter::repeat_n(
+                "regra registrada",
+                self.personalizadas.len(),
+            ))
+            .collect();
+
+        let df = df! {
+            COLUNAS_REGRAS[0] => ids,
+            COLUNAS_REGRAS[1] => situacoes,
+            COLUNAS_REGRAS[2] => descricoes,
+            COLUNAS_REGRAS[3] => bases_legais,
+            COLUNAS_REGRAS[4] => aplicadas,
+            COLUNAS_REGRAS[5] => ordens,
+            COLUNAS_REGRAS[6] => criterios,
+            COLUNAS_REGRAS[7] => origens,
+        }?;
+
+        Ok(df)
+    }
+
+    /// Imprime as regras aplicadas.
+    pub fn imprimir_selecao(&self) {
+        let ids: Vec<&str> = self.ativas().map(|regra| regra.id()).collect();
+        println!("Situações de glosa aplicadas: {}\n", ids.join(", "));
+    }
+
+    /// Verifica a unicidade dos identificadores e compila todas as regras.
+    fn validar(&self) -> JoinResult<()> {
+        let mut ids: HashSet<&str> = HashSet::new();
+
+        for regra in &self.regras {
+            if !ids.insert(regra.id.as_str()) {
+                return Err(regra.erro("identificador duplicado"));
+            }
+
+            regra.compilar()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl RegraDeGlosa {
+    /// Número da situação exibido na mensagem.
+    pub fn situacao(&self) -> &str {
+        self.situacao.as_deref().unwrap_or(&self.id)
+    }
+
+    /// Compila todas as expressões da regra a fim de detectar erros antecipadamente.
+    fn compilar(&self) -> JoinResult<()> {
+        if let Some(nome) = &self.preparo {
+            obter_preparo(nome).map_err(|motivo| self.erro(motivo))?;
+        }
+
+        if let Some(expr) = self.termo_encontrado() {
+            let _ = expr?;
+        }
+
+        for coluna_temporaria in &self.colunas_temporarias {
+            let _ = coluna_temporaria
+                .expressao()
+                .map_err(|motivo| self.erro(motivo))?;
+        }
+
+        let _ = self.condicao()?;
+        let _ = self.mensagem()?;
+        let _ = self.novo_valor()?;
+
+        Ok(())
+    }
+
+    /// Termo do `vocabulario` encontrado (coluna "Termo Encontrado Temp").
+    fn termo_encontrado(&self) -> Option<JoinResult<Expr>> {
+        let nome: &String = self.vocabulario.as_ref()?;
+
+        let expr = match &self.termos {
+            Some(termos) => termos
+                .termo_encontrado()
+                .map_err(|motivo| self.erro(motivo)),
+            None => Err(self.erro(format!("vocabulário '{nome}' não carregado"))),
+        };
+
+        Some(expr.map(|expr| expr.alias(COLUNA_TERMO_ENCONTRADO)))
+    }
+
+    /// Condição de aplicação da glosa.
+    pub fn condicao(&self) -> JoinResult<Expr> {
+        self.filtro.compilar().map_err(|motivo| self.erro(motivo))
+    }
+
+    /// Texto da mensagem de glosa: segmentos unidos por espaço.
+    pub fn mensagem(&self) -> JoinResult<Expr> {
+        let mut segmentos: Vec<Expr> = Vec::new();
+
+        for segmento in &self.mensagem {
+            segmentos.push(segmento.compilar().map_err(|motivo| self.erro(motivo))?);
+        }
+
+        if segmentos.is_empty() {
+            return Ok(lit(NULL).cast(DataType::String));
+        }
+
+        Ok(concat_str(segmentos, " ", true))
+    }
+
+    /// Novo valor da Base de Cálculo nas linhas glosadas.
+    pub fn novo_valor(&self) -> JoinResult<Expr> {
+        self.ajuste.compilar().map_err(|motivo| self.erro(motivo))
+    }
+
+    /// Coluna com o valor glosado por esta regra.
+    pub fn coluna_valor_glosado(&self) -> String {
+        coluna_valor_glosado(&self.id)
+    }
+
+    fn erro(&self, motivo: impl ToString) -> JoinError {
+        JoinError::InvalidGlosaRule {
+            id: self.id.clone(),
+            motivo: motivo.to_string(),
+        }
+    }
+}
+
+impl GlosaRule for RegraDeGlosa {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn situation(&self) -> &str {
+        self.situacao()
+    }
+
+    fn description(&self) -> &str {
+        &self.descricao
+    }
+
+    fn legal_basis(&self) -> Option<&str> {
+        self.base_legal.as_deref()
+    }
+
+    fn enabled(&self) -> bool {
+        self.ativa
+    }
+
+    fn precedence(&self) -> i32 {
+        self.precedencia
+    }
+
+    /// Executa o `preparo` e adiciona as colunas temporárias.
+    fn prepare(&self, lazyframe: LazyFrame, args: &Arguments) -> JoinResult<LazyFrame> {
+        let mut lazyframe: LazyFrame = match &self.preparo {
+            Some(nome) => {
+                obter_preparo(nome).map_err(|motivo| self.erro(motivo))?(lazyframe, args)?
+            }
+            None => lazyframe,
+        };
+
+        if let Some(expr) = self.termo_encontrado() {
+            lazyframe = lazyframe.with_column(expr?);
+        }
+
+        for coluna_temporaria in &self.colunas_temporarias {
+            lazyframe = lazyframe.with_column(
+                coluna_temporaria
+                    .expressao()
+                    .map_err(|motivo| self.erro(motivo))?,
+            );
+        }
+
+        Ok(lazyframe)
+    }
+
+    fn temporary_columns(&self) -> Vec<String> {
+        self.colunas_temporarias
+            .iter()
+            .map(|coluna_temporaria| coluna_temporaria.nome.clone())
+            .chain(self.remover_colunas.iter().cloned())
+            .chain(
+                self.vocabulario
+                    .as_ref()
+                    .map(|_| COLUNA_TERMO_ENCONTRADO.to_string()),
+            )
+            .collect()
+    }
+
+    fn condition(&self) -> JoinResult<Expr> {
+        self.condicao()
+    }
+
+    fn message(&self) -> JoinResult<Expr> {
+        self.mensagem()
+    }
+
+    fn adjustment(&self) -> JoinResult<Expr> {
+        self.novo_valor()
+    }
+}
+
+/// Verifica se o identificador informado (`--situacoes`) refere-se à regra.
+pub(crate) fn corresponde(regra: &dyn GlosaRule, id: &str) -> bool {
+    let id: String = normalizar_situacao(id);
+    regra.id().eq_ignore_ascii_case(&id) || regra.situation().eq_ignore_ascii_case(&id)
+}
+
+impl SelecaoDeSituacoes {
+    pub fn new(incluir: Option<&[String]>, excluir: Option<&[String]>) -> Self {
+        let normalizar = |ids: &[String]| -> Vec<String> {
+            ids.iter()
+                .map(|id| id.trim())
+                .filter(|id| !id.is_empty())
+                .map(normalizar_situacao)
+                .collect()
+        };
+
+        SelecaoDeSituacoes {
+            incluir: incluir.map(normalizar),
+            excluir: excluir.map(normalizar).unwrap_or_default(),
+            automaticas: Vec::new(),
+        }
+    }
+}
+
+/// Completa com zero à esquerda o número da situação: "1" -> "01", "6a" -> "06a".
+pub fn normalizar_situacao(id: &str) -> String {
+    let id: String = id.trim().to_lowercase();
+    let digitos: usize = id.chars().take_while(char::is_ascii_digit).count();
+
+    if digitos == 1 { format!("0{id}") } else { id }
+}
+
+impl ColunaTemporaria {
+    pub fn expressao(&self) -> Result<Expr, String> {
+        let quando: Expr = self.quando.compilar()?;
+
+        let expr: Expr = match &self.valor {
+            Some(valor) => when(quando).then(valor.compilar()?).otherwise(lit(NULL)),
+            None => quando.fill_null(lit(false)),
+        };
+
+        Ok(expr.alias(self.nome.as_str()))
+    }
+}
+
+impl Predicado {
+    pub fn compilar(&self) -> Result<Expr, String> {
+        let expr: Expr = match self {
+            Predicado::Todos(predicados) => predicados
+                .iter()
+                .map(Predicado::compilar)
+                .reduce(|a, b| Ok(a?.and(b?)))
+                .unwrap_or(Ok(lit(true)))?,
+            Predicado::Algum(predicados) => predicados
+                .iter()
+                .map(Predicado::compilar)
+                .reduce(|a, b| Ok(a?.or(b?)))
+                .unwrap_or(Ok(lit(false)))?,
+            Predicado::Nao(predicado) => predicado.compilar()?.not(),
+            Predicado::Filtro(nome) => filtro_nomeado(nome)?,
+            Predicado::Nulo(nome) => col(resolver_coluna(nome)?).is_null(),
+            Predicado::NaoNulo(nome) => col(resolver_coluna(nome)?).is_not_null(),
+            Predicado::Verdadeiro(nome) => col(resolver_coluna(nome)?).fill_null(lit(false)),
+            Predicado::Regex { coluna, padrao } => {
+                Regex::new(padrao).map_err(|e| format!("regex '{padrao}': {e}"))?;
+                col(resolver_coluna(coluna)?)
+                    .str()
+                    .contains(lit(padrao.as_str()), false)
+            }
+            Predicado::EmLista {
+                coluna,
+                valores,
+                constantes,
+            } => {
+                let series: Series = obter_series(valores, constantes)?;
+                let literal_series: Expr = series.to_list_expr().map_err(|e| e.to_string())?;
+                col(resolver_coluna(coluna)?).is_in(literal_series, true)
+            }
+            Predicado::Faixa {
+                coluna,
+                inicio,
+                fim,
+            } => {
+                let nome: &str = resolver_coluna(coluna)?;
+                col(nome)
+                    .is_not_null()
+                    .and(col(nome).gt_eq(lit(*inicio)))
+                    .and(col(nome).lt_eq(lit(*fim)))
+            }
+            Predicado::Periodo {
+                coluna,
+                inicio,
+                fim,
+            } => {
+                let nome: &str = resolver_coluna(coluna)?;
+                let mut expr: Expr = col(nome).is_not_null();
+                if let Some(inicio) = inicio {
+                    expr = expr.and(col(nome).gt_eq(lit(obter_data(inicio)?)));
+                }
+                if let Some(fim) = fim {
+                    expr = expr.and(col(nome).lt_eq(lit(obter_data(fim)?)));
+                }
+                expr
+            }
+            Predicado::Igual([a, b]) => equal(resolver_coluna(a)?, resolver_coluna(b)?),
+            Predicado::Diferente([a, b]) => unequal(resolver_coluna(a)?, resolver_coluna(b)?),
+            Predicado::Comparar {
+                esquerda,
+                operador,
+                direita,
+            } => {
+                let a: Expr = esquerda.compilar()?;
+                let b: Expr = direita.compilar()?;
+                match operador {
+                    Operador::Igual => a.eq(b),
+                    Operador::Diferente => a.neq(b),
+                    Operador::Menor => a.lt(b),
+                    Operador::MenorOuIgual => a.lt_eq(b),
+                    Operador::Maior => a.gt(b),
+                    Operador::MaiorOuIgual => a.gt_eq(b),
+                }
+            }
+        };
+
+        Ok(expr)
+    }
+}
+
+impl Operando {
+    pub fn compilar(&self) -> Result<Expr, String> {
+        let expr: Expr = match self {
+            Operando::Numero(valor) => lit(*valor),
+            Operando::Coluna(nome) => col(resolver_coluna(nome)?),
+            Operando::Diferenca { diferenca } => {
+                let mut operandos = diferenca.iter().map(Operando::compilar);
+                let primeiro: Expr = operandos
+                    .next()
+                    .ok_or("diferenca sem operandos".to_string())??;
+                operandos.try_fold(primeiro, |acc, operando| {
+                    Ok::<Expr, String>(acc - operando?)
+                })?
+            }
+            Operando::Arredondado { arredondar, casas } => {
+                arredondar.compilar()?.round_expr(*casas)
+            }
+            Operando::Minimo { minimo } => {
+                let mut operandos = minimo.iter().map(Operando::compilar);
+                let primeiro: Expr = operandos
+                    .next()
+                    .ok_or("minimo sem operandos".to_string())??;
+                operandos.try_fold(primeiro, |acc, operando| {
+                    let operando: Expr = operando?;
+                    Ok::<Expr, String>(
+                        when(acc.clone().is_null().or(operando.clone().lt(acc.clone())))
+                            .then(operando)
+                            .otherwise(acc),
+                    )
+                })?
+            }
+        };
+
+        Ok(expr)
+    }
+}
+
+impl Ajuste {
+    pub fn compilar(&self) -> Result<Expr, String> {
+        let valor_bc: Expr = col(coluna(Left, "valor_bc"));
+
+        let expr: Expr = match self {
+            Ajuste::Zerar => lit(0.0),
+            Ajuste::Valor(operando) => operando.compilar()?,
+            Ajuste::Percentual(percentual) => {
+                if !(0.0..=100.0).contains(percentual) {
+                    return Err(format!(
+                        "percentual fora do intervalo [0, 100]: {percentual}"
+                    ));
+                }
+                valor_bc * lit(100.0 - percentual) / lit(100.0)
+            }
+            Ajuste::Deduzir(operando) => {
+                let deducao: Expr = operando.compilar()?.fill_null(lit(0.0));
+                let novo_valor: Expr = valor_bc.clone() - deducao;
+                // Não inverter o sinal da Base de Cálculo.
+                when(
+                    valor_bc
+                        .clone()
+                        .gt_eq(lit(0))
+                        .and(novo_valor.clone().lt(lit(0))),
+                )
+                .then(lit(0.0))
+                .otherwise(novo_valor)
+            }
+            Ajuste::Sinalizar => valor_bc,
+        };
+
+        Ok(expr)
+    }
+}
+
+/// Nome da coluna com o valor glosado pela regra `id`.
+pub fn coluna_valor_glosado(id: &str) -> String {
+    format!("{PREFIXO_VALOR_GLOSADO}{id}")
+}
+
+impl Segmento {
+    pub fn compilar(&self) -> Result<Expr, String> {
+        let expr: Expr = match self {
+            Segmento::Texto(texto) => lit(texto.as_str()),
+            Segmento::Coluna(nome) => col(resolver_coluna(nome)?),
+            Segmento::Valor(operando) => operando.compilar()?.round_expr(2),
+            Segmento::Condicional { quando, segmentos } => {
+                let segmentos: Vec<Expr> = segmentos
+                    .iter()
+                    .map(Segmento::compilar)
+                    .collect::<Result<_, _>>()?;
+
+                when(quando.compilar()?)
+                    .then(concat_str(segmentos, " ", true))
+                    .otherwise(lit(NULL))
+            }
+        };
+
+        Ok(expr)
+    }
+}
+
+/// Resolve referências do tipo `lado.apelido` (ex.: "right.cfop") para o nome da coluna.
+///
+/// Demais referências são consideradas nomes literais de colunas.
+pub fn resolver_coluna(referencia: &str) -> Result<&str, String> {
+    let Some((lado, apelido)) = referencia.split_once('.') else {
+        return Ok(referencia);
+    };
+
+    let side: Side = match lado {
+        "left" => Left,
+        "middle" => Middle,
+        "right" => Right,
+        _ => return Ok(referencia),
+    };
+
+    obter_coluna(side, apelido).ok_or_else(|| format!("coluna desconhecida '{referencia}'"))
+}
+
+/// Filtros de `filtros.rs` disponíveis no arquivo de regras.
+fn filtro_nomeado(nome: &str) -> Result<Expr, String> {
+    let expr: PolarsResult<Expr> = match nome {
+        "operacoes_de_credito" => operacoes_de_credito(),
+        "operacoes_de_entrada" => operacoes_de_entrada(),
+        "operacoes_de_saida" => operacoes_de_saida(),
+        "cst_50_a_56" => cst_50_a_56(),
+        "cst_50_a_66" => cst_50_a_66(),
+        "codigo_nat_01" => codigo_nat_01(),
+        "optante_do_simples_nacional_ou_mei" => {
+            optante_do_simples_nacional_ou_mei().map_err(PolarsError::from)
+        }
+        _ => return Err(format!("filtro desconhecido '{nome}'")),
+    };
+
+    expr.map_err(|e| e.to_string())
+}
+
+/// Listas de valores definidas no código.
+fn constante_nomeada(nome: &str) -> Result<Vec<i64>, String> {
+    let valores: &[i32] = match nome {
+        "CFOP_DE_ARMAZENAGEM" => &CFOP_DE_ARMAZENAGEM,
+        "CFOP_DE_INDUSTRIALIZACAO" => &CFOP_DE_INDUSTRIALIZACAO,
+        _ => return Err(format!("constante desconhecida '{nome}'")),
+    };
+
+    Ok(valores.iter().map(|&v| i64::from(v)).collect())
+}
+
+/// Series com os valores literais e as constantes de `em_lista`.
+fn obter_series(valores: &[ValorLiteral], constantes: &[String]) -> Result<Series, String> {
+    let mut inteiros: Vec<i64> = Vec::new();
+    let mut textos: Vec<String> = Vec::new();
+
+    for valor in valores {
+        match valor {
+            ValorLiteral::Inteiro(v) => inteiros.push(*v),
+            ValorLiteral::Texto(v) => textos.push(v.clone()),
+        }
+    }
+
+    for nome in constantes {
+        inteiros.extend(constante_nomeada(nome)?);
+    }
+
+    match (inteiros.is_empty(), textos.is_empty()) {
+        (false, true) => Ok(Series::new("valores".into(), inteiros)),
+        (true, false) => Ok(Series::new("valores".into(), textos)),
+        (true, true) => Err("em_lista sem valores".to_string()),
+        (false, false) => Err("em_lista com valores de tipos distintos".to_string()),
+    }
+}
+
+/// Data no formato "AAAA-MM-DD" (ex.: início de vigência de uma norma).
+fn obter_data(texto: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(texto.trim(), "%Y-%m-%d")
+        .map_err(|e| format!("data '{texto}' inválida (esperado AAAA-MM-DD): {e}"))
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_regras_de_glosa
+#[cfg(test)]
+mod tests_regras_de_glosa {
+    use super::*;
+
+    #[test]
+    /// `cargo test -- --show-output test_regras_padrao`
+    fn test_regras_padrao() -> JoinResult<()> {
+        let regras = RegrasDeGlosa::padrao()?;
+
+        let ids: Vec<&str> = regras.regras.iter().map(|r| r.id.as_str()).collect();
+        println!("ids: {ids:?}");
+
+        assert_eq!(
+            ids,
+            [
+                "01", "02", "03", "04", "05", "06a", "06b", "06c", "07", "08", "09", "10", "11",
+                "12", "13", "14", "15", "16", "17", "18", "19", "20"
+            ]
+        );
+
+        // Situações desativadas por padrão.
+        let inativas: Vec<&str> = regras
+            .regras
+            .iter()
+            .filter(|r| !r.ativa)
+            .map(|r| r.id.as_str())
+            .collect();
+        assert_eq!(inativas, ["05", "13", "14", "19"]);
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_selecao_de_situacoes`
+    fn test_selecao_de_situacoes() -> JoinResult<()> {
+        let ids =
+            |valores: &[&str]| -> Vec<String> { valores.iter().map(|id| id.to_string()).collect() };
+
+        let incluir = ids(&["1", "2", "6", "13"]);
+        let excluir = ids(&["06B"]);
+        let selecao = SelecaoDeSituacoes::new(Some(&incluir), Some(&excluir));
+
+        let regras = RegrasDeGlosa::padrao()?.com_selecao(selecao)?;
+        let ativas: Vec<&str> = regras.ativas().map(|r| r.id()).collect();
+        assert_eq!(ativas, ["01", "02", "06a", "06c", "13"]);
+
+        let df = regras.to_dataframe()?;
+        println!("df: {df}");
+        assert_eq!(df.height(), regras.regras.len());
+
+        // Apenas exclusão: as demais seguem o arquivo de regras.
+        let selecao = SelecaoDeSituacoes::new(None, Some(&ids(&["01", "14"])));
+        let regras = RegrasDeGlosa::padrao()?.com_selecao(selecao)?;
+        let ativas: Vec<&str> = regras.ativas().map(|r| r.id()).collect();
+        assert_eq!(
+            ativas,
+            [
+                "02", "03", "04", "06a", "06b", "06c", "07", "08", "09", "10", "11", "12", "15",
+                "16", "17", "18", "20"
+            ]
+        );
+
+        // Precedência: a Situação 12 é aplicada antes das demais.
+        let mut regras = RegrasDeGlosa::padrao()?;
+        for regra in &mut regras.regras {
+            if regra.id == "12" {
+                regra.precedencia = 10;
+            }
+        }
+        let ativas: Vec<&str> = regras.ativas().map(|r| r.id()).take(3).collect();
+        assert_eq!(ativas, ["12", "01", "02"]);
+
+        // Situação inexistente.
+        let selecao = SelecaoDeSituacoes::new(Some(&ids(&["99"])), None);
+        assert!(RegrasDeGlosa::padrao()?.com_selecao(selecao).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_ajustes_parciais`
+    fn test_ajustes_parciais() -> JoinResult<()> {
+        let valor_bc: &str = coluna(Left, "valor_bc");
+
+        let df = df! {
+            valor_bc => [100.0, 50.0, -20.0],
+            "Frete" => [Some(30.0), Some(80.0), None],
+        }?;
+
+        let ajustes: [(&str, [f64; 3]); 5] = [
+            ("ajuste = \"zerar\"", [0.0, 0.0, 0.0]),
+            ("ajuste = { valor = 10 }", [10.0, 10.0, 10.0]),
+            ("ajuste = { percentual = 40 }", [60.0, 30.0, -12.0]),
+            ("ajuste = { deduzir = \"Frete\" }", [70.0, 0.0, -20.0]),
+            ("ajuste = \"sinalizar\"", [100.0, 50.0, -20.0]),
+        ];
+
+        #[derive(Deserialize)]
+        struct Teste {
+            ajuste: Ajuste,
+        }
+
+        for (texto, esperado) in ajustes {
+            let teste: Teste = toml::from_str(texto)?;
+            let expr: Expr = teste.ajuste.compilar().map_err(JoinError::Other)?;
+
+            let resultado: Vec<f64> = df
+                .clone()
+                .lazy()
+                .with_column(expr.cast(DataType::Float64).alias("novo_valor"))
+                .collect()?
+                .column("novo_valor")?
+                .f64()?
+                .iter()
+                .flatten()
+                .collect();
+
+            println!("{texto}: {resultado:?}");
+            assert_eq!(resultado, esperado);
+        }
+
+        let invalido: Teste = toml::from_str("ajuste = { percentual = 140 }")?;
+        assert!(invalido.ajuste.compilar().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_regra_invalida`
+    fn test_regra_invalida() {
+        let coluna_desconhecida = r#"
+            [[regra]]
+            id = "99"
+            descricao = "Teste"
+            [regra.filtro]
+            nao_nulo = "right.inexistente"
+        "#;
+        assert!(RegrasDeGlosa::from_toml(coluna_desconhecida).is_err());
+
+        let id_duplicado = r#"
+            [[regra]]
+            id = "99"
+            descricao = "Teste"
+            filtro = { filtro = "operacoes_de_credito" }
+
+            [[regra]]
+            id = "99"
+            descricao = "Teste"
+            filtro = { filtro = "operacoes_de_credito" }
+        "#;
+        assert!(RegrasDeGlosa::from_toml(id_duplicado).is_err());
+
+        let vocabulario_desconhecido = r#"
+            [[regra]]
+            id = "99"
+            descricao = "Teste"
+            vocabulario = "inexistente"
+            filtro = { nao_nulo = "Termo Encontrado Temp" }
+        "#;
+        assert!(RegrasDeGlosa::from_toml(vocabulario_desconhecido).is_err());
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_compilar_predicado`
+    fn test_compilar_predicado() -> JoinResult<()> {
+        let cfop: &str = coluna(Right, "cfop");
+        let descricao_cfop: &str = coluna(Right, "descricao_cfop");
+
+        let df = df! {
+            cfop => [Some(1124), Some(1102), None, Some(5949)],
+            descricao_cfop => [Some("Industrialização"), Some("Compra"), Some("Anulação"), None],
+        }?;
+
+        let texto = r#"
+            todos = [
+                { algum = [{ nulo = "right.cfop" }, { nao = { em_lista = { coluna = "right.cfop", constantes = ["CFOP_DE_ARMAZENAGEM", "CFOP_DE_INDUSTRIALIZACAO"] } } }] },
+                { regex = { coluna = "right.descricao_cfop", padrao = "(?i)Compra|Anula" } },
+            ]
+        "#;
+
+        let predicado: Predicado = toml::from_str(texto)?;
+        let expr: Expr = predicado.compilar().map_err(JoinError::Other)?;
+
+        let resultado: Vec<Option<bool>> = df
+            .lazy()
+            .select([expr.alias("filtro")])
+            .collect()?
+            .column("filtro")?
+            .bool()?
+            .iter()
+            .collect();
+
+        assert_eq!(
+            resultado,
+            [Some(false), Some(true), Some(true), Some(false)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_icms_na_base_de_calculo`
+    fn test_icms_na_base_de_calculo() -> JoinResult<()> {
+        let periodo: &str = coluna(Left, "pa");
+        let tipo_operacao: &str = coluna(Left, "tipo_operacao");
+        let cst: &str = coluna(Left, "cst");
+        let natureza: &str = coluna(Left, "natureza");
+        let valor_bc: &str = coluna(Left, "valor_bc");
+        let valor_item: &str = coluna(Left, "valor_item");
+        let valor_icms: &str = coluna(Right, "valor_icms");
+
+        let datas: Vec<NaiveDate> = [
+            "2023-04-01",
+            "2023-05-01",
+            "2024-01-01",
+            "2024-02-01",
+            "2024-03-01",
+        ]
+        .into_iter()
+        .map(obter_data)
+        .collect::<Result<_, _>>()
+        .map_err(JoinError::Other)?;
+
+        // Linha 1: ICMS incluído, porém anterior à Lei nº 14.592/2023.
+        // Linha 2: ICMS integralmente incluído. Linha 3: parcialmente incluído.
+        // Linha 4: ICMS já excluído da Base de Cálculo.
+        // Linha 5: ICMS incluído em operação de saída (não é operação de crédito).
+        let df = df! {
+            periodo => datas,
+            tipo_operacao => [1, 1, 1, 1, 2],
+            cst => [Some(50), Some(50), Some(50), Some(50), Some(1)],
+            natureza => [Some(1), Some(1), Some(1), Some(1), None],
+            valor_bc => [100.0, 100.0, 90.0, 82.0, 100.0],
+            valor_item => [100.0, 100.0, 100.0, 100.0, 100.0],
+            valor_icms => [18.0, 18.0, 18.0, 18.0, 18.0],
+        }?;
+
+        // Regra "15" de `regras_de_glosa.toml`: filtro e ajuste da regra.
+        let regras = RegrasDeGlosa::padrao()?;
+        let regra = regras
+            .regras
+            .iter()
+            .find(|r| r.id == "15")
+            .ok_or(JoinError::Other("Situação 15 ausente".to_string()))?;
+
+        let resultado: DataFrame = df
+            .lazy()
+            .select([
+                regra.condicao()?.alias("glosar"),
+                regra
+                    .novo_valor()?
+                    .cast(DataType::Float64)
+                    .alias("novo_valor"),
+            ])
+            .collect()?;
+        println!("resultado: {resultado}");
+
+        let glosar: Vec<Option<bool>> = resultado.column("glosar")?.bool()?.iter().collect();
+        assert_eq!(
+            glosar,
+            [
+                Some(false),
+                Some(true),
+                Some(true),
+                Some(false),
+                Some(false)
+            ]
+        );
+
+        // Apenas a parcela do ICMS incluída é glosada: min(ICMS, BC - (Item - ICMS)).
+        let novo_valor: Vec<Option<f64>> = resultado.column("novo_valor")?.f64()?.iter().collect();
+        assert_eq!(novo_valor[1..3], [Some(82.0), Some(82.0)]);
+
+        let data_invalida = r#"periodo = { coluna = "left.pa", inicio = "01/05/2023" }"#;
+        let predicado: Predicado = toml::from_str(data_invalida)?;
+        assert!(predicado.compilar().is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,433 @@
+//! # Relatório de Valores Rejeitados
+//!
+//! Os arquivos são lidos como texto ([`get_lazyframe_from_csv`]), sem ignorar erros
+//! de leitura. Duas espécies de rejeição são registradas (arquivo, linha, coluna,
+//! valor original e erro), de modo que nenhuma informação seja descartada sem rastro:
+//!
+//! - registros cujo número de campos difere do cabeçalho ([`registros_irregulares`]):
+//!   o registro inteiro é rejeitado e excluído do processamento;
+//! - valores que não podem ser convertidos ao tipo esperado: as mesmas expressões de
+//!   conversão ([`ConversaoDeTipo`]) produzem as colunas tipadas e este relatório.
+//!
+//! [`get_lazyframe_from_csv`]: crate::get_lazyframe_from_csv
+
+use polars::prelude::*;
+use std::io::{self, BufRead};
+
+use crate::{
+    Arguments, ConversaoDeTipo, JoinError, JoinResult, LazyFrameExtension, LeituraDoCsv,
+    NULL_VALUES, ToLiteralListExpr,
+};
+
+/// Colunas do relatório de valores rejeitados.
+pub const COLUNAS_REJEITADOS: [&str; 5] = ["Arquivo", "Linha", "Coluna", "Valor", "Erro"];
+
+/// Reúne os valores rejeitados dos arquivos CSV de entrada.
+///
+/// Se o número de rejeições exceder `--max-rejeitados`, o resumo é impresso
+/// e o programa é interrompido antes do pareamento dos arquivos.
+pub fn obter_linhas_rejeitadas(
+    leituras: &[&LeituraDoCsv],
+    args: &Arguments,
+) -> JoinResult<DataFrame> {
+    let lazyframes: Vec<LazyFrame> = leituras
+        .iter()
+        .map(|leitura| leitura.rejeitados.clone())
+        .collect();
+
+    let df_rejeitados: DataFrame = if lazyframes.is_empty() {
+        dataframe_vazio()?
+    } else {
+        concat(lazyframes, UnionArgs::default())?.coletar()?
+    };
+
+    if let Err(error) = verificar_limite_de_rejeitados(&df_rejeitados, args.max_rejeitados) {
+        imprimir_resumo_de_rejeitados(&df_rejeitados)?;
+        return Err(error);
+    }
+
+    Ok(df_rejeitados)
+}
+
+/// Identifica os valores que não puderam ser convertidos pelas `conversoes`.
+///
+/// `texto` contém as colunas do arquivo do tipo String (marcadores de nulo já
+/// substituídos) e a coluna `linha` com o número da linha no arquivo CSV.
+///
+/// O arquivo é percorrido uma única vez: apenas os registros com alguma
+/// rejeição são retidos para compor o relatório.
+pub fn rejeitados_da_conversao(
+    texto: LazyFrame,
+    linha: &str,
+    conversoes: &[ConversaoDeTipo],
+    arquivo: &str,
+) -> PolarsResult<LazyFrame> {
+    if conversoes.is_empty() {
+        return Ok(dataframe_vazio()?.lazy());
+    }
+
+    let null_markers: Expr = Series::new("null_vals".into(), NULL_VALUES).to_list_expr()?;
+    let indicador = |conversao: &ConversaoDeTipo| format!("{} Rejeitado Temp", conversao.coluna);
+
+    let mut selecao: Vec<Expr> = vec![col(linha)];
+    let mut indicadores: Vec<Expr> = Vec::new();
+
+    for conversao in conversoes {
+        let coluna: &str = &conversao.coluna;
+
+        // Valores nulos ou marcadores de nulo (NULL_VALUES) não são considerados rejeições.
+        let valor_informado: Expr = col(coluna).is_not_null().and(
+            col(coluna)
+                .str()
+                .strip_chars(lit(NULL))
+                .is_in(null_markers.clone(), true)
+                .not(),
+        );
+
+        selecao.push(col(coluna));
+        selecao.push(
+            valor_informado
+                .and(conversao.expr.clone().is_null())
+                .alias(indicador(conversao)),
+        );
+        indicadores.push(col(indicador(conversao)));
+    }
+
+    let registros_rejeitados: LazyFrame = texto
+        .select(selecao)
+        .filter(any_horizontal(indicadores)?)
+        .cache(); // Apenas os registros com rejeições
+
+    let lazyframes: Vec<LazyFrame> = conversoes
+        .iter()
+        .map(|conversao| {
+            registros_rejeitados
+                .clone()
+                .filter(col(indicador(conversao)))
+                .select([
+                    lit(arquivo).alias(COLUNAS_REJEITADOS[0]),
+                    col(linha)
+                        .cast(DataType::UInt64)
+                        .alias(COLUNAS_REJEITADOS[1]),
+                    lit(conversao.coluna.as_str()).alias(COLUNAS_REJEITADOS[2]),
+                    col(&conversao.coluna).alias(COLUNAS_REJEITADOS[3]),
+                    lit(conversao.erro.clone()).alias(COLUNAS_REJEITADOS[4]),
+                ])
+        })
+        .collect();
+
+    concat(lazyframes, UnionArgs::default())
+}
+
+/// Registro do arquivo CSV cujo número de campos difere do cabeçalho.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistroIrregular {
+    /// Número da linha (o cabeçalho é a linha 1).
+    pub linha: u64,
+
+    /// Número de campos do registro.
+    pub campos: usize,
+
+    /// Número de campos do cabeçalho.
+    pub esperados: usize,
+
+    /// Conteúdo original do registro.
+    pub conteudo: String,
+}
+
+impl RegistroIrregular {
+    /// Mensagem registrada no relatório de valores rejeitados.
+    pub fn erro(&self) -> String {
+        if self.conteudo.trim().is_empty() {
+            return "Linha em branco".to_string();
+        }
+
+        format!(
+            "Registro com {} campos (o cabeçalho possui {})",
+            self.campos, self.esperados
+        )
+    }
+}
+
+/// Percorre o arquivo CSV, sem carregá-lo em memória, e identifica os registros
+/// cujo número de campos difere do cabeçalho.
+///
+/// Campos entre aspas podem conter o delimitador e quebras de linha. Assim como
+/// no leitor CSV do Polars, a contagem das linhas considera cada registro
+/// (o cabeçalho é a linha 1) e as linhas em branco são registros de um único campo.
+pub fn registros_irregulares<R: BufRead>(
+    mut reader: R,
+    delimitador: u8,
+) -> io::Result<Vec<RegistroIrregular>> {
+    let mut irregulares: Vec<RegistroIrregular> = Vec::new();
+    let mut esperados: Option<usize> = None;
+    let mut linha: u64 = 0;
+
+    let mut registro: Vec<u8> = Vec::new();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut campos: usize = 1;
+    let mut entre_aspas: bool = false;
+
+    loop {
+        buffer.clear();
+        let fim_do_arquivo: bool = reader.read_until(b'\n', &mut buffer)? == 0;
+
+        if !fim_do_arquivo {
+            for &byte in &buffer {
+                if byte == b'"' {
+                    entre_aspas = !entre_aspas;
+                } else if byte == delimitador && !entre_aspas {
+                    campos += 1;
+                }
+            }
+
+            registro.extend_from_slice(&buffer);
+
+            if entre_aspas {
+                continue; // O campo entre aspas continua na próxima linha
+            }
+        }
+
+        if !registro.is_empty() {
+            linha += 1;
+
+            match esperados {
+                None => esperados = Some(campos),
+                Some(esperados) if campos != esperados => {
+                    irregulares.push(RegistroIrregular {
+                        linha,
+                        campos,
+                        esperados,
+                        conteudo: String::from_utf8_lossy(registro.trim_ascii_end()).into_owned(),
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+
+        if fim_do_arquivo {
+            break;
+        }
+
+        registro.clear();
+        campos = 1;
+    }
+
+    Ok(irregulares)
+}
+
+/// Relatório de rejeitados dos registros irregulares do `arquivo`.
+pub fn rejeitados_dos_registros(
+    irregulares: &[RegistroIrregular],
+    arquivo: &str,
+) -> PolarsResult<DataFrame> {
+    df! {
+        COLUNAS_REJEITADOS[0] => vec![arquivo; irregulares.len()],
+        COLUNAS_REJEITADOS[1] => irregulares.iter().map(|registro| registro.linha).collect::<Vec<u64>>(),
+        COLUNAS_REJEITADOS[2] => vec![None::<&str>; irregulares.len()],
+        COLUNAS_REJEITADOS[3] => irregulares.iter().map(|registro| registro.conteudo.as_str()).collect::<Vec<&str>>(),
+        COLUNAS_REJEITADOS[4] => irregulares.iter().map(RegistroIrregular::erro).collect::<Vec<String>>(),
+    }
+}
+
+/// DataFrame sem linhas com as colunas do relatório de rejeitados.
+fn dataframe_vazio() -> PolarsResult<DataFrame> {
+    df! {
+        COLUNAS_REJEITADOS[0] => Vec::<String>::new(),
+        COLUNAS_REJEITADOS[1] => Vec::<u64>::new(),
+        COLUNAS_REJEITADOS[2] => Vec::<String>::new(),
+        COLUNAS_REJEITADOS[3] => Vec::<String>::new(),
+        COLUNAS_REJEITADOS[4] => Vec::<String>::new(),
+    }
+}
+
+/// Imprime a contagem de valores rejeitados por arquivo e coluna.
+pub fn imprimir_resumo_de_rejeitados(df_rejeitados: &DataFrame) -> JoinResult<()> {
+    if df_rejeitados.height() == 0 {
+        println!("Valores rejeitados na leitura dos arquivos CSV: 0\n");
+        return Ok(());
+    }
+
+    let resumo: DataFrame = df_rejeitados
+        .clone()
+        .lazy()
+        .group_by([col(COLUNAS_REJEITADOS[0]), col(COLUNAS_REJEITADOS[2])])
+        .agg([len().alias("Nº de Rejeições")])
+        .sort(
+            [COLUNAS_REJEITADOS[0], COLUNAS_REJEITADOS[2]],
+            SortMultipleOptions::default().with_maintain_order(true),
+        )
+        .collect()?;
+
+    println!(
+        "Valores rejeitados na leitura dos arquivos CSV: {}\n{resumo}\n",
+        df_rejeitados.height()
+    );
+
+    Ok(())
+}
+
+/// Interrompe o processamento quando o número de rejeições excede o limite informado.
+pub fn verificar_limite_de_rejeitados(
+    df_rejeitados: &DataFrame,
+    limite: Option<usize>,
+) -> JoinResult<()> {
+    match limite {
+        Some(limite) if df_rejeitados.height() > limite => Err(JoinError::RejectedLimitExceeded {
+            total: df_rejeitados.height(),
+            limite,
+        }),
+        _ => Ok(()),
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_rejeitados
+#[cfg(test)]
+mod tests_rejeitados {
+    use super::*;
+    use crate::{Side, get_lazyframe_from_csv};
+    use std::{fs::File, io::Write};
+    use tempfile::tempdir;
+
+    #[test]
+    /// `cargo test -- --show-output test_rejeitados_da_conversao`
+    fn test_rejeitados_da_conversao() -> JoinResult<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("efd.csv");
+        // Linha 5: linha em branco.
+        // Linha 6: campo entre aspas com quebra de linha (um único registro).
+        // Linhas 7 e 8: registros com campos a menos e a mais.
+        let csv_content = "Linhas|Valor Total do Item|Período de Apuração|Registro\n\
+             1|10.5|2022-01-01|C170\n\
+             x2|abc|2022-13-01|C170\n\
+             <N/D>||2022-02-01|C170\n\
+             \n\
+             5|1.0|2022-01-01|\"C1\n70\"\n\
+             6|2.0\n\
+             7|3.0|2022-01-01|C170|extra\n\
+             8|zz|2022-01-01|C170\n";
+        File::create(&file_path)?.write_all(csv_content.as_bytes())?;
+
+        let leitura = get_lazyframe_from_csv(Some(file_path), Some('|'), Side::Left)?;
+
+        // Os registros irregulares são excluídos; os valores rejeitados tornam-se nulos.
+        let df_convertido = leitura.lazyframe.collect()?;
+        println!("df_convertido: {df_convertido}");
+        assert_eq!(df_convertido.height(), 5);
+        assert_eq!(
+            df_convertido.column("Registro")?.str()?.get(3),
+            Some("C1\n70")
+        );
+        assert_eq!(df_convertido.column("Linhas")?.null_count(), 2);
+        assert_eq!(df_convertido.column("Período de Apuração")?.null_count(), 1);
+        assert_eq!(df_convertido.column("Valor Total do Item")?.null_count(), 3);
+
+        let df = leitura
+            .rejeitados
+            .sort(
+                [COLUNAS_REJEITADOS[1], COLUNAS_REJEITADOS[2]],
+                SortMultipleOptions::default().with_nulls_last(true),
+            )
+            .collect()?;
+
+        println!("df: {df}");
+
+        // Linha 3: "x2" (UInt64), "abc" (Float64) e "2022-13-01" (Date)
+        // Linhas 5, 7 e 8: registros irregulares; linha 9: "zz" (Float64)
+        let linhas: Vec<u64> = df
+            .column(COLUNAS_REJEITADOS[1])?
+            .u64()?
+            .iter()
+            .flatten()
+            .collect();
+        assert_eq!(linhas, vec![3, 3, 3, 5, 7, 8, 9]);
+
+        let colunas: Vec<Option<&str>> = df.column(COLUNAS_REJEITADOS[2])?.str()?.iter().collect();
+        assert_eq!(
+            colunas,
+            vec![
+                Some("Linhas"),
+                Some("Período de Apuração"),
+                Some("Valor Total do Item"),
+                None,
+                None,
+                None,
+                Some("Valor Total do Item")
+            ]
+        );
+
+        let erros: Vec<&str> = df
+            .column(COLUNAS_REJEITADOS[4])?
+            .str()?
+            .iter()
+            .flatten()
+            .collect();
+        assert_eq!(erros[3], "Linha em branco");
+        assert_eq!(erros[4], "Registro com 2 campos (o cabeçalho possui 4)");
+        assert_eq!(erros[5], "Registro com 5 campos (o cabeçalho possui 4)");
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_registros_irregulares`
+    fn test_registros_irregulares() -> JoinResult<()> {
+        let texto = "a;b;c\r\n\
+                     1;\"x;y\";3\r\n\
+                     2;\"linha\r\nquebrada\";3\r\n\
+                     3;4\r\n\
+                     \r\n\
+                     4;5;6;7";
+
+        let irregulares = registros_irregulares(texto.as_bytes(), b';')?;
+
+        assert_eq!(
+            irregulares,
+            [
+                RegistroIrregular {
+                    linha: 4,
+                    campos: 2,
+                    esperados: 3,
+                    conteudo: "3;4".to_string(),
+                },
+                RegistroIrregular {
+                    linha: 5,
+                    campos: 1,
+                    esperados: 3,
+                    conteudo: String::new(),
+                },
+                RegistroIrregular {
+                    linha: 6,
+                    campos: 4,
+                    esperados: 3,
+                    conteudo: "4;5;6;7".to_string(),
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_verificar_limite_de_rejeitados`
+    fn test_verificar_limite_de_rejeitados() -> JoinResult<()> {
+        let df = df! {
+            COLUNAS_REJEITADOS[0] => ["a.csv", "a.csv"],
+            COLUNAS_REJEITADOS[1] => [2u64, 3],
+            COLUNAS_REJEITADOS[2] => ["Linhas", "Linhas"],
+            COLUNAS_REJEITADOS[3] => ["x", "y"],
+            COLUNAS_REJEITADOS[4] => ["erro", "erro"],
+        }?;
+
+        assert!(verificar_limite_de_rejeitados(&df, None).is_ok());
+        assert!(verificar_limite_de_rejeitados(&df, Some(2)).is_ok());
+        assert!(verificar_limite_de_rejeitados(&df, Some(1)).is_err());
+
+        Ok(())
+    }
+}
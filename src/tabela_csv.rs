@@ -0,0 +1,170 @@
+//! # Tabelas CSV auxiliares
+//!
+//! Leitura dos arquivos CSV informados pelo Auditor (`--creditos-estornados`,
+//! `--excecoes`) com o mesmo leitor CSV do Polars utilizado para os arquivos
+//! de entrada: campos entre aspas podem conter o delimitador.
+//!
+//! Todas as colunas são lidas como texto. Os nomes das colunas são
+//! comparados sem distinção entre maiúsculas e minúsculas.
+//!
+//! Registros com número de campos diferente do cabeçalho são rejeitados,
+//! com a indicação do número da linha (ver `registros_irregulares`).
+
+use polars::prelude::*;
+use std::io::Cursor;
+
+use crate::registros_irregulares;
+
+/// Delimitadores aceitos, identificados pelo cabeçalho.
+const DELIMITADORES: [u8; 4] = [b';', b',', b'|', b'\t'];
+
+/// Tabela CSV com todas as colunas do tipo texto.
+#[derive(Debug, Clone)]
+pub struct TabelaCsv {
+    dataframe: DataFrame,
+}
+
+impl TabelaCsv {
+    /// Interpreta o texto CSV. O delimitador é identificado pelo cabeçalho.
+    pub fn from_texto(texto: &str) -> Result<Self, String> {
+        let original: &str = texto.trim_start_matches('\u{feff}');
+        let texto: &str = original.trim_start();
+
+        // Linhas em branco removidas do início do arquivo.
+        let deslocamento: u64 = original[..original.len() - texto.len()]
+            .matches('\n')
+            .count() as u64;
+
+        let cabecalho: &str = texto
+            .lines()
+            .next()
+            .filter(|linha| !linha.trim().is_empty())
+            .ok_or("arquivo vazio")?;
+
+        let delimitador: u8 = DELIMITADORES
+            .into_iter()
+            .find(|d| cabecalho.as_bytes().contains(d))
+            .unwrap_or(b';');
+
+        // Linhas em branco são ignoradas; os demais registros devem ter o número
+        // de campos do cabeçalho.
+        let irregulares: Vec<String> = registros_irregulares(texto.as_bytes(), delimitador)
+            .map_err(|erro| format!("CSV inválido: {erro}"))?
+            .iter()
+            .filter(|registro| !registro.conteudo.trim().is_empty())
+            .map(|registro| {
+                format!(
+                    "linha {}: {}",
+                    registro.linha + deslocamento,
+                    registro.erro()
+                )
+            })
+            .collect();
+
+        if !irregulares.is_empty() {
+            return Err(format!("CSV inválido: {}", irregulares.join("; ")));
+        }
+
+        let mut dataframe: DataFrame = CsvReadOptions::default()
+            .with_has_header(true)
+            .with_infer_schema_length(Some(0)) // Todas as colunas do tipo String
+            .map_parse_options(|opcoes| {
+                opcoes
+                    .with_separator(delimitador)
+                    .with_quote_char(Some(b'"'))
+                    .with_encoding(CsvEncoding::LossyUtf8)
+                    .with_missing_is_null(true)
+            })
+            .into_reader_with_file_handle(Cursor::new(texto.as_bytes()))
+            .finish()
+            .map_err(|erro| format!("CSV inválido: {erro}"))?;
+
+        let nomes: Vec<PlSmallStr> = dataframe
+            .get_column_names()
+            .into_iter()
+            .map(|nome| nome.trim().to_lowercase().into())
+            .collect();
+
+        dataframe
+            .set_column_names(&nomes)
+            .map_err(|erro| format!("CSV inválido: {erro}"))?;
+
+        Ok(TabelaCsv { dataframe })
+    }
+
+    /// Número de registros (sem o cabeçalho).
+    pub fn height(&self) -> usize {
+        self.dataframe.height()
+    }
+
+    /// Verifica se o cabeçalho contém a coluna.
+    pub fn contem(&self, nome: &str) -> bool {
+        self.dataframe.column(nome).is_ok()
+    }
+
+    /// Valor não vazio do campo `nome` no registro `indice`.
+    pub fn campo(&self, nome: &str, indice: usize) -> Option<&str> {
+        self.dataframe
+            .column(nome)
+            .ok()?
+            .str()
+            .ok()?
+            .get(indice)
+            .map(str::trim)
+            .filter(|valor| !valor.is_empty())
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_tabela_csv
+#[cfg(test)]
+mod tests_tabela_csv {
+    use super::*;
+
+    #[test]
+    /// `cargo test -- --show-output test_campo_entre_aspas`
+    fn test_campo_entre_aspas() {
+        let texto = "Linha;Justificativa\n\
+                     95217;\"Nota de crédito; comprovada\"\n\
+                     ;\n";
+
+        let tabela = TabelaCsv::from_texto(texto).unwrap();
+
+        assert_eq!(tabela.height(), 2);
+        assert!(tabela.contem("linha"));
+        assert_eq!(tabela.campo("linha", 0), Some("95217"));
+        assert_eq!(
+            tabela.campo("justificativa", 0),
+            Some("Nota de crédito; comprovada")
+        );
+        assert_eq!(tabela.campo("linha", 1), None);
+        assert_eq!(tabela.campo("inexistente", 0), None);
+
+        assert!(TabelaCsv::from_texto("").is_err());
+    }
+
+    #[test]
+    /// `cargo test -- --show-output test_registros_com_campos_a_mais_ou_a_menos`
+    fn test_registros_com_campos_a_mais_ou_a_menos() {
+        let texto = "\n\
+                     Linha;Situacao;Justificativa\n\
+                     95217;07;Nota de crédito\n\
+                     95218;07\n\
+                     \n\
+                     95219;07;Devolução;comprovada\n";
+
+        let erro: String = TabelaCsv::from_texto(texto).unwrap_err();
+        println!("erro: {erro}");
+
+        assert_eq!(
+            erro,
+            "CSV inválido: \
+             linha 4: Registro com 2 campos (o cabeçalho possui 3); \
+             linha 6: Registro com 4 campos (o cabeçalho possui 3)"
+        );
+    }
+}
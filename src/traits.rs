@@ -3,9 +3,9 @@ use std::collections::HashSet;
 use polars::prelude::*;
 
 use crate::{
-    MyColumn,
+    LINHAS_DE_AMOSTRA, MyColumn,
     Side::{Left, Middle, Right},
-    coluna, get_cnpj_base_expr,
+    coluna, engine, get_cnpj_base_expr, is_coluna_de_glosa,
 };
 
 /// Extension trait for [`ListNameSpace`] to provide element-wise deduplication.
@@ -153,6 +153,12 @@ pub trait LazyFrameExtension {
     fn drop_columns(self, columns_to_drop: &[&str]) -> PolarsResult<Self>
     where
         Self: std::marker::Sized;
+
+    /// Executa o plano com o motor configurado em `configurar_execucao()`.
+    fn coletar(self) -> PolarsResult<DataFrame>;
+
+    /// Obtém as primeiras linhas do `LazyFrame` para exibição no terminal.
+    fn amostra(self) -> PolarsResult<DataFrame>;
 }
 
 impl LazyFrameExtension for LazyFrame {
@@ -253,6 +259,16 @@ impl LazyFrameExtension for LazyFrame {
             Ok(self.drop(by_name(existing_columns_to_drop, true, true)))
         }
     }
+
+    fn coletar(self) -> PolarsResult<DataFrame> {
+        // `QueryResult::Multiple` ocorre apenas em planos com múltiplos destinos (sinks).
+        self.collect_with_engine(engine())
+            .map(|resultado| resultado.unwrap_single())
+    }
+
+    fn amostra(self) -> PolarsResult<DataFrame> {
+        self.limit(LINHAS_DE_AMOSTRA).coletar()
+    }
 }
 
 /// A trait to convert a Polars Series into a literal List Expression.
@@ -388,7 +404,9 @@ where
 pub trait DataFrameExtension {
     /// Reorders the DataFrame columns according to a predefined canonical order.
     ///
-    /// Columns present in the DataFrame but not in the canonical order are omitted.
+    /// Columns present in the DataFrame but not in the canonical order are omitted,
+    /// except the dynamic glosa columns (see `is_coluna_de_glosa`), which are placed
+    /// after the Middle columns.
     /// Columns present in the canonical order but not in the DataFrame are ignored.
     /// The canonical order is typically defined externally (e.g., `MyColumn::get_columns()`).
     ///
@@ -411,7 +429,7 @@ impl DataFrameExtension for DataFrame {
 
         // Filter the canonical column list to include only those present in the DataFrame.
         // Then extract just the names in the desired order.
-        let columns_to_select: Vec<&str> = MyColumn::get_columns()
+        let mut columns_to_select: Vec<&str> = MyColumn::get_columns()
             .iter()
             // Keep only columns from the canonical list that actually exist in the DataFrame
             .filter(|col| current_columns.contains(col.name))
@@ -431,6 +449,24 @@ impl DataFrameExtension for DataFrame {
             })
             .collect();
 
+        // Dynamic glosa columns (one per rule) are placed after the Middle columns.
+        let dynamic_columns: Vec<&str> = self
+            .get_column_names()
+            .into_iter()
+            .map(|name| name.as_str())
+            .filter(|name| is_coluna_de_glosa(name))
+            .collect();
+
+        if !dynamic_columns.is_empty() {
+            let side_map = MyColumn::get_side_map();
+            let position: usize = columns_to_select
+                .iter()
+                .rposition(|name| side_map.get(name) == Some(&Middle))
+                .map_or(columns_to_select.len(), |index| index + 1);
+
+            columns_to_select.splice(position..position, dynamic_columns);
+        }
+
         // Perform the select operation with the ordered list of existing columns.
         // Using df.select ensures only specified columns are kept and they are in the specified order.
         self.select(columns_to_select)
@@ -0,0 +1,198 @@
+//! # Trilha de Auditoria
+//!
+//! Tabela no formato longo (linha da EFD × regra) que acompanha o
+//! Termo de Verificação Fiscal: para cada glosa, a linha da EFD, a chave,
+//! o período de apuração, a situação, a base legal e os valores da Base de
+//! Cálculo antes e após a glosa.
+//!
+//! A tabela é derivada das colunas `Valor Glosado: Situação <id>` produzidas
+//! por `glosar_bc`, de modo que nunca diverge da aba de itens: os valores
+//! "antes" e "após" de cada regra são reconstruídos a partir da Base de Cálculo
+//! original e dos valores glosados pelas regras aplicadas anteriormente.
+
+use polars::prelude::*;
+
+use crate::{
+    ExprExtension, JoinResult, RegrasDeGlosa,
+    Side::{Left, Middle},
+    coluna, coluna_valor_glosado,
+};
+
+/// Colunas da trilha de auditoria (além das colunas de identificação da linha).
+pub const COLUNAS_TRILHA: [&str; 7] = [
+    "Ordem de Aplicação",
+    "Regra",
+    "Situação",
+    "Base Legal",
+    "Valor da Base de Cálculo antes da Glosa",
+    "Valor da Base de Cálculo após a Glosa",
+    "Valor Glosado",
+];
+
+/// Obtém a trilha de auditoria a partir do resultado de `glosar_bc`.
+///
+/// Cada linha da tabela corresponde a uma regra que atingiu uma linha da EFD.
+pub fn obter_trilha_de_auditoria(
+    df_itens_result: &DataFrame,
+    regras: &RegrasDeGlosa,
+) -> JoinResult<DataFrame> {
+    let num_linha: &str = coluna(Left, "num_linha");
+    let efd_linha: &str = coluna(Left, "efd_linha");
+    let chave: &str = coluna(Left, "chave");
+    let periodo: &str = coluna(Left, "pa");
+    let valor_bc: &str = coluna(Left, "valor_bc");
+    let valor_glosado: &str = coluna(Middle, "valor_glosado");
+
+    let lazyframe: LazyFrame = df_itens_result.clone().lazy();
+
+    // Base de Cálculo antes da primeira glosa.
+    let valor_original: Expr = col(valor_bc) + col(valor_glosado).fill_null(lit(0.0));
+
+    // Valor glosado pelas regras aplicadas anteriormente.
+    let mut glosado_anterior: Expr = lit(0.0);
+
+    let mut lazyframes: Vec<LazyFrame> = Vec::new();
+
+    for (indice, regra) in regras.ativas().enumerate() {
+        let glosado: String = coluna_valor_glosado(regra.id());
+
+        // Coluna ausente: a regra não foi aplicada.
+        if df_itens_result.column(&glosado).is_err() {
+            continue;
+        }
+
+        let antes: Expr = valor_original.clone() - glosado_anterior.clone();
+
+        lazyframes.push(
+            lazyframe
+                .clone()
+                .filter(col(&glosado).is_not_null())
+                .select([
+                    col(num_linha),
+                    col(efd_linha),
+                    col(chave),
+                    col(periodo),
+                    lit(indice as u32 + 1).alias(COLUNAS_TRILHA[0]),
+                    lit(regra.id()).alias(COLUNAS_TRILHA[1]),
+                    lit(regra.situation()).alias(COLUNAS_TRILHA[2]),
+                    lit(regra.legal_basis().unwrap_or_default()).alias(COLUNAS_TRILHA[3]),
+                    antes.clone().alias(COLUNAS_TRILHA[4]),
+                    (antes - col(&glosado)).alias(COLUNAS_TRILHA[5]),
+                    col(&glosado).alias(COLUNAS_TRILHA[6]),
+                ]),
+        );
+
+        glosado_anterior = glosado_anterior + col(&glosado).fill_null(lit(0.0));
+    }
+
+    if lazyframes.is_empty() {
+        return Ok(dataframe_vazio(df_itens_result)?);
+    }
+
+    let df_trilha: DataFrame = concat(lazyframes, UnionArgs::default())?
+        .sort(
+            [num_linha, COLUNAS_TRILHA[0]],
+            SortMultipleOptions::default().with_maintain_order(true),
+        )
+        .with_columns([
+            col(COLUNAS_TRILHA[4]).round_expr(2),
+            col(COLUNAS_TRILHA[5]).round_expr(2),
+        ])
+        .collect()?;
+
+    println!("Trilha de Auditoria: {} glosas\n", df_trilha.height());
+
+    Ok(df_trilha)
+}
+
+/// Trilha de auditoria sem linhas, com as mesmas colunas.
+fn dataframe_vazio(df_itens_result: &DataFrame) -> PolarsResult<DataFrame> {
+    let identificacao: Vec<&str> = vec![
+        coluna(Left, "num_linha"),
+        coluna(Left, "efd_linha"),
+        coluna(Left, "chave"),
+        coluna(Left, "pa"),
+    ];
+
+    let mut df: DataFrame = df_itens_result.select(identificacao)?.clear();
+
+    for (indice, nome) in COLUNAS_TRILHA.iter().enumerate() {
+        let dtype: DataType = match indice {
+            0 => DataType::UInt32,
+            1..=3 => DataType::String,
+            _ => DataType::Float64,
+        };
+        df.with_column(Column::new_empty((*nome).into(), &dtype))?;
+    }
+
+    Ok(df)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// cargo test -- --show-output tests_trilha_de_auditoria
+#[cfg(test)]
+mod tests_trilha_de_auditoria {
+    use super::*;
+    use crate::SelecaoDeSituacoes;
+
+    #[test]
+    /// `cargo test -- --show-output test_trilha_de_auditoria`
+    fn test_trilha_de_auditoria() -> JoinResult<()> {
+        let incluir: Vec<String> = vec!["03".to_string(), "04".to_string()];
+        let selecao = SelecaoDeSituacoes::new(Some(&incluir), None);
+        let regras = RegrasDeGlosa::padrao()?.com_selecao(selecao)?;
+
+        let glosado_03: String = coluna_valor_glosado("03");
+        let glosado_04: String = coluna_valor_glosado("04");
+
+        // Linha 1: Situação 03 (glosa integral). Linha 2: Situação 04 (apenas o frete).
+        // Linha 3: sem glosa.
+        let df_itens_result = df! {
+            coluna(Left, "num_linha") => [1u64, 2, 3],
+            coluna(Left, "efd_linha") => [10u64, 20, 30],
+            coluna(Left, "chave") => ["a", "b", "c"],
+            coluna(Left, "pa") => ["2023-01-01", "2023-01-01", "2023-02-01"],
+            coluna(Left, "valor_bc") => [0.0, 70.0, 50.0],
+            coluna(Middle, "valor_glosado") => [100.0, 30.0, 0.0],
+            glosado_03.as_str() => [Some(100.0), None, None],
+            glosado_04.as_str() => [None, Some(30.0), None],
+        }?;
+
+        let df_trilha = obter_trilha_de_auditoria(&df_itens_result, &regras)?;
+        println!("df_trilha: {df_trilha}");
+
+        assert_eq!(df_trilha.height(), 2);
+
+        let antes: Vec<Option<f64>> = df_trilha.column(COLUNAS_TRILHA[4])?.f64()?.iter().collect();
+        let apos: Vec<Option<f64>> = df_trilha.column(COLUNAS_TRILHA[5])?.f64()?.iter().collect();
+
+        assert_eq!(antes, [Some(100.0), Some(100.0)]);
+        assert_eq!(apos, [Some(0.0), Some(70.0)]);
+
+        // A soma dos valores glosados coincide com a aba de itens.
+        let total_trilha: f64 = df_trilha
+            .column(COLUNAS_TRILHA[6])?
+            .f64()?
+            .sum()
+            .unwrap_or(0.0);
+        let total_itens: f64 = df_itens_result
+            .column(coluna(Middle, "valor_glosado"))?
+            .f64()?
+            .sum()
+            .unwrap_or(0.0);
+        assert_eq!(total_trilha, total_itens);
+
+        let vazio = obter_trilha_de_auditoria(
+            &df_itens_result.drop(&glosado_03)?.drop(&glosado_04)?,
+            &regras,
+        )?;
+        assert_eq!(vazio.height(), 0);
+        assert_eq!(vazio.width(), 4 + COLUNAS_TRILHA.len());
+
+        Ok(())
+    }
+}